@@ -0,0 +1,4 @@
+pub mod binary_io;
+pub mod huffman;
+pub mod lzw;
+pub mod run_length;
@@ -0,0 +1,142 @@
+//! # Binary input and output
+//!
+//! The bit-level streams the compression algorithms read and write, in place of the
+//! book's `BinaryStdIn` and `BinaryStdOut`. Bits are packed most significant first,
+//! and the last byte of the output is padded with 0 bits.
+
+pub struct BinaryOut {
+    bytes: Vec<u8>,
+    buffer: u8, // bits not yet written out
+    n: usize,   // number of bits in the buffer
+}
+
+impl BinaryOut {
+    pub fn new() -> Self {
+        BinaryOut {
+            bytes: Vec::new(),
+            buffer: 0,
+            n: 0,
+        }
+    }
+
+    pub fn write_bit(&mut self, bit: bool) {
+        self.buffer = (self.buffer << 1) | bit as u8;
+        self.n += 1;
+        if self.n == 8 {
+            self.bytes.push(self.buffer);
+            self.buffer = 0;
+            self.n = 0;
+        }
+    }
+
+    /// Writes the `r` low-order bits of `x`, the most significant first.
+    pub fn write_bits(&mut self, x: u32, r: usize) {
+        assert!(r <= 32, "cannot write {} bits of a u32", r);
+        for i in (0..r).rev() {
+            self.write_bit((x >> i) & 1 == 1);
+        }
+    }
+
+    pub fn write_byte(&mut self, b: u8) {
+        self.write_bits(b as u32, 8);
+    }
+
+    /// Flushes the buffered bits, padded with 0s to a whole byte, and returns the output.
+    pub fn into_bytes(mut self) -> Vec<u8> {
+        if self.n > 0 {
+            self.bytes.push(self.buffer << (8 - self.n));
+        }
+        self.bytes
+    }
+}
+
+impl Default for BinaryOut {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub struct BinaryIn<'a> {
+    bytes: &'a [u8],
+    pos: usize, // index of the next bit
+}
+
+impl<'a> BinaryIn<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        BinaryIn { bytes, pos: 0 }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pos == self.bytes.len() * 8
+    }
+
+    /// Returns the number of bits not yet read.
+    pub fn remaining(&self) -> usize {
+        self.bytes.len() * 8 - self.pos
+    }
+
+    pub fn read_bit(&mut self) -> Option<bool> {
+        let byte = *self.bytes.get(self.pos / 8)?;
+        let bit = (byte >> (7 - self.pos % 8)) & 1 == 1;
+        self.pos += 1;
+        Some(bit)
+    }
+
+    /// Reads `r` bits as the low-order bits of a `u32`, or `None` if fewer are left.
+    pub fn read_bits(&mut self, r: usize) -> Option<u32> {
+        assert!(r <= 32, "cannot read {} bits into a u32", r);
+        if self.pos + r > self.bytes.len() * 8 {
+            return None;
+        }
+        let mut x = 0;
+        for _ in 0..r {
+            x = (x << 1) | self.read_bit()? as u32;
+        }
+        Some(x)
+    }
+
+    pub fn read_byte(&mut self) -> Option<u8> {
+        self.read_bits(8).map(|x| x as u8)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_read() {
+        let mut out = BinaryOut::new();
+        out.write_bit(true);
+        out.write_bits(0b0110, 4);
+        out.write_byte(b'A');
+        out.write_bits(0xABC, 12);
+        let bytes = out.into_bytes();
+        // 1 0110 01000001 101010111100, then 7 bits of padding
+        assert_eq!(
+            bytes,
+            vec![0b1011_0010, 0b0000_1101, 0b0101_1110, 0b0000_0000]
+        );
+
+        let mut input = BinaryIn::new(&bytes);
+        assert_eq!(input.read_bit(), Some(true));
+        assert_eq!(input.read_bits(4), Some(0b0110));
+        assert_eq!(input.read_byte(), Some(b'A'));
+        assert_eq!(input.read_bits(12), Some(0xABC));
+        assert!(!input.is_empty());
+        assert_eq!(input.remaining(), 7);
+        assert_eq!(input.read_bits(8), None);
+        assert_eq!(input.read_bits(7), Some(0));
+        assert!(input.is_empty());
+        assert_eq!(input.read_bit(), None);
+    }
+
+    #[test]
+    fn empty() {
+        assert!(BinaryOut::new().into_bytes().is_empty());
+        let mut input = BinaryIn::new(&[]);
+        assert!(input.is_empty());
+        assert_eq!(input.read_bit(), None);
+        assert_eq!(input.read_bits(0), Some(0));
+    }
+}
@@ -0,0 +1,255 @@
+//! # Huffman compression
+//!
+//! Each byte is encoded by a prefix-free variable-length codeword, read off the path to
+//! its leaf in a trie: 0 for left and 1 for right. The trie is built bottom up by merging
+//! the two least frequent subtries, which gives an optimal prefix-free code.
+//! The output is the trie (in preorder: 1 and the byte for a leaf, 0 for an internal
+//! node), then the number of bytes as 32 bits, then the codewords.
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use super::binary_io::{BinaryIn, BinaryOut};
+
+const R: usize = 256;
+const MAX_NODES: usize = 2 * R - 1; // R leaves and R - 1 internal nodes
+
+enum Node {
+    Leaf(u8),
+    Internal(usize, usize), // indices of the left and right subtries
+}
+
+pub struct Huffman;
+
+impl Huffman {
+    pub fn compress(input: &[u8]) -> Vec<u8> {
+        let n = u32::try_from(input.len()).expect("Huffman input is longer than u32::MAX bytes");
+        let mut freq = [0usize; R];
+        for &b in input {
+            freq[b as usize] += 1;
+        }
+        let (trie, root) = build_trie(&freq);
+        let mut codes = vec![Vec::new(); R];
+        build_code(&trie, root, &mut Vec::new(), &mut codes);
+
+        let mut out = BinaryOut::new();
+        write_trie(&trie, root, &mut out);
+        out.write_bits(n, 32);
+        for &b in input {
+            for &bit in &codes[b as usize] {
+                out.write_bit(bit);
+            }
+        }
+        out.into_bytes()
+    }
+
+    /// Expands the output of `compress`, panicking if it is truncated or malformed.
+    pub fn expand(input: &[u8]) -> Vec<u8> {
+        let mut bits = BinaryIn::new(input);
+        let trie = read_trie(&mut bits);
+        let n = bits.read_bits(32).expect("Huffman input is truncated") as usize;
+        // every codeword is at least one bit, so a bogus length cannot allocate much
+        assert!(n <= bits.remaining(), "Huffman input is truncated");
+        (0..n)
+            .map(|_| {
+                let mut x = 0;
+                loop {
+                    match trie[x] {
+                        Node::Leaf(b) => return b,
+                        Node::Internal(left, right) => {
+                            let bit = bits.read_bit().expect("Huffman input is truncated");
+                            x = if bit { right } else { left };
+                        }
+                    }
+                }
+            })
+            .collect()
+    }
+}
+
+// the trie's nodes and the index of its root
+fn build_trie(freq: &[usize; R]) -> (Vec<Node>, usize) {
+    let mut trie = Vec::new();
+    // ties are broken by node index, so the trie does not depend on the heap's internals
+    let mut pq = BinaryHeap::new();
+    for (c, &f) in freq.iter().enumerate() {
+        if f > 0 {
+            pq.push(Reverse((f, trie.len())));
+            trie.push(Node::Leaf(c as u8));
+        }
+    }
+    // a trie needs at least two leaves, so that every byte gets a nonempty codeword
+    for (c, &f) in freq.iter().enumerate().take(2) {
+        if pq.len() < 2 && f == 0 {
+            pq.push(Reverse((0, trie.len())));
+            trie.push(Node::Leaf(c as u8));
+        }
+    }
+
+    while pq.len() > 1 {
+        let Reverse((f1, left)) = pq.pop().unwrap();
+        let Reverse((f2, right)) = pq.pop().unwrap();
+        pq.push(Reverse((f1 + f2, trie.len())));
+        trie.push(Node::Internal(left, right));
+    }
+    let Reverse((_, root)) = pq.pop().unwrap();
+    (trie, root)
+}
+
+fn build_code(trie: &[Node], x: usize, path: &mut Vec<bool>, codes: &mut [Vec<bool>]) {
+    match trie[x] {
+        Node::Leaf(b) => codes[b as usize] = path.clone(),
+        Node::Internal(left, right) => {
+            for (child, bit) in [(left, false), (right, true)] {
+                path.push(bit);
+                build_code(trie, child, path, codes);
+                path.pop();
+            }
+        }
+    }
+}
+
+fn write_trie(trie: &[Node], x: usize, out: &mut BinaryOut) {
+    match trie[x] {
+        Node::Leaf(b) => {
+            out.write_bit(true);
+            out.write_byte(b);
+        }
+        Node::Internal(left, right) => {
+            out.write_bit(false);
+            write_trie(trie, left, out);
+            write_trie(trie, right, out);
+        }
+    }
+}
+
+// the trie's nodes, with the root at index 0; iterative, and at most `MAX_NODES`,
+// so that malformed input cannot overflow the stack
+fn read_trie(bits: &mut BinaryIn) -> Vec<Node> {
+    let mut trie = Vec::new();
+    // internal nodes still missing their right child; a child index is never 0,
+    // the root's, so 0 marks a child that is not yet read
+    let mut open: Vec<usize> = Vec::new();
+    loop {
+        assert!(
+            trie.len() < MAX_NODES,
+            "Huffman trie has more than {} nodes",
+            MAX_NODES
+        );
+        let x = trie.len();
+        let is_leaf = bits.read_bit().expect("Huffman input is truncated");
+        if is_leaf {
+            trie.push(Node::Leaf(
+                bits.read_byte().expect("Huffman input is truncated"),
+            ));
+        } else {
+            trie.push(Node::Internal(0, 0));
+        }
+        if let Some(&parent) = open.last() {
+            if let Node::Internal(left, right) = &mut trie[parent] {
+                if *left == 0 {
+                    *left = x;
+                } else {
+                    *right = x;
+                    open.pop();
+                }
+            }
+        } else {
+            // `compress` always writes at least two leaves
+            assert!(!is_leaf, "Huffman trie has a single leaf");
+        }
+        if !is_leaf {
+            open.push(x);
+        }
+        if open.is_empty() {
+            return trie;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn abracadabra() {
+        let txt = b"ABRACADABRA!";
+        let compressed = Huffman::compress(txt);
+        // 6 leaves and 5 internal nodes take 59 bits, the length 32 and the text 28,
+        // as in the book: 119 bits in all
+        assert_eq!(compressed.len(), 15);
+        assert_eq!(Huffman::expand(&compressed), txt);
+    }
+
+    #[test]
+    fn frequent_bytes_get_short_codewords() {
+        let mut freq = [0; R];
+        freq[b'a' as usize] = 100;
+        freq[b'b' as usize] = 10;
+        freq[b'c' as usize] = 5;
+        freq[b'd' as usize] = 1;
+        let (trie, root) = build_trie(&freq);
+        let mut codes = vec![Vec::new(); R];
+        build_code(&trie, root, &mut Vec::new(), &mut codes);
+        let len = |c: u8| codes[c as usize].len();
+        assert_eq!((len(b'a'), len(b'b'), len(b'c'), len(b'd')), (1, 2, 3, 3));
+    }
+
+    #[test]
+    fn few_distinct_bytes() {
+        for input in [&b""[..], b"\0", b"\0\0\0", b"zzzz", b"\x01\0"] {
+            assert_eq!(Huffman::expand(&Huffman::compress(input)), input);
+        }
+    }
+
+    #[test]
+    fn random() {
+        use rand::rngs::StdRng;
+        use rand::{Rng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(56);
+        let bytes: Vec<u8> = (0..20_000).map(|_| rng.gen()).collect();
+        assert_eq!(Huffman::expand(&Huffman::compress(&bytes)), bytes);
+
+        // skewed, so it compresses
+        let skewed: Vec<u8> = (0..20_000)
+            .map(|_| b"aaaaaaaabbbbccd"[rng.gen_range(0..15)])
+            .collect();
+        let compressed = Huffman::compress(&skewed);
+        assert!(compressed.len() < skewed.len() / 4);
+        assert_eq!(Huffman::expand(&compressed), skewed);
+    }
+
+    #[test]
+    #[should_panic(expected = "Huffman input is truncated")]
+    fn truncated() {
+        let compressed = Huffman::compress(b"ABRACADABRA!");
+        Huffman::expand(&compressed[..10]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Huffman trie has more than 511 nodes")]
+    fn deep_trie() {
+        // all 0 bits: internal nodes without end, which would overflow a recursive reader
+        Huffman::expand(&vec![0; 2_000_000]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Huffman trie has a single leaf")]
+    fn leaf_root() {
+        // a leaf 'A' and a length of 0xFFFFFFFF, which would decode without reading a bit
+        let mut out = BinaryOut::new();
+        out.write_bit(true);
+        out.write_byte(b'A');
+        out.write_bits(u32::MAX, 32);
+        Huffman::expand(&out.into_bytes());
+    }
+
+    #[test]
+    #[should_panic(expected = "Huffman input is truncated")]
+    fn bogus_length() {
+        let mut compressed = Huffman::compress(b"ABRACADABRA!");
+        // the 32-bit length starts at bit 59
+        compressed[8] = 0xFF;
+        Huffman::expand(&compressed);
+    }
+}
@@ -0,0 +1,149 @@
+//! # LZW compression
+//!
+//! Lempel-Ziv-Welch compression with fixed-length 12-bit codewords. The longest prefix
+//! of the input that is in the code table is written out as its codeword, and that prefix
+//! plus the next byte becomes a new entry, until all `L` codewords are used.
+//! Codeword `R` marks the end of the input, so the single bytes take codewords `0..R`
+//! and the learned strings `R + 1..L`. Expansion rebuilds the same table as it reads.
+use std::collections::HashMap;
+
+use super::binary_io::{BinaryIn, BinaryOut};
+
+const R: u32 = 256; // number of input bytes, and the end-of-input codeword
+const L: u32 = 4096; // number of codewords = 2^W
+const W: usize = 12; // codeword width
+
+pub struct LZW;
+
+impl LZW {
+    pub fn compress(input: &[u8]) -> Vec<u8> {
+        // a string in the table is keyed by the codeword of its prefix and its last byte;
+        // the single bytes are implicit
+        let mut st: HashMap<(u32, u8), u32> = HashMap::new();
+        let mut code = R + 1;
+        let mut out = BinaryOut::new();
+        let mut rest = input;
+        while let Some(&first) = rest.first() {
+            // the longest prefix in the table
+            let (mut codeword, mut t) = (first as u32, 1);
+            while let Some(&longer) = rest.get(t).and_then(|&b| st.get(&(codeword, b))) {
+                codeword = longer;
+                t += 1;
+            }
+            out.write_bits(codeword, W);
+            if t < rest.len() && code < L {
+                st.insert((codeword, rest[t]), code);
+                code += 1;
+            }
+            rest = &rest[t..];
+        }
+        out.write_bits(R, W);
+        out.into_bytes()
+    }
+
+    /// Expands the output of `compress`, panicking if the input ends without
+    /// the end-of-input codeword or holds a codeword that is not yet defined.
+    pub fn expand(input: &[u8]) -> Vec<u8> {
+        let mut st: Vec<Vec<u8>> = (0..R).map(|c| vec![c as u8]).collect();
+        st.push(Vec::new()); // unused: the end-of-input codeword
+        let mut codes = BinaryIn::new(input);
+        let mut read = || match codes.read_bits(W) {
+            Some(codeword) => codeword as usize,
+            None => panic!("LZW input ends without the end-of-input codeword"),
+        };
+
+        let mut out = Vec::new();
+        let mut codeword = read();
+        if codeword == R as usize {
+            return out;
+        }
+        let mut val = st[codeword].clone();
+        loop {
+            out.extend_from_slice(&val);
+            codeword = read();
+            if codeword == R as usize {
+                return out;
+            }
+            let s = if codeword < st.len() {
+                st[codeword].clone()
+            } else if codeword == st.len() {
+                // the entry being defined right now: val plus its own first byte
+                let mut s = val.clone();
+                s.push(val[0]);
+                s
+            } else {
+                panic!("undefined LZW codeword {}", codeword);
+            };
+            if st.len() < L as usize {
+                let mut entry = val;
+                entry.push(s[0]);
+                st.push(entry);
+            }
+            val = s;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn codewords(compressed: &[u8]) -> Vec<u32> {
+        let mut input = BinaryIn::new(compressed);
+        std::iter::from_fn(|| input.read_bits(W)).collect()
+    }
+
+    #[test]
+    fn abracadabra() {
+        let txt = b"ABRACADABRABRABRA";
+        let compressed = LZW::compress(txt);
+        // the book's trace, with the learned codewords starting at 0x101
+        assert_eq!(
+            codewords(&compressed),
+            vec![0x41, 0x42, 0x52, 0x41, 0x43, 0x41, 0x44, 0x101, 0x103, 0x102, 0x108, 0x41, 0x100]
+        );
+        assert_eq!(LZW::expand(&compressed), txt);
+    }
+
+    #[test]
+    fn tricky_case() {
+        // ABABA is written as A, B, then AB(A), whose codeword is not yet in the table
+        let compressed = LZW::compress(b"ABABABA");
+        assert_eq!(
+            codewords(&compressed),
+            vec![0x41, 0x42, 0x101, 0x103, 0x100]
+        );
+        assert_eq!(LZW::expand(&compressed), b"ABABABA");
+
+        let same = vec![b'x'; 10_000];
+        assert_eq!(LZW::expand(&LZW::compress(&same)), same);
+    }
+
+    #[test]
+    fn full_table() {
+        use rand::rngs::StdRng;
+        use rand::{Rng, SeedableRng};
+
+        // far more than L - R - 1 distinct strings, so the table fills up
+        let mut rng = StdRng::seed_from_u64(55);
+        let data: Vec<u8> = (0..50_000).map(|_| rng.gen_range(b'a'..=b'h')).collect();
+        assert_eq!(LZW::expand(&LZW::compress(&data)), data);
+
+        let bytes: Vec<u8> = (0..20_000).map(|_| rng.gen()).collect();
+        assert_eq!(LZW::expand(&LZW::compress(&bytes)), bytes);
+    }
+
+    #[test]
+    fn empty() {
+        let compressed = LZW::compress(&[]);
+        assert_eq!(codewords(&compressed), vec![R]);
+        assert!(LZW::expand(&compressed).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "without the end-of-input codeword")]
+    fn truncated() {
+        let compressed = LZW::compress(b"ABRACADABRA");
+        LZW::expand(&compressed[..6]);
+    }
+}
@@ -0,0 +1,82 @@
+//! # Run-length coding
+//!
+//! A bitstream is alternating runs of 0s and 1s, so it is encoded as the run lengths,
+//! each an 8-bit count, starting with a run of 0s. A run longer than 255 is split
+//! by a run of length 0 of the other bit. This pays off for bitmaps with long runs,
+//! but typically expands ASCII text, whose runs are short.
+use super::binary_io::{BinaryIn, BinaryOut};
+
+const R: u32 = 256; // maximum run length is R - 1
+const LG_R: usize = 8;
+
+pub struct RunLength;
+
+impl RunLength {
+    pub fn compress(input: &[u8]) -> Vec<u8> {
+        let mut bits = BinaryIn::new(input);
+        let mut out = BinaryOut::new();
+        let mut run = 0;
+        let mut old = false;
+        while let Some(b) = bits.read_bit() {
+            if b != old {
+                out.write_bits(run, LG_R);
+                run = 0;
+                old = !old;
+            } else if run == R - 1 {
+                out.write_bits(run, LG_R);
+                out.write_bits(0, LG_R);
+                run = 0;
+            }
+            run += 1;
+        }
+        out.write_bits(run, LG_R);
+        out.into_bytes()
+    }
+
+    pub fn expand(input: &[u8]) -> Vec<u8> {
+        let mut counts = BinaryIn::new(input);
+        let mut out = BinaryOut::new();
+        let mut b = false;
+        while let Some(run) = counts.read_bits(LG_R) {
+            for _ in 0..run {
+                out.write_bit(b);
+            }
+            b = !b;
+        }
+        out.into_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn runs() {
+        // 15 0s, then 7 1s, then 7 0s, then 11 1s, as in the book, and 8 0s of padding
+        let bitmap = [0x00, 0x01, 0xFC, 0x07, 0xFF, 0x00];
+        let compressed = RunLength::compress(&bitmap);
+        assert_eq!(compressed, vec![15, 7, 7, 11, 8]);
+        assert_eq!(RunLength::expand(&compressed), bitmap);
+    }
+
+    #[test]
+    fn long_runs() {
+        // 300 1s: an empty run of 0s, then 255 1s, no 0s and 45 more 1s
+        let mut bitmap = vec![0xFF; 37];
+        bitmap.push(0xF0);
+        let compressed = RunLength::compress(&bitmap);
+        assert_eq!(compressed, vec![0, 255, 0, 45, 4]);
+        assert_eq!(RunLength::expand(&compressed), bitmap);
+
+        let zeros = vec![0; 1000];
+        assert_eq!(RunLength::expand(&RunLength::compress(&zeros)), zeros);
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(RunLength::compress(&[]), vec![0]);
+        assert!(RunLength::expand(&[0]).is_empty());
+        assert!(RunLength::expand(&[]).is_empty());
+    }
+}
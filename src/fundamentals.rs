@@ -1,11 +1,18 @@
+pub mod bag;
 pub mod binary_search;
+pub mod bitset;
+pub mod bitvector;
+pub mod deque;
 pub mod evaluate;
 pub mod fixed_capacity_stack;
+pub mod interner;
 pub mod linked_stack;
 pub mod queue;
 pub mod quick_find_uf;
 pub mod quick_union_uf;
+pub mod randomized_queue;
 pub mod resizing_stack;
+pub mod ring_buffer;
 pub mod stack;
 pub mod three_sum;
 pub mod three_sum_fast;
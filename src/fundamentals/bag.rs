@@ -0,0 +1,159 @@
+//! # Bag
+//!
+//! A collection where items can be added but not removed, implemented with linked list.
+//! Iteration is in LIFO order, though clients should not depend on it.
+
+type Link<T> = Option<Box<Node<T>>>;
+
+struct Node<T> {
+    item: T,
+    next: Link<T>,
+}
+
+pub struct Bag<T> {
+    first: Link<T>,
+    n: usize,
+}
+
+impl<T> Bag<T> {
+    pub fn new() -> Self {
+        Bag { first: None, n: 0 }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.first.is_none()
+    }
+
+    pub fn size(&self) -> usize {
+        self.n
+    }
+
+    pub fn add(&mut self, t: T) {
+        let new_node = Box::new(Node {
+            item: t,
+            next: self.first.take(),
+        });
+        self.first = Some(new_node);
+        self.n += 1;
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            next: self.first.as_deref(),
+        }
+    }
+}
+
+impl<T> Default for Bag<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for Bag<T> {
+    fn drop(&mut self) {
+        let mut cur_link = self.first.take();
+        while let Some(mut boxed_node) = cur_link {
+            cur_link = boxed_node.next.take();
+        }
+    }
+}
+
+pub struct Iter<'a, T> {
+    next: Option<&'a Node<T>>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next.map(|node| {
+            self.next = node.next.as_deref();
+            &node.item
+        })
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Bag<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+pub struct IntoIter<T>(Bag<T>);
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.first.take().map(|node| {
+            self.0.first = node.next;
+            self.0.n -= 1;
+            node.item
+        })
+    }
+}
+
+impl<T> IntoIterator for Bag<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add() {
+        let mut b = Bag::new();
+        assert!(b.is_empty());
+        b.add(4);
+        b.add(5);
+        b.add(6);
+        assert!(!b.is_empty());
+        assert_eq!(b.size(), 3);
+
+        let mut iterator = b.iter();
+        assert_eq!(iterator.next(), Some(&6));
+        assert_eq!(iterator.next(), Some(&5));
+        assert_eq!(iterator.next(), Some(&4));
+        assert_eq!(iterator.next(), None);
+        // iterating does not consume the bag
+        assert_eq!(b.size(), 3);
+    }
+
+    #[test]
+    fn for_loop() {
+        let mut b = Bag::new();
+        for i in 0..5 {
+            b.add(i);
+        }
+
+        let mut v = vec![];
+        for &i in &b {
+            v.push(i);
+        }
+        assert_eq!(v, vec![4, 3, 2, 1, 0]);
+
+        let mut sum = 0;
+        for i in b {
+            sum += i;
+        }
+        assert_eq!(sum, 10);
+    }
+
+    #[test]
+    fn into_iter() {
+        let mut b = Bag::new();
+        b.add(String::from("a"));
+        b.add(String::from("b"));
+
+        let mut iterator = b.into_iter();
+        assert_eq!(iterator.next(), Some(String::from("b")));
+        assert_eq!(iterator.next(), Some(String::from("a")));
+        assert_eq!(iterator.next(), None);
+    }
+}
@@ -0,0 +1,249 @@
+//! # Bit set
+//!
+//! A set of integers from a fixed universe `0..n`, stored as one bit per integer in 64-bit words:
+//! n / 8 bytes, against n bytes for a `Vec<bool>`. Iteration skips empty words and finds each
+//! member with `trailing_zeros`, and the set operations work a word at a time.
+
+const WORD: usize = 64;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitSet {
+    words: Vec<u64>, // i is in the set iff bit i % 64 of words[i / 64] is 1
+    n: usize,        // size of the universe
+}
+
+impl BitSet {
+    /// Creates an empty set over the universe `0..n`.
+    pub fn new(n: usize) -> Self {
+        BitSet {
+            words: vec![0; n.div_ceil(WORD)],
+            n,
+        }
+    }
+
+    /// Returns the size of the universe.
+    pub fn universe(&self) -> usize {
+        self.n
+    }
+
+    fn validate(&self, i: usize) {
+        if i >= self.n {
+            panic!("index {} is not between 0 and {}", i, self.n);
+        }
+    }
+
+    /// Adds i to the set, returning whether it was absent.
+    pub fn insert(&mut self, i: usize) -> bool {
+        self.validate(i);
+        let (word, bit) = (&mut self.words[i / WORD], 1 << (i % WORD));
+        let absent = *word & bit == 0;
+        *word |= bit;
+        absent
+    }
+
+    /// Removes i from the set, returning whether it was present.
+    pub fn remove(&mut self, i: usize) -> bool {
+        self.validate(i);
+        let (word, bit) = (&mut self.words[i / WORD], 1 << (i % WORD));
+        let present = *word & bit != 0;
+        *word &= !bit;
+        present
+    }
+
+    pub fn contains(&self, i: usize) -> bool {
+        self.validate(i);
+        self.words[i / WORD] & (1 << (i % WORD)) != 0
+    }
+
+    /// Returns the number of members. It counts the bits, in O(n / 64) time.
+    pub fn len(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    /// Removes every member.
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    /// Returns the members in ascending order.
+    pub fn iter(&self) -> Iter<'_> {
+        Iter {
+            words: &self.words,
+            index: 0,
+            current: self.words.first().copied().unwrap_or(0),
+        }
+    }
+
+    fn validate_universe(&self, other: &BitSet) {
+        if self.n != other.n {
+            panic!("universes differ: {} and {}", self.n, other.n);
+        }
+    }
+
+    /// Adds the members of `other`.
+    pub fn union_with(&mut self, other: &BitSet) {
+        self.validate_universe(other);
+        for (a, b) in self.words.iter_mut().zip(&other.words) {
+            *a |= b;
+        }
+    }
+
+    /// Keeps only the members also in `other`.
+    pub fn intersect_with(&mut self, other: &BitSet) {
+        self.validate_universe(other);
+        for (a, b) in self.words.iter_mut().zip(&other.words) {
+            *a &= b;
+        }
+    }
+
+    /// Removes the members of `other`.
+    pub fn difference_with(&mut self, other: &BitSet) {
+        self.validate_universe(other);
+        for (a, b) in self.words.iter_mut().zip(&other.words) {
+            *a &= !b;
+        }
+    }
+}
+
+pub struct Iter<'a> {
+    words: &'a [u64],
+    index: usize, // index of the current word
+    current: u64, // bits of the current word not yet returned
+}
+
+impl Iterator for Iter<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.current == 0 {
+            self.index += 1;
+            self.current = *self.words.get(self.index)?;
+        }
+        let bit = self.current.trailing_zeros() as usize;
+        // clear the lowest set bit
+        self.current &= self.current - 1;
+        Some(self.index * WORD + bit)
+    }
+}
+
+impl<'a> IntoIterator for &'a BitSet {
+    type Item = usize;
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    use super::*;
+
+    fn sorted(set: &HashSet<usize>) -> Vec<usize> {
+        let mut v = set.iter().copied().collect::<Vec<usize>>();
+        v.sort_unstable();
+        v
+    }
+
+    #[test]
+    fn insert_remove() {
+        let mut s = BitSet::new(10);
+        assert!(s.is_empty());
+        assert!(s.insert(3));
+        assert!(!s.insert(3));
+        assert!(s.insert(9));
+        assert!(s.contains(3));
+        assert!(!s.contains(4));
+        assert_eq!(s.len(), 2);
+        assert!(s.remove(3));
+        assert!(!s.remove(3));
+        assert_eq!(s.iter().collect::<Vec<usize>>(), vec![9]);
+        s.clear();
+        assert!(s.is_empty());
+        assert_eq!(s.universe(), 10);
+        assert_eq!(BitSet::new(0).iter().count(), 0);
+    }
+
+    #[test]
+    fn word_edges() {
+        let mut s = BitSet::new(130);
+        for i in [0, 63, 64, 65, 127, 128, 129] {
+            s.insert(i);
+        }
+        assert_eq!(
+            s.iter().collect::<Vec<usize>>(),
+            vec![0, 63, 64, 65, 127, 128, 129]
+        );
+        s.remove(64);
+        assert!(s.contains(63) && !s.contains(64) && s.contains(65));
+        assert_eq!(s.len(), 6);
+
+        // a word of all ones
+        let mut s = BitSet::new(64);
+        (0..64).for_each(|i| {
+            s.insert(i);
+        });
+        assert!((&s).into_iter().eq(0..64));
+    }
+
+    #[test]
+    #[should_panic(expected = "index 64 is not between 0 and 64")]
+    fn out_of_range() {
+        BitSet::new(64).insert(64);
+    }
+
+    #[test]
+    fn set_operations() {
+        let mut rng = StdRng::seed_from_u64(65);
+        for _ in 0..50 {
+            let n = rng.gen_range(1..300);
+            let mut random = || {
+                let mut bits = BitSet::new(n);
+                let mut set = HashSet::new();
+                for _ in 0..rng.gen_range(0..n) {
+                    let i = rng.gen_range(0..n);
+                    assert_eq!(bits.insert(i), set.insert(i));
+                }
+                (bits, set)
+            };
+            let (a, a_set) = random();
+            let (b, b_set) = random();
+            assert_eq!(a.iter().collect::<Vec<usize>>(), sorted(&a_set));
+            assert_eq!(a.len(), a_set.len());
+
+            let mut union = a.clone();
+            union.union_with(&b);
+            assert_eq!(
+                union.iter().collect::<Vec<usize>>(),
+                sorted(&a_set.union(&b_set).copied().collect())
+            );
+            let mut intersection = a.clone();
+            intersection.intersect_with(&b);
+            assert_eq!(
+                intersection.iter().collect::<Vec<usize>>(),
+                sorted(&a_set.intersection(&b_set).copied().collect())
+            );
+            let mut difference = a.clone();
+            difference.difference_with(&b);
+            assert_eq!(
+                difference.iter().collect::<Vec<usize>>(),
+                sorted(&a_set.difference(&b_set).copied().collect())
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "universes differ")]
+    fn different_universes() {
+        BitSet::new(3).union_with(&BitSet::new(4));
+    }
+}
@@ -0,0 +1,279 @@
+//! # Rank/select bit vector
+//!
+//! A static bit vector with rank and select queries.
+//!
+//! Two levels of precomputed counts answer `rank1` in O(1): the number of ones before each
+//! 512-bit superblock, and the number of ones from the start of the superblock to each 64-bit word.
+//! The rest is one `count_ones` on a masked word. The counts take about 25% extra space.
+//!
+//! `select1` and `select0` binary search the superblock counts and then scan at most 8 words: O(log n).
+
+const WORD: usize = 64;
+const WORDS_PER_SUPER: usize = 8; // 512-bit superblocks
+
+pub struct RankSelectBitVec {
+    bits: Vec<u64>,   // bit i is bit i % 64 of bits[i / 64]
+    len: usize,       // number of bits
+    supers: Vec<u64>, // supers[s] = ones before superblock s
+    blocks: Vec<u16>, // blocks[w] = ones from the start of w's superblock to word w
+}
+
+impl RankSelectBitVec {
+    /// Builds from the first `len` bits of `bits`; any bits after `len` are ignored.
+    pub fn new(mut bits: Vec<u64>, len: usize) -> Self {
+        let words = len.div_ceil(WORD);
+        if bits.len() < words {
+            panic!("{} words cannot hold {} bits", bits.len(), len);
+        }
+        bits.truncate(words);
+        if !len.is_multiple_of(WORD) {
+            bits[words - 1] &= (1 << (len % WORD)) - 1;
+        }
+
+        // one extra entry each, so that rank1(len) needs no special case
+        let mut supers = Vec::with_capacity(words / WORDS_PER_SUPER + 1);
+        let mut blocks = Vec::with_capacity(words + 1);
+        let mut total = 0;
+        let mut in_super = 0;
+        for (w, word) in bits.iter().chain([&0]).enumerate() {
+            if w.is_multiple_of(WORDS_PER_SUPER) {
+                supers.push(total);
+                in_super = 0;
+            }
+            blocks.push(in_super as u16);
+            let ones = word.count_ones() as u64;
+            total += ones;
+            in_super += ones;
+        }
+        RankSelectBitVec {
+            bits,
+            len,
+            supers,
+            blocks,
+        }
+    }
+
+    /// Returns the number of bits.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the total number of ones.
+    pub fn count_ones(&self) -> usize {
+        self.rank1(self.len)
+    }
+
+    /// Returns bit `i`.
+    pub fn get(&self, i: usize) -> bool {
+        if i >= self.len {
+            panic!("index {} is not between 0 and {}", i, self.len);
+        }
+        self.bits[i / WORD] >> (i % WORD) & 1 == 1
+    }
+
+    /// Returns the number of ones in positions `[0, i)`.
+    pub fn rank1(&self, i: usize) -> usize {
+        self.validate_index(i, self.len);
+        let w = i / WORD;
+        let mut rank = self.supers[w / WORDS_PER_SUPER] + self.blocks[w] as u64;
+        if !i.is_multiple_of(WORD) {
+            rank += (self.bits[w] & ((1 << (i % WORD)) - 1)).count_ones() as u64;
+        }
+        rank as usize
+    }
+
+    /// Returns the number of zeros in positions `[0, i)`.
+    pub fn rank0(&self, i: usize) -> usize {
+        i - self.rank1(i)
+    }
+
+    /// Returns the position of the `k`-th one (starting from 0), if there are more than `k` ones.
+    pub fn select1(&self, k: usize) -> Option<usize> {
+        let k = k as u64;
+        if k >= self.count_ones() as u64 {
+            return None;
+        }
+        // the last superblock with fewer than k + 1 ones before it
+        let s = self.supers.partition_point(|&ones| ones <= k) - 1;
+        let mut remaining = k - self.supers[s];
+        let mut w = s * WORDS_PER_SUPER;
+        loop {
+            let ones = self.bits[w].count_ones() as u64;
+            if remaining < ones {
+                return Some(w * WORD + select_in_word(self.bits[w], remaining as u32));
+            }
+            remaining -= ones;
+            w += 1;
+        }
+    }
+
+    /// Returns the position of the `k`-th zero (starting from 0), if there are more than `k` zeros.
+    pub fn select0(&self, k: usize) -> Option<usize> {
+        if k >= self.len - self.count_ones() {
+            return None;
+        }
+        let k = k as u64;
+        let zeros_before = |s: usize| (s * WORDS_PER_SUPER * WORD) as u64 - self.supers[s];
+        // the last superblock with fewer than k + 1 zeros before it
+        let (mut lo, mut hi) = (0, self.supers.len());
+        while hi - lo > 1 {
+            let mid = lo + (hi - lo) / 2;
+            if zeros_before(mid) <= k {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        let mut remaining = k - zeros_before(lo);
+        let mut w = lo * WORDS_PER_SUPER;
+        // the padding after len is all zeros, but comes after every real zero
+        loop {
+            let zeros = self.bits[w].count_zeros() as u64;
+            if remaining < zeros {
+                return Some(w * WORD + select_in_word(!self.bits[w], remaining as u32));
+            }
+            remaining -= zeros;
+            w += 1;
+        }
+    }
+
+    fn validate_index(&self, i: usize, max: usize) {
+        if i > max {
+            panic!("index {} is not between 0 and {}", i, max);
+        }
+    }
+}
+
+// position of the k-th one (from 0) in x, which has more than k ones
+fn select_in_word(mut x: u64, k: u32) -> usize {
+    for _ in 0..k {
+        x &= x - 1; // clear the lowest one
+    }
+    x.trailing_zeros() as usize
+}
+
+/// Builds a `RankSelectBitVec` one bit at a time.
+#[derive(Default)]
+pub struct RankSelectBitVecBuilder {
+    bits: Vec<u64>,
+    len: usize,
+}
+
+impl RankSelectBitVecBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, bit: bool) {
+        if self.len.is_multiple_of(WORD) {
+            self.bits.push(0);
+        }
+        if bit {
+            *self.bits.last_mut().unwrap() |= 1 << (self.len % WORD);
+        }
+        self.len += 1;
+    }
+
+    pub fn build(self) -> RankSelectBitVec {
+        RankSelectBitVec::new(self.bits, self.len)
+    }
+}
+
+impl FromIterator<bool> for RankSelectBitVec {
+    fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+        let mut builder = RankSelectBitVecBuilder::new();
+        for bit in iter {
+            builder.push(bit);
+        }
+        builder.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    use super::*;
+
+    fn check(bits: &[bool]) {
+        let bv: RankSelectBitVec = bits.iter().copied().collect();
+        assert_eq!(bv.len(), bits.len());
+
+        let mut rank = 0;
+        let mut ones = Vec::new();
+        let mut zeros = Vec::new();
+        for (i, &bit) in bits.iter().enumerate() {
+            assert_eq!(bv.rank1(i), rank);
+            assert_eq!(bv.rank0(i), i - rank);
+            assert_eq!(bv.get(i), bit);
+            if bit {
+                ones.push(i);
+                rank += 1;
+            } else {
+                zeros.push(i);
+            }
+        }
+        assert_eq!(bv.rank1(bits.len()), rank);
+        assert_eq!(bv.count_ones(), rank);
+
+        for (k, &i) in ones.iter().enumerate() {
+            assert_eq!(bv.select1(k), Some(i));
+            assert_eq!(bv.rank1(i), k);
+        }
+        assert_eq!(bv.select1(ones.len()), None);
+
+        for (k, &i) in zeros.iter().enumerate() {
+            assert_eq!(bv.select0(k), Some(i));
+            assert_eq!(bv.rank0(i), k);
+        }
+        assert_eq!(bv.select0(zeros.len()), None);
+    }
+
+    #[test]
+    fn random() {
+        let mut rng = StdRng::seed_from_u64(27);
+        for density in [0.01, 0.1, 0.5, 0.9, 0.99] {
+            for len in [1, 63, 64, 65, 511, 512, 513, 3000] {
+                let bits: Vec<bool> = (0..len).map(|_| rng.gen_bool(density)).collect();
+                check(&bits);
+            }
+        }
+    }
+
+    #[test]
+    fn uniform() {
+        for len in [0, 1, 64, 512, 1000] {
+            check(&vec![false; len]);
+            check(&vec![true; len]);
+        }
+        let bv: RankSelectBitVec = vec![true; 1000].into_iter().collect();
+        assert_eq!(bv.select1(999), Some(999));
+        assert_eq!(bv.select1(1000), None);
+    }
+
+    #[test]
+    fn from_words() {
+        // bits after len are ignored
+        let bv = RankSelectBitVec::new(vec![u64::MAX, u64::MAX], 70);
+        assert_eq!(bv.count_ones(), 70);
+        assert_eq!(bv.select1(69), Some(69));
+        assert_eq!(bv.select1(70), None);
+
+        let bv = RankSelectBitVec::new(vec![0b1010], 4);
+        assert_eq!(bv.select1(0), Some(1));
+        assert_eq!(bv.select1(1), Some(3));
+        assert_eq!(bv.rank1(4), 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn rank_out_of_range() {
+        let bv = RankSelectBitVec::new(vec![0], 10);
+        bv.rank1(11);
+    }
+}
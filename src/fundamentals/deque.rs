@@ -0,0 +1,287 @@
+//! # Double-ended queue
+//!
+//! A deque implemented with a doubly-linked list.
+//! All operations at either end take constant time.
+//!
+//! https://rust-unofficial.github.io/too-many-lists/sixth.html
+
+use std::marker::PhantomData;
+use std::ptr::NonNull;
+
+type Link<T> = Option<NonNull<Node<T>>>;
+
+struct Node<T> {
+    item: T,
+    prev: Link<T>,
+    next: Link<T>,
+}
+
+pub struct Deque<T> {
+    first: Link<T>,
+    last: Link<T>,
+    n: usize,
+    _marker: PhantomData<T>,
+}
+
+// The deque owns every node, and links are only written through `&mut self`.
+unsafe impl<T: Send> Send for Deque<T> {}
+unsafe impl<T: Sync> Sync for Deque<T> {}
+
+impl<T> Deque<T> {
+    pub fn new() -> Self {
+        Deque {
+            first: None,
+            last: None,
+            n: 0,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    pub fn size(&self) -> usize {
+        self.n
+    }
+
+    /// Adds the item to the front.
+    pub fn push_front(&mut self, t: T) {
+        let node = Box::new(Node {
+            item: t,
+            prev: None,
+            next: self.first,
+        });
+        let node = NonNull::from(Box::leak(node));
+        match self.first {
+            Some(old) => unsafe { (*old.as_ptr()).prev = Some(node) },
+            None => self.last = Some(node),
+        }
+        self.first = Some(node);
+        self.n += 1;
+    }
+
+    /// Adds the item to the back.
+    pub fn push_back(&mut self, t: T) {
+        let node = Box::new(Node {
+            item: t,
+            prev: self.last,
+            next: None,
+        });
+        let node = NonNull::from(Box::leak(node));
+        match self.last {
+            Some(old) => unsafe { (*old.as_ptr()).next = Some(node) },
+            None => self.first = Some(node),
+        }
+        self.last = Some(node);
+        self.n += 1;
+    }
+
+    /// Removes and returns the item at the front.
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.first.map(|node| {
+            // the node was leaked from a `Box` in `push_*`
+            let boxed = unsafe { Box::from_raw(node.as_ptr()) };
+            self.first = boxed.next;
+            match self.first {
+                Some(new) => unsafe { (*new.as_ptr()).prev = None },
+                None => self.last = None,
+            }
+            self.n -= 1;
+            boxed.item
+        })
+    }
+
+    /// Removes and returns the item at the back.
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.last.map(|node| {
+            let boxed = unsafe { Box::from_raw(node.as_ptr()) };
+            self.last = boxed.prev;
+            match self.last {
+                Some(new) => unsafe { (*new.as_ptr()).next = None },
+                None => self.first = None,
+            }
+            self.n -= 1;
+            boxed.item
+        })
+    }
+
+    /// Returns the item at the front.
+    pub fn peek_front(&self) -> Option<&T> {
+        self.first.map(|node| unsafe { &(*node.as_ptr()).item })
+    }
+
+    /// Returns the item at the back.
+    pub fn peek_back(&self) -> Option<&T> {
+        self.last.map(|node| unsafe { &(*node.as_ptr()).item })
+    }
+
+    /// Returns the items from front to back.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            next: self.first,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Default for Deque<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for Deque<T> {
+    fn drop(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+}
+
+pub struct Iter<'a, T> {
+    next: Link<T>,
+    _marker: PhantomData<&'a T>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next.map(|node| unsafe {
+            let node = &*node.as_ptr();
+            self.next = node.next;
+            &node.item
+        })
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Deque<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+pub struct IntoIter<T>(Deque<T>);
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.pop_front()
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.pop_back()
+    }
+}
+
+impl<T> IntoIterator for Deque<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn push_pop() {
+        let mut d = Deque::new();
+        assert_eq!(d.pop_front(), None);
+        assert_eq!(d.pop_back(), None);
+
+        d.push_back(2);
+        d.push_front(1);
+        d.push_back(3);
+        assert_eq!(d.size(), 3);
+        assert_eq!(d.peek_front(), Some(&1));
+        assert_eq!(d.peek_back(), Some(&3));
+
+        assert_eq!(d.pop_back(), Some(3));
+        assert_eq!(d.pop_front(), Some(1));
+        assert_eq!(d.peek_front(), Some(&2));
+        assert_eq!(d.peek_back(), Some(&2));
+        assert_eq!(d.pop_back(), Some(2));
+        assert_eq!(d.pop_front(), None);
+        assert!(d.is_empty());
+        assert_eq!(d.peek_back(), None);
+
+        // reuse after becoming empty
+        d.push_front(4);
+        assert_eq!(d.pop_back(), Some(4));
+    }
+
+    #[test]
+    fn iter() {
+        let mut d = Deque::new();
+        d.push_back(5);
+        d.push_front(4);
+        d.push_back(6);
+
+        assert_eq!(d.iter().collect::<Vec<&i32>>(), vec![&4, &5, &6]);
+        assert_eq!(d.into_iter().rev().collect::<Vec<i32>>(), vec![6, 5, 4]);
+    }
+
+    #[test]
+    fn all_ends() {
+        let mut d = Deque::new();
+        for i in 0..10 {
+            if i % 2 == 0 {
+                d.push_front(i);
+            } else {
+                d.push_back(i);
+            }
+        }
+        let mut v = vec![];
+        for &i in &d {
+            v.push(i);
+        }
+        assert_eq!(v, vec![8, 6, 4, 2, 0, 1, 3, 5, 7, 9]);
+
+        let mut front = vec![];
+        let mut back = vec![];
+        while !d.is_empty() {
+            front.extend(d.pop_front());
+            back.extend(d.pop_back());
+        }
+        assert_eq!(front, vec![8, 6, 4, 2, 0]);
+        assert_eq!(back, vec![9, 7, 5, 3, 1]);
+        assert_eq!(d.size(), 0);
+    }
+
+    #[test]
+    fn drop_large() {
+        use std::rc::Rc;
+
+        let item = Rc::new(());
+        let mut d = Deque::new();
+        for i in 0..1_000_000 {
+            if i % 2 == 0 {
+                d.push_back(Rc::clone(&item));
+            } else {
+                d.push_front(Rc::clone(&item));
+            }
+        }
+        assert_eq!(Rc::strong_count(&item), 1_000_001);
+        // dropping does not recurse through the links, and frees every item
+        drop(d);
+        assert_eq!(Rc::strong_count(&item), 1);
+
+        let mut d = Deque::new();
+        for _ in 0..10 {
+            d.push_back(Rc::clone(&item));
+        }
+        let mut iter = d.into_iter();
+        iter.next();
+        iter.next_back();
+        drop(iter);
+        assert_eq!(Rc::strong_count(&item), 1);
+    }
+}
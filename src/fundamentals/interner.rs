@@ -0,0 +1,110 @@
+//! # String interner
+//!
+//! Maps each distinct string to a small integer id (0, 1, 2, ... in order of first appearance)
+//! and back, so that strings can be compared and stored as integers.
+//! The symbol graphs use one to name their vertices; sharing an interner between graphs
+//! gives the same name the same vertex in each.
+
+use crate::searching::linear_probing_hash_st::LinearProbingHashST;
+
+#[derive(Clone, Default)]
+pub struct StringInterner {
+    ids: LinearProbingHashST<String, u32>, // string -> id
+    names: Vec<String>,                    // id -> string
+}
+
+impl StringInterner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the id of `s`, assigning the next id if it is new.
+    pub fn intern(&mut self, s: &str) -> u32 {
+        if let Some(&id) = self.ids.get(s) {
+            return id;
+        }
+        let id = u32::try_from(self.names.len()).expect("too many strings to intern");
+        self.ids.put(s.to_string(), id);
+        self.names.push(s.to_string());
+        id
+    }
+
+    /// Returns the id of `s`, if it has been interned.
+    pub fn get(&self, s: &str) -> Option<u32> {
+        self.ids.get(s).copied()
+    }
+
+    /// Returns the string with the given id.
+    pub fn resolve(&self, id: u32) -> &str {
+        match self.try_resolve(id) {
+            Some(name) => name,
+            None => panic!("id {} is not between 0 and {}", id, self.names.len()),
+        }
+    }
+
+    /// Returns the string with the given id, or `None` if no string has it.
+    pub fn try_resolve(&self, id: u32) -> Option<&str> {
+        self.names.get(id as usize).map(String::as_str)
+    }
+
+    /// Returns the number of distinct strings.
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// Returns every `(id, string)` pair in order of id.
+    pub fn iter(&self) -> impl Iterator<Item = (u32, &str)> {
+        self.names
+            .iter()
+            .enumerate()
+            .map(|(id, name)| (id as u32, name.as_str()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn intern_resolve() {
+        let mut interner = StringInterner::new();
+        assert!(interner.is_empty());
+        let words = [
+            "it", "was", "the", "best", "of", "times", "it", "was", "the", "worst",
+        ];
+        let ids = words
+            .iter()
+            .map(|w| interner.intern(w))
+            .collect::<Vec<u32>>();
+        assert_eq!(ids, vec![0, 1, 2, 3, 4, 5, 0, 1, 2, 6]);
+        assert_eq!(interner.len(), 7);
+        for (w, &id) in words.iter().zip(&ids) {
+            assert_eq!(interner.resolve(id), *w);
+            assert_eq!(interner.get(w), Some(id));
+        }
+        assert_eq!(interner.get("age"), None);
+        assert_eq!(interner.try_resolve(6), Some("worst"));
+        assert_eq!(interner.try_resolve(7), None);
+        assert_eq!(
+            interner.iter().map(|(_, s)| s).collect::<Vec<&str>>(),
+            vec!["it", "was", "the", "best", "of", "times", "worst"]
+        );
+
+        // many strings, to resize the table
+        for i in 0..1000 {
+            assert_eq!(interner.intern(&i.to_string()), i + 7);
+        }
+        assert_eq!(interner.intern("500"), 507);
+        assert_eq!(interner.resolve(1006), "999");
+    }
+
+    #[test]
+    #[should_panic]
+    fn resolve_unknown() {
+        StringInterner::new().resolve(0);
+    }
+}
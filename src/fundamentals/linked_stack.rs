@@ -50,11 +50,30 @@ impl<T> LinkedStack<T> {
         self.first.as_ref().map(|node| &node.item)
     }
 
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        self.first.as_mut().map(|node| &mut node.item)
+    }
+
+    /// Pops every item, leaving the stack empty even if the iterator is not run to the end.
+    pub fn drain(&mut self) -> Drain<'_, T> {
+        Drain(self)
+    }
+
     pub fn iter(&self) -> Iter<'_, T> {
         Iter {
             next: self.first.as_deref(),
         }
     }
+
+    /// Returns a cursor at the "ghost" position before the top, from which
+    /// `move_next` goes to the top item.
+    pub fn cursor_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut {
+            cur: None,
+            index: 0,
+            stack: self,
+        }
+    }
 }
 
 impl<T> Default for LinkedStack<T> {
@@ -86,6 +105,107 @@ impl<'a, T> Iterator for Iter<'a, T> {
     }
 }
 
+pub struct Drain<'a, T>(&'a mut LinkedStack<T>);
+
+impl<'a, T> Iterator for Drain<'a, T> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.pop()
+    }
+}
+
+impl<'a, T> Drop for Drain<'a, T> {
+    fn drop(&mut self) {
+        while self.0.pop().is_some() {}
+    }
+}
+
+/// A cursor over a `LinkedStack`, from the top down, that can insert and remove items.
+///
+/// It is either at an item or at the "ghost" position before the top (and after the bottom).
+pub struct CursorMut<'a, T> {
+    // the link that owns the current item, or `None` at the ghost position;
+    // it points into `stack`, which is borrowed mutably for as long as the cursor lives
+    cur: Option<*mut Link<T>>,
+    index: usize, // index of the current item from the top
+    stack: &'a mut LinkedStack<T>,
+}
+
+impl<'a, T> CursorMut<'a, T> {
+    /// Returns the index of the current item from the top, or `None` at the ghost position.
+    pub fn index(&self) -> Option<usize> {
+        self.cur.map(|_| self.index)
+    }
+
+    /// Returns the current item, or `None` at the ghost position.
+    pub fn current(&mut self) -> Option<&mut T> {
+        // `*link` is `Some` whenever the cursor is at an item
+        self.cur
+            .map(|link| unsafe { &mut (*link).as_mut().unwrap().item })
+    }
+
+    /// Moves to the next item down; from the bottom it moves to the ghost position,
+    /// and from the ghost position to the top.
+    pub fn move_next(&mut self) {
+        self.cur = match self.cur {
+            None => {
+                self.index = 0;
+                let first: *mut Link<T> = &mut self.stack.first;
+                unsafe { (*first).is_some().then_some(first) }
+            }
+            Some(link) => {
+                self.index += 1;
+                let next: *mut Link<T> = unsafe { &mut (*link).as_mut().unwrap().next };
+                unsafe { (*next).is_some().then_some(next) }
+            }
+        };
+    }
+
+    /// Inserts an item just below the current one; at the ghost position, it is pushed on top.
+    pub fn insert_after(&mut self, t: T) {
+        match self.cur {
+            None => self.stack.push(t),
+            Some(link) => {
+                let node = unsafe { (*link).as_mut().unwrap() };
+                node.next = Some(Box::new(Node {
+                    item: t,
+                    next: node.next.take(),
+                }));
+                self.stack.n += 1;
+            }
+        }
+    }
+
+    /// Removes and returns the current item, and moves to the next one
+    /// (or to the ghost position, if it was the bottom).
+    pub fn remove_current(&mut self) -> Option<T> {
+        let link = self.cur?;
+        let node = unsafe { (*link).take().unwrap() };
+        unsafe {
+            *link = node.next;
+            if (*link).is_none() {
+                self.cur = None;
+            }
+        }
+        self.stack.n -= 1;
+        Some(node.item)
+    }
+
+    /// Splits off every item below the current one into a new stack, keeping their order.
+    /// At the ghost position, it takes the whole stack.
+    pub fn split_after(&mut self) -> LinkedStack<T> {
+        let (first, n) = match self.cur {
+            None => (self.stack.first.take(), self.stack.n),
+            Some(link) => {
+                let node = unsafe { (*link).as_mut().unwrap() };
+                (node.next.take(), self.stack.n - self.index - 1)
+            }
+        };
+        self.stack.n -= n;
+        LinkedStack { first, n }
+    }
+}
+
 pub struct IntoIter<T>(LinkedStack<T>);
 
 // `cargo clippy` will complain `into_iter()`
@@ -160,4 +280,127 @@ mod tests {
         assert_eq!(iterator.next(), Some(4));
         assert_eq!(iterator.next(), None);
     }
+
+    #[test]
+    fn peek_mut() {
+        let mut s = LinkedStack::new();
+        assert_eq!(s.peek_mut(), None);
+        s.push(4);
+        s.push(5);
+        if let Some(top) = s.peek_mut() {
+            *top *= 10;
+        }
+        assert_eq!(s.pop(), Some(50));
+        assert_eq!(s.peek(), Some(&4));
+    }
+
+    #[test]
+    fn drain() {
+        let mut s = LinkedStack::new();
+        s.push(4);
+        s.push(5);
+        s.push(6);
+
+        assert_eq!(s.drain().collect::<Vec<i32>>(), vec![6, 5, 4]);
+        assert!(s.is_empty());
+        assert_eq!(s.size(), 0);
+
+        // dropping a partially consumed drain still empties the stack
+        s.push(1);
+        s.push(2);
+        assert_eq!(s.drain().next(), Some(2));
+        assert!(s.is_empty());
+        assert_eq!(s.size(), 0);
+        s.push(3);
+        assert_eq!(s.peek(), Some(&3));
+    }
+
+    #[test]
+    fn cursor() {
+        let mut s = LinkedStack::new();
+        for i in (1..=5).rev() {
+            s.push(i);
+        }
+        // 1 2 3 4 5, from the top
+        let mut cursor = s.cursor_mut();
+        assert_eq!(cursor.current(), None);
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(&mut 1));
+        cursor.move_next();
+        assert_eq!(cursor.index(), Some(1));
+        cursor.insert_after(20);
+        cursor.move_next();
+        *cursor.current().unwrap() += 1;
+        assert_eq!(cursor.remove_current(), Some(21));
+        assert_eq!(cursor.current(), Some(&mut 3));
+        cursor.insert_after(30);
+        assert_eq!(s.size(), 6);
+        assert_eq!(
+            s.iter().copied().collect::<Vec<i32>>(),
+            vec![1, 2, 3, 30, 4, 5]
+        );
+
+        // removing the bottom moves to the ghost position, then to the top
+        let mut cursor = s.cursor_mut();
+        for _ in 0..6 {
+            cursor.move_next();
+        }
+        assert_eq!(cursor.remove_current(), Some(5));
+        assert_eq!(cursor.index(), None);
+        assert_eq!(cursor.remove_current(), None);
+        cursor.insert_after(0);
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(&mut 0));
+        assert_eq!(s.size(), 6);
+        assert_eq!(s.pop(), Some(0));
+    }
+
+    #[test]
+    fn split_after() {
+        let mut s = LinkedStack::new();
+        for i in (0..10).rev() {
+            s.push(i);
+        }
+        let mut cursor = s.cursor_mut();
+        for _ in 0..4 {
+            cursor.move_next();
+        }
+        let mut tail = cursor.split_after();
+        assert_eq!(s.size(), 4);
+        assert_eq!(tail.size(), 6);
+        assert_eq!(s.iter().copied().collect::<Vec<i32>>(), vec![0, 1, 2, 3]);
+        assert_eq!(
+            tail.iter().copied().collect::<Vec<i32>>(),
+            vec![4, 5, 6, 7, 8, 9]
+        );
+
+        // splicing back at the bottom restores the stack
+        let mut cursor = s.cursor_mut();
+        for _ in 0..4 {
+            cursor.move_next();
+        }
+        for item in tail.drain().collect::<Vec<i32>>().into_iter().rev() {
+            cursor.insert_after(item);
+        }
+        assert_eq!(
+            s.iter().copied().collect::<Vec<i32>>(),
+            (0..10).collect::<Vec<i32>>()
+        );
+        assert_eq!(s.size(), 10);
+
+        // at the ghost position it takes everything
+        let all = s.cursor_mut().split_after();
+        assert!(s.is_empty());
+        assert_eq!(s.size(), 0);
+        assert_eq!(all.size(), 10);
+
+        // at the bottom it takes nothing
+        let mut s = all;
+        let mut cursor = s.cursor_mut();
+        for _ in 0..10 {
+            cursor.move_next();
+        }
+        assert!(cursor.split_after().is_empty());
+        assert_eq!(s.size(), 10);
+    }
 }
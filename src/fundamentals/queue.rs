@@ -2,27 +2,37 @@
 //!
 //! A queue implemented with linked list
 
-use std::ptr;
+use std::marker::PhantomData;
+use std::ptr::NonNull;
 
-type Link<T> = Option<Box<Node<T>>>;
+// Every node is leaked from a `Box` and owned by the queue, so `first`, the `next`
+// links and `last` all carry the same raw-pointer provenance (as in `Deque`);
+// a `Box` link would be retagged as unique on every move, invalidating `last`.
+type Link<T> = Option<NonNull<Node<T>>>;
 struct Node<T> {
     item: T,
     next: Link<T>,
 }
 pub struct Queue<T> {
     first: Link<T>,
-    // For one-element stack, both `first` and `last` would point
-    // to the same one. One solution is to use `Rc<RefCell<Node<T>>>`.
-    last: *mut Node<T>,
+    // For one-element queue, both `first` and `last` point to the same node.
+    last: Link<T>,
     n: usize,
+    _marker: PhantomData<T>,
 }
 
+// The queue owns every node, and links are only written through `&mut self`,
+// so the queue is as thread-safe as a `Vec<T>`.
+unsafe impl<T: Send> Send for Queue<T> {}
+unsafe impl<T: Sync> Sync for Queue<T> {}
+
 impl<T> Queue<T> {
     pub fn new() -> Self {
         Queue {
             first: None,
-            last: ptr::null_mut(),
+            last: None,
             n: 0,
+            _marker: PhantomData,
         }
     }
 
@@ -35,32 +45,61 @@ impl<T> Queue<T> {
     }
 
     pub fn enqueue(&mut self, t: T) {
-        let mut new_last = Box::new(Node {
+        let node = Box::new(Node {
             item: t,
             next: None,
         });
-        let raw_last: *mut _ = &mut *new_last;
-
-        if !self.last.is_null() {
-            unsafe {
-                (*self.last).next = Some(new_last);
-            }
-        } else {
-            self.first = Some(new_last);
+        let node = NonNull::from(Box::leak(node));
+        match self.last {
+            Some(old) => unsafe { (*old.as_ptr()).next = Some(node) },
+            None => self.first = Some(node),
         }
-        self.last = raw_last;
+        self.last = Some(node);
+        self.n += 1;
     }
 
     pub fn dequeue(&mut self) -> Option<T> {
-        self.first.take().map(|head| {
-            // let head = *head;
-            self.first = head.next;
+        self.first.map(|node| {
+            // the node was leaked from a `Box` in `enqueue`
+            let boxed = unsafe { Box::from_raw(node.as_ptr()) };
+            self.first = boxed.next;
             if self.first.is_none() {
-                self.last = ptr::null_mut();
+                self.last = None;
             }
-            head.item
+            self.n -= 1;
+            boxed.item
         })
     }
+
+    /// Returns the item least recently added to this queue.
+    pub fn peek(&self) -> Option<&T> {
+        self.first.map(|node| unsafe { &(*node.as_ptr()).item })
+    }
+
+    /// Returns a mutable reference to the item least recently added to this queue.
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        self.first.map(|node| unsafe { &mut (*node.as_ptr()).item })
+    }
+
+    /// Moves every item of `other` to the back of this queue, leaving `other` empty.
+    /// It relinks the two chains in O(1).
+    pub fn append(&mut self, other: &mut Queue<T>) {
+        if other.is_empty() {
+            return;
+        }
+        match self.last {
+            Some(last) => unsafe { (*last.as_ptr()).next = other.first.take() },
+            None => self.first = other.first.take(),
+        }
+        self.last = other.last.take();
+        self.n += other.n;
+        other.n = 0;
+    }
+
+    /// Returns the item most recently added to this queue.
+    pub fn peek_back(&self) -> Option<&T> {
+        self.last.map(|node| unsafe { &(*node.as_ptr()).item })
+    }
 }
 
 impl<T> Default for Queue<T> {
@@ -71,9 +110,22 @@ impl<T> Default for Queue<T> {
 
 impl<T> Drop for Queue<T> {
     fn drop(&mut self) {
-        let mut cur_link = self.first.take();
-        while let Some(mut boxed_node) = cur_link {
-            cur_link = boxed_node.next.take();
+        while self.dequeue().is_some() {}
+    }
+}
+
+impl<T> FromIterator<T> for Queue<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut q = Queue::new();
+        q.extend(iter);
+        q
+    }
+}
+
+impl<T> Extend<T> for Queue<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for t in iter {
+            self.enqueue(t);
         }
     }
 }
@@ -97,15 +149,17 @@ impl<T> IntoIterator for Queue<T> {
 }
 
 pub struct Iter<'a, T> {
-    next: Option<&'a Node<T>>,
+    next: Link<T>,
+    _marker: PhantomData<&'a T>,
 }
 
 impl<'a, T> Iterator for Iter<'a, T> {
     type Item = &'a T;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.next.map(|node| {
-            self.next = node.next.as_deref();
+        self.next.map(|node| unsafe {
+            let node = &*node.as_ptr();
+            self.next = node.next;
             &node.item
         })
     }
@@ -114,7 +168,8 @@ impl<'a, T> Iterator for Iter<'a, T> {
 impl<T> Queue<T> {
     pub fn iter(&self) -> Iter<'_, T> {
         Iter {
-            next: self.first.as_deref(),
+            next: self.first,
+            _marker: PhantomData,
         }
     }
 }
@@ -129,10 +184,77 @@ mod tests {
         q.enqueue(4);
         q.enqueue(5);
         q.enqueue(6);
+        assert_eq!(q.size(), 3);
         assert_eq!(q.dequeue(), Some(4));
         assert_eq!(q.dequeue(), Some(5));
         assert_eq!(q.dequeue(), Some(6));
         assert_eq!(q.dequeue(), None);
+        assert!(q.is_empty());
+    }
+
+    #[test]
+    fn peek() {
+        let mut q = Queue::new();
+        assert_eq!(q.peek(), None);
+        assert_eq!(q.peek_back(), None);
+
+        q.enqueue(4);
+        assert_eq!(q.peek(), Some(&4));
+        assert_eq!(q.peek_back(), Some(&4));
+
+        q.enqueue(5);
+        q.enqueue(6);
+        assert_eq!(q.peek(), Some(&4));
+        assert_eq!(q.peek_back(), Some(&6));
+        // peeking does not dequeue
+        assert_eq!(q.size(), 3);
+
+        q.dequeue();
+        q.dequeue();
+        assert_eq!(q.peek(), Some(&6));
+        assert_eq!(q.peek_back(), Some(&6));
+        q.dequeue();
+        assert_eq!(q.peek_back(), None);
+    }
+
+    #[test]
+    fn peek_mut() {
+        let mut q = Queue::new();
+        assert_eq!(q.peek_mut(), None);
+        for i in 1..=5 {
+            q.enqueue(i);
+        }
+        // inspect, then conditionally dequeue
+        while q.peek().is_some_and(|&front| front < 3) {
+            q.dequeue();
+        }
+        if let Some(front) = q.peek_mut() {
+            *front *= 10;
+        }
+        assert_eq!(q.size(), 3);
+        assert_eq!(q.dequeue(), Some(30));
+        assert_eq!(q.peek(), Some(&4));
+
+        // the front is also the back
+        let mut q = Queue::new();
+        q.enqueue(String::from("a"));
+        q.peek_mut().unwrap().push('b');
+        assert_eq!(q.peek_back().map(|s| s.as_str()), Some("ab"));
+    }
+
+    #[test]
+    fn collect_extend() {
+        let mut q: Queue<i32> = (1..=3).collect();
+        assert_eq!(q.size(), 3);
+        q.extend(vec![4, 5]);
+        assert_eq!(q.peek_back(), Some(&5));
+        assert_eq!(q.dequeue(), Some(1));
+
+        let v: Vec<i32> = q.into_iter().collect();
+        assert_eq!(v, vec![2, 3, 4, 5]);
+
+        let q: Queue<i32> = std::iter::empty().collect();
+        assert!(q.is_empty());
     }
 
     #[test]
@@ -176,4 +298,39 @@ mod tests {
         assert_eq!(iterator.next(), Some(&6));
         assert_eq!(iterator.next(), None);
     }
+
+    #[test]
+    fn append() {
+        let mut q: Queue<i32> = (0..3).collect();
+        let mut other: Queue<i32> = (3..6).collect();
+        let tail: *const i32 = other.peek_back().unwrap();
+
+        q.append(&mut other);
+        assert_eq!(q.size(), 6);
+        assert!(other.is_empty());
+        assert_eq!(other.peek_back(), None);
+        // the tail node is relinked, not copied
+        assert!(std::ptr::eq(q.peek_back().unwrap(), tail));
+
+        // both queues stay usable
+        q.enqueue(6);
+        other.enqueue(7);
+        assert_eq!(
+            q.iter().copied().collect::<Vec<i32>>(),
+            (0..7).collect::<Vec<i32>>()
+        );
+        assert_eq!(other.dequeue(), Some(7));
+
+        // appending to an empty queue, and appending an empty queue
+        let mut empty = Queue::new();
+        empty.append(&mut q);
+        assert_eq!(empty.size(), 7);
+        empty.append(&mut q);
+        assert_eq!(empty.size(), 7);
+        assert_eq!(empty.peek_back(), Some(&6));
+        assert_eq!(
+            empty.into_iter().collect::<Vec<i32>>(),
+            (0..7).collect::<Vec<i32>>()
+        );
+    }
 }
@@ -1,7 +1,12 @@
-//! # Quick union of UF
+//! # Weighted quick-union with path compression
 //!
+//! Weighted by size, with path compression by halving in `find`,
+//! so that any sequence of operations takes near-constant amortized time.
+//! This is the union-find to use; `weighted_quick_union_uf` is the same without
+//! path compression, and `quick_find_uf` is the book's slower baseline.
 pub struct UF {
-    id: Vec<usize>,
+    id: Vec<usize>, // parent link
+    sz: Vec<usize>, // size of component for roots
     count: usize,
 }
 
@@ -9,26 +14,37 @@ impl UF {
     pub fn new(n: usize) -> UF {
         UF {
             id: (0..n).collect(),
+            sz: vec![1; n],
             count: n,
         }
     }
 
+    /// Returns the number of components.
     pub fn count(&self) -> usize {
         self.count
     }
 
-    pub fn connected(&self, p: usize, q: usize) -> bool {
+    pub fn connected(&mut self, p: usize, q: usize) -> bool {
         self.find(p) == self.find(q)
     }
 
-    pub fn find(&self, p: usize) -> usize {
+    /// Returns the root of the component containing p,
+    /// pointing every other node on the way to its grandparent.
+    pub fn find(&mut self, p: usize) -> usize {
         let mut component = p;
         while component != self.id[component] {
+            self.id[component] = self.id[self.id[component]];
             component = self.id[component];
         }
         component
     }
 
+    /// Returns the number of elements in the component containing p.
+    pub fn size(&mut self, p: usize) -> usize {
+        let root = self.find(p);
+        self.sz[root]
+    }
+
     pub fn union(&mut self, p: usize, q: usize) {
         let p_root = self.find(p);
         let q_root = self.find(q);
@@ -36,7 +52,14 @@ impl UF {
             return;
         }
 
-        self.id[p_root] = q_root;
+        // make smaller root point to larger one
+        if self.sz[p_root] < self.sz[q_root] {
+            self.id[p_root] = q_root;
+            self.sz[q_root] += self.sz[p_root];
+        } else {
+            self.id[q_root] = p_root;
+            self.sz[p_root] += self.sz[q_root];
+        }
         self.count -= 1;
     }
 }
@@ -70,4 +93,44 @@ mod tests {
 
         assert_eq!(uf.count(), 2);
     }
+
+    // number of links from p up to its root, without compressing
+    fn depth(uf: &UF, p: usize) -> usize {
+        let mut d = 0;
+        let mut p = p;
+        while p != uf.id[p] {
+            p = uf.id[p];
+            d += 1;
+        }
+        d
+    }
+
+    #[test]
+    fn chain() {
+        let n = 1000;
+        let mut uf = UF::new(n);
+        for i in 1..n {
+            uf.union(i - 1, i);
+        }
+        assert_eq!(uf.count(), 1);
+        assert_eq!(uf.size(0), n);
+        assert_eq!(uf.find(0), uf.find(n - 1));
+        // weighting alone keeps every tree within lg n
+        assert!((0..n).all(|p| depth(&uf, p) <= 10));
+
+        let mut uf = UF::new(n);
+        for i in (0..n).step_by(2) {
+            uf.union(i, i + 1);
+        }
+        assert_eq!(uf.count(), n / 2);
+        assert_eq!(uf.size(6), 2);
+        for i in (2..n).step_by(2) {
+            uf.union(0, i);
+        }
+        assert_eq!(uf.count(), 1);
+        for p in 0..n {
+            uf.find(p);
+        }
+        assert!((0..n).all(|p| depth(&uf, p) <= 1));
+    }
 }
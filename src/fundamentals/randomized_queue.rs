@@ -0,0 +1,170 @@
+//! # Randomized Queue
+//!
+//! A queue where `dequeue` removes an item chosen uniformly at random.
+//! Items are kept in a resizing array that, like `ResizingStack`, doubles when full
+//! and halves when one-quarter full; the random item is swapped with the last one
+//! before it is removed, so that every operation takes constant amortized time.
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+pub struct RandomizedQueue<T> {
+    a: Vec<T>, // its capacity is resized explicitly, as in `ResizingStack`
+    rng: StdRng,
+}
+
+impl<T> RandomizedQueue<T> {
+    pub fn new() -> Self {
+        Self::with_rng(StdRng::from_entropy())
+    }
+
+    /// Creates an empty queue whose random choices are determined by `seed`.
+    pub fn with_seed(seed: u64) -> Self {
+        Self::with_rng(StdRng::seed_from_u64(seed))
+    }
+
+    fn with_rng(rng: StdRng) -> Self {
+        RandomizedQueue {
+            a: Vec::with_capacity(8),
+            rng,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.a.is_empty()
+    }
+
+    pub fn size(&self) -> usize {
+        self.a.len()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.a.capacity()
+    }
+
+    fn resize(&mut self, new_cap: usize) {
+        assert!(new_cap >= self.a.len());
+        if new_cap > self.a.capacity() {
+            self.a.reserve_exact(new_cap - self.a.len());
+        } else {
+            self.a.shrink_to(new_cap);
+        }
+    }
+
+    pub fn enqueue(&mut self, t: T) {
+        if self.a.len() == self.a.capacity() {
+            self.resize((self.a.capacity() * 2).max(1));
+        }
+        self.a.push(t);
+    }
+
+    /// Removes and returns an item chosen uniformly at random, or `None` if the queue is empty.
+    pub fn dequeue(&mut self) -> Option<T> {
+        if self.a.is_empty() {
+            return None;
+        }
+        let i = self.rng.gen_range(0..self.a.len());
+        let item = self.a.swap_remove(i);
+        let n = self.a.len();
+        if n > 0 && n == self.a.capacity() / 4 {
+            self.resize(self.a.capacity() / 2);
+        }
+        Some(item)
+    }
+
+    /// Returns, but does not remove, an item chosen uniformly at random.
+    pub fn sample(&mut self) -> Option<&T> {
+        self.a.choose(&mut self.rng)
+    }
+
+    /// Returns an iterator over the items in a random order, which is
+    /// drawn independently on every call.
+    pub fn iter(&mut self) -> Iter<'_, T> {
+        let mut order: Vec<&T> = self.a.iter().collect();
+        order.shuffle(&mut self.rng);
+        Iter { order }
+    }
+}
+
+impl<T> Default for RandomizedQueue<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub struct Iter<'a, T> {
+    order: Vec<&'a T>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.order.pop()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn enqueue_dequeue() {
+        let mut q = RandomizedQueue::with_seed(96);
+        assert!(q.is_empty());
+        assert_eq!(q.dequeue(), None);
+        assert_eq!(q.sample(), None);
+
+        for i in 0..100 {
+            q.enqueue(i);
+        }
+        assert_eq!(q.size(), 100);
+        assert_eq!(q.capacity(), 128);
+        let s = *q.sample().unwrap();
+        assert!(s < 100);
+        assert_eq!(q.size(), 100);
+
+        let mut out = Vec::new();
+        while let Some(i) = q.dequeue() {
+            out.push(i);
+            assert_eq!(q.size(), 100 - out.len());
+        }
+        assert!(q.is_empty());
+        assert_ne!(out, (0..100).collect::<Vec<i32>>());
+        // every item is dequeued exactly once
+        out.sort_unstable();
+        assert_eq!(out, (0..100).collect::<Vec<i32>>());
+        // shrunk as items were removed
+        assert_eq!(q.capacity(), 2);
+    }
+
+    #[test]
+    fn seeded() {
+        let run = |seed| {
+            let mut q = RandomizedQueue::with_seed(seed);
+            for i in 0..20 {
+                q.enqueue(i);
+            }
+            std::iter::from_fn(|| q.dequeue()).collect::<Vec<i32>>()
+        };
+        assert_eq!(run(96), run(96));
+        assert_ne!(run(96), run(97));
+    }
+
+    #[test]
+    fn iter() {
+        let mut q = RandomizedQueue::with_seed(96);
+        for i in 0..50 {
+            q.enqueue(i);
+        }
+        let first = q.iter().copied().collect::<Vec<i32>>();
+        let second = q.iter().copied().collect::<Vec<i32>>();
+        assert_ne!(first, second);
+        let mut sorted = first.clone();
+        sorted.sort_unstable();
+        assert_eq!(sorted, (0..50).collect::<Vec<i32>>());
+        // iterating does not remove anything
+        assert_eq!(q.size(), 50);
+    }
+}
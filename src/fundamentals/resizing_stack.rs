@@ -32,6 +32,24 @@ impl<T> ResizingStack<T> {
         }
     }
 
+    /// Creates an empty stack with room for exactly `cap` items before it needs to resize.
+    pub fn with_capacity(cap: usize) -> Self {
+        let a = if cap == 0 {
+            // nothing is allocated until the first push
+            NonNull::dangling()
+        } else {
+            let layout = Layout::array::<T>(cap).unwrap();
+            let ptr = unsafe { alloc::alloc(layout) };
+            NonNull::new(ptr as *mut T).unwrap()
+        };
+        ResizingStack {
+            a,
+            n: 0,
+            capacity: cap,
+            _marker: PhantomData,
+        }
+    }
+
     pub fn is_empty(&self) -> bool {
         self.n == 0
     }
@@ -44,9 +62,13 @@ impl<T> ResizingStack<T> {
         assert!(new_cap >= self.n);
 
         let new_layout = Layout::array::<T>(new_cap).unwrap();
-        let old_layout = Layout::array::<T>(self.capacity).unwrap();
-        let old_ptr = self.a.as_ptr() as *mut u8;
-        let new_ptr = unsafe { alloc::realloc(old_ptr, old_layout, new_layout.size()) };
+        let new_ptr = if self.capacity == 0 {
+            unsafe { alloc::alloc(new_layout) }
+        } else {
+            let old_layout = Layout::array::<T>(self.capacity).unwrap();
+            let old_ptr = self.a.as_ptr() as *mut u8;
+            unsafe { alloc::realloc(old_ptr, old_layout, new_layout.size()) }
+        };
 
         self.a = NonNull::new(new_ptr as *mut T).unwrap();
         self.capacity = new_cap;
@@ -54,7 +76,7 @@ impl<T> ResizingStack<T> {
 
     pub fn push(&mut self, t: T) {
         if self.n == self.capacity {
-            self.resize(self.capacity * 2);
+            self.resize((self.capacity * 2).max(1));
         }
         unsafe {
             ptr::write(self.a.as_ptr().add(self.n), t);
@@ -75,6 +97,50 @@ impl<T> ResizingStack<T> {
             item
         }
     }
+
+    /// Keeps the first `len` items (from the bottom) and drops the rest.
+    /// The capacity is unchanged.
+    pub fn truncate(&mut self, len: usize) {
+        if len >= self.n {
+            return;
+        }
+        let tail = ptr::slice_from_raw_parts_mut(unsafe { self.a.as_ptr().add(len) }, self.n - len);
+        // update n first, so that a panicking destructor cannot cause a double drop
+        self.n = len;
+        unsafe {
+            ptr::drop_in_place(tail);
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Keeps only the items for which `f` returns true, preserving their order.
+    /// It halves the capacity, as `pop` does, while the stack is at most one-quarter full.
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, mut f: F) {
+        let len = self.n;
+        // if `f` or a destructor panics, the rest are leaked rather than dropped twice
+        self.n = 0;
+        let mut kept = 0;
+        for i in 0..len {
+            unsafe {
+                let cur = self.a.as_ptr().add(i);
+                if f(&*cur) {
+                    if kept != i {
+                        ptr::copy_nonoverlapping(cur, self.a.as_ptr().add(kept), 1);
+                    }
+                    kept += 1;
+                } else {
+                    ptr::drop_in_place(cur);
+                }
+            }
+        }
+        self.n = kept;
+        while self.n > 0 && self.n <= self.capacity / 4 {
+            self.resize(self.capacity / 2);
+        }
+    }
 }
 
 impl<T> Deref for ResizingStack<T> {
@@ -159,6 +225,7 @@ impl<T> Default for ResizingStack<T> {
 
 #[cfg(test)]
 mod tests {
+    use std::rc::Rc;
     use std::vec;
 
     use super::*;
@@ -189,6 +256,87 @@ mod tests {
         assert_eq!(8, s.capacity);
     }
 
+    #[test]
+    fn with_capacity() {
+        let mut s = ResizingStack::with_capacity(100);
+        assert_eq!(s.capacity(), 100);
+        for i in 0..100 {
+            s.push(i);
+        }
+        assert_eq!(s.capacity(), 100);
+        s.push(100);
+        assert_eq!(s.capacity(), 200);
+        assert_eq!(s.pop(), Some(100));
+
+        let mut s = ResizingStack::with_capacity(0);
+        assert_eq!(s.capacity(), 0);
+        assert_eq!(s.pop(), None);
+        s.push(String::from("a"));
+        s.push(String::from("b"));
+        assert_eq!(s.capacity(), 2);
+        assert_eq!(s.pop(), Some(String::from("b")));
+
+        // an empty stack that never allocated
+        let _ = ResizingStack::<String>::with_capacity(0);
+    }
+
+    #[test]
+    fn truncate() {
+        let item = Rc::new(());
+        let mut s = ResizingStack::new();
+        for _ in 0..6 {
+            s.push(Rc::clone(&item));
+        }
+        assert_eq!(Rc::strong_count(&item), 7);
+
+        s.truncate(10);
+        assert_eq!(s.size(), 6);
+
+        s.truncate(2);
+        assert_eq!(s.size(), 2);
+        assert_eq!(s.capacity(), 8);
+        // the truncated items have been dropped
+        assert_eq!(Rc::strong_count(&item), 3);
+
+        s.push(Rc::clone(&item));
+        assert_eq!(s.size(), 3);
+        s.truncate(0);
+        assert!(s.is_empty());
+        assert_eq!(Rc::strong_count(&item), 1);
+    }
+
+    #[test]
+    fn retain() {
+        let item = Rc::new(());
+        let mut s = ResizingStack::new();
+        for i in 0..10 {
+            s.push((i, Rc::clone(&item)));
+        }
+        assert_eq!(s.capacity(), 16);
+
+        s.retain(|(i, _)| i % 3 != 0);
+        assert_eq!(
+            s.iter().map(|(i, _)| *i).collect::<Vec<i32>>(),
+            vec![8, 7, 5, 4, 2, 1]
+        );
+        // the removed items have been dropped
+        assert_eq!(Rc::strong_count(&item), 7);
+        assert_eq!(s.capacity(), 16);
+
+        s.retain(|(i, _)| *i > 6);
+        assert_eq!(s.size(), 2);
+        assert_eq!(Rc::strong_count(&item), 3);
+        // 2 of 16 is below one-quarter
+        assert_eq!(s.capacity(), 4);
+        assert_eq!(s.pop().map(|(i, _)| i), Some(8));
+
+        s.retain(|_| false);
+        assert!(s.is_empty());
+        assert_eq!(Rc::strong_count(&item), 1);
+        s.push((1, Rc::clone(&item)));
+        assert_eq!(s.size(), 1);
+    }
+
     #[test]
     fn values() {
         let mut s = ResizingStack::new();
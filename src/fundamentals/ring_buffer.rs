@@ -0,0 +1,187 @@
+//! # Ring buffer
+//!
+//! A fixed-capacity FIFO queue stored in a circular array.
+//! All the storage is allocated by `new`; `push_back` on a full buffer
+//! hands the item back as an `Err` instead of growing.
+
+pub struct RingBuffer<T> {
+    a: Vec<Option<T>>,
+    head: usize, // index of the first item
+    n: usize,    // number of items
+}
+
+impl<T> RingBuffer<T> {
+    pub fn new(capacity: usize) -> Self {
+        RingBuffer {
+            a: (0..capacity).map(|_| None).collect(),
+            head: 0,
+            n: 0,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.n == self.a.len()
+    }
+
+    pub fn size(&self) -> usize {
+        self.n
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.a.len()
+    }
+
+    // physical index of the i-th item
+    fn index(&self, i: usize) -> usize {
+        (self.head + i) % self.a.len()
+    }
+
+    /// Adds the item to the back, or returns it if the buffer is full.
+    pub fn push_back(&mut self, t: T) -> Result<(), T> {
+        if self.is_full() {
+            return Err(t);
+        }
+        let tail = self.index(self.n);
+        self.a[tail] = Some(t);
+        self.n += 1;
+        Ok(())
+    }
+
+    /// Removes and returns the item at the front.
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        let item = self.a[self.head].take();
+        self.head = self.index(1);
+        self.n -= 1;
+        item
+    }
+
+    /// Returns the item at the front.
+    pub fn peek_front(&self) -> Option<&T> {
+        if self.is_empty() {
+            return None;
+        }
+        self.a[self.head].as_ref()
+    }
+
+    /// Returns the item at the back.
+    pub fn peek_back(&self) -> Option<&T> {
+        if self.is_empty() {
+            return None;
+        }
+        self.a[self.index(self.n - 1)].as_ref()
+    }
+
+    /// Returns the items in insertion order.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { rb: self, i: 0 }
+    }
+}
+
+pub struct Iter<'a, T> {
+    rb: &'a RingBuffer<T>,
+    i: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.i == self.rb.n {
+            return None;
+        }
+        let item = self.rb.a[self.rb.index(self.i)].as_ref();
+        self.i += 1;
+        item
+    }
+}
+
+impl<'a, T> IntoIterator for &'a RingBuffer<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+pub struct IntoIter<T>(RingBuffer<T>);
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.pop_front()
+    }
+}
+
+impl<T> IntoIterator for RingBuffer<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn push_pop() {
+        let mut rb = RingBuffer::new(3);
+        assert_eq!(rb.pop_front(), None);
+        assert_eq!(rb.peek_front(), None);
+
+        assert_eq!(rb.push_back(1), Ok(()));
+        assert_eq!(rb.push_back(2), Ok(()));
+        assert_eq!(rb.push_back(3), Ok(()));
+        assert!(rb.is_full());
+        assert_eq!(rb.push_back(4), Err(4));
+        assert_eq!(rb.peek_front(), Some(&1));
+        assert_eq!(rb.peek_back(), Some(&3));
+
+        assert_eq!(rb.pop_front(), Some(1));
+        // wraps around
+        assert_eq!(rb.push_back(4), Ok(()));
+        assert_eq!(rb.peek_back(), Some(&4));
+        assert_eq!(rb.size(), 3);
+        assert_eq!(rb.pop_front(), Some(2));
+        assert_eq!(rb.pop_front(), Some(3));
+        assert_eq!(rb.pop_front(), Some(4));
+        assert_eq!(rb.pop_front(), None);
+        assert!(rb.is_empty());
+    }
+
+    #[test]
+    fn iter() {
+        let mut rb = RingBuffer::new(4);
+        for i in 0..4 {
+            rb.push_back(i).unwrap();
+        }
+        rb.pop_front();
+        rb.pop_front();
+        rb.push_back(4).unwrap();
+        rb.push_back(5).unwrap();
+
+        assert_eq!(rb.iter().collect::<Vec<&i32>>(), vec![&2, &3, &4, &5]);
+        assert_eq!((&rb).into_iter().count(), 4);
+        assert_eq!(rb.into_iter().collect::<Vec<i32>>(), vec![2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn zero_capacity() {
+        let mut rb = RingBuffer::new(0);
+        assert!(rb.is_empty() && rb.is_full());
+        assert_eq!(rb.push_back('a'), Err('a'));
+        assert_eq!(rb.pop_front(), None);
+        assert_eq!(rb.peek_back(), None);
+    }
+}
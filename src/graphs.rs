@@ -1,31 +1,55 @@
+pub mod acyclic_lp;
 pub mod acyclic_sp;
+pub mod algebraic;
+pub mod articulation_points;
+pub mod bellman_ford_sp;
 pub mod bfs_directed_paths;
 pub mod bfs_paths;
+pub mod bidirectional_dijkstra;
 pub mod bipartite;
+pub mod bridges;
 pub mod cc;
+pub mod condensation;
+pub mod cpm;
 pub mod cycle;
 pub mod dfs;
 pub mod dfs_directed_paths;
 pub mod dfs_order;
 pub mod dfs_paths;
 pub mod digraph;
+pub mod dijkstra_radix;
 pub mod dijkstra_sp;
 pub mod dijkstra_undirected_sp;
 pub mod directed_cycle;
 pub mod directed_dfs;
 pub mod directed_edge;
 pub mod edge;
+pub mod eulerian;
+pub mod flow_edge;
+pub mod flow_network;
+pub mod floyd_warshall;
+pub mod ford_fulkerson;
+pub mod functional;
 pub mod graph;
 pub mod graph_generator;
+pub mod graph_reader;
+pub mod iddfs;
+pub mod interop;
 pub mod kosaraju_scc;
 pub mod kruskal_mst;
 pub mod lazy_prim_mst;
+pub mod metrics;
 pub mod naive_bellman_ford_sp;
 pub mod naive_scc;
+pub mod prereq;
+pub mod prim_mst;
+pub mod simple_graph;
 pub mod symbol_digraph;
 pub mod symbol_graph;
+pub mod tarjan_scc;
 pub mod topological;
 pub mod topological_x;
+pub mod transitive_closure;
 pub mod weighted_digraph;
 pub mod weighted_directed_cycle;
 pub mod weighted_graph;
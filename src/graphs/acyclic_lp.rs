@@ -0,0 +1,162 @@
+//! # Computes longest paths in an edge weighted acyclic digraph.
+
+use super::{
+    directed_edge::DirectedEdge, topological::Topological, weighted_digraph::EdgeWeightedDiagraph,
+};
+
+/// Solving the single-source longest paths problem in edge-weighted acyclic
+/// graphs (DAGs). The edge weights can be positive, negative, or zero.
+///
+/// This implementation is `AcyclicSP` with the comparison reversed: distances start at
+/// `f64::MIN`, and an edge is relaxed when it gives a longer path. It is the basis of the
+/// critical path method for parallel job scheduling.
+/// The time complexity is O(V + E).
+pub struct AcyclicLP {
+    dist_to: Vec<f64>,                  // dist_to[v] = distance of longest s->v path
+    edge_to: Vec<Option<DirectedEdge>>, // edge_to[v] = last edge on longest s->v path
+}
+
+impl AcyclicLP {
+    pub fn new(g: &EdgeWeightedDiagraph, s: usize) -> Self {
+        let mut lp = AcyclicLP {
+            dist_to: vec![f64::MIN; g.v()],
+            edge_to: vec![None; g.v()],
+        };
+        lp.dist_to[s] = 0.0;
+
+        // visit vertices in topological order
+        let topological = Topological::from_weighted_diagraph(g);
+        if !topological.has_order() {
+            panic!("Digraph is not acyclic");
+        }
+        for v in topological.order() {
+            if !lp.has_path_to(v) {
+                // f64::MIN plus a weight is not a distance
+                continue;
+            }
+            for &e in g.adj_ref(v) {
+                lp.relax(e);
+            }
+        }
+        lp
+    }
+
+    fn relax(&mut self, e: DirectedEdge) {
+        let v = e.from();
+        let w = e.to();
+        if self.dist_to[w] < self.dist_to[v] + e.weight() {
+            self.dist_to[w] = self.dist_to[v] + e.weight();
+            self.edge_to[w] = Some(e);
+        }
+    }
+
+    /// Returns the length of a longest path from s to v, or `f64::MIN` if there is none.
+    pub fn dist_to(&self, v: usize) -> f64 {
+        self.dist_to[v]
+    }
+
+    pub fn has_path_to(&self, v: usize) -> bool {
+        self.dist_to[v] > f64::MIN
+    }
+
+    pub fn path_to(&self, v: usize) -> std::vec::IntoIter<DirectedEdge> {
+        let mut path = Vec::new();
+        let mut edge = self.edge_to[v];
+        while let Some(e) = edge {
+            path.push(e);
+            edge = self.edge_to[e.from()];
+        }
+        path.reverse();
+        path.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn tiny_ewdag() {
+        let mut g = EdgeWeightedDiagraph::new(8);
+        g.add_edge(DirectedEdge::new(5, 4, 0.35));
+        g.add_edge(DirectedEdge::new(4, 7, 0.37));
+        g.add_edge(DirectedEdge::new(5, 7, 0.28));
+        g.add_edge(DirectedEdge::new(5, 1, 0.32));
+        g.add_edge(DirectedEdge::new(4, 0, 0.38));
+        g.add_edge(DirectedEdge::new(0, 2, 0.26));
+        g.add_edge(DirectedEdge::new(3, 7, 0.39));
+        g.add_edge(DirectedEdge::new(1, 3, 0.29));
+        g.add_edge(DirectedEdge::new(7, 2, 0.34));
+        g.add_edge(DirectedEdge::new(6, 2, 0.40));
+        g.add_edge(DirectedEdge::new(3, 6, 0.52));
+        g.add_edge(DirectedEdge::new(6, 0, 0.58));
+        g.add_edge(DirectedEdge::new(6, 4, 0.93));
+
+        let lp = AcyclicLP::new(&g, 5);
+        let expected = [2.44, 0.32, 2.77, 0.61, 2.06, 0.0, 1.13, 2.43];
+        for (v, &d) in expected.iter().enumerate() {
+            assert!(lp.has_path_to(v));
+            assert!((lp.dist_to(v) - d).abs() < 1e-9, "vertex {}", v);
+        }
+        assert_eq!(
+            lp.path_to(2).map(|e| e.to()).collect::<Vec<usize>>(),
+            vec![1, 3, 6, 4, 7, 2]
+        );
+        assert_eq!(lp.path_to(5).count(), 0);
+
+        // nothing reaches 5 from 3
+        let lp = AcyclicLP::new(&g, 3);
+        assert!(!lp.has_path_to(5));
+        assert_eq!(lp.path_to(5).count(), 0);
+        assert!((lp.dist_to(2) - 2.16).abs() < 1e-9);
+    }
+
+    #[test]
+    fn negated_shortest_paths() {
+        use crate::graphs::acyclic_sp::AcyclicSP;
+
+        // longest paths are the shortest paths with every weight negated
+        let edges = [
+            (5, 4, 0.35),
+            (4, 7, 0.37),
+            (5, 7, 0.28),
+            (5, 1, 0.32),
+            (4, 0, 0.38),
+            (0, 2, 0.26),
+            (3, 7, 0.39),
+            (1, 3, 0.29),
+            (7, 2, 0.34),
+            (6, 2, 0.40),
+            (3, 6, 0.52),
+            (6, 0, 0.58),
+            (6, 4, 0.93),
+        ];
+        let mut g = EdgeWeightedDiagraph::new(8);
+        let mut negated = EdgeWeightedDiagraph::new(8);
+        for &(v, w, weight) in &edges {
+            g.add_edge(DirectedEdge::new(v, w, weight));
+            negated.add_edge(DirectedEdge::new(v, w, -weight));
+        }
+        for s in 0..g.v() {
+            let lp = AcyclicLP::new(&g, s);
+            let sp = AcyclicSP::new(&negated, s);
+            for v in 0..g.v() {
+                assert_eq!(lp.has_path_to(v), sp.has_path_to(v));
+                if lp.has_path_to(v) {
+                    assert!((lp.dist_to(v) + sp.dist_to(v)).abs() < 1e-9);
+                    let length: f64 = lp.path_to(v).map(|e| e.weight()).sum();
+                    assert!((length - lp.dist_to(v)).abs() < 1e-9);
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "not acyclic")]
+    fn cycle() {
+        let mut g = EdgeWeightedDiagraph::new(2);
+        g.add_edge(DirectedEdge::new(0, 1, 1.0));
+        g.add_edge(DirectedEdge::new(1, 0, 1.0));
+        AcyclicLP::new(&g, 0);
+    }
+}
@@ -30,7 +30,7 @@ impl AcyclicSP {
             panic!("Digraph is not acyclic");
         }
         for v in topological.order() {
-            for e in g.adj(v) {
+            for &e in g.adj_ref(v) {
                 sp.relax(e);
             }
         }
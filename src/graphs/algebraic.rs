@@ -0,0 +1,162 @@
+//! # Algebraic connectivity
+//!
+//! The Laplacian of a graph is `L = D - A`, where `D` holds the degrees and `A` is the adjacency matrix.
+//! Its smallest eigenvalue is 0, with the all-ones eigenvector. The second smallest, λ2,
+//! is the algebraic connectivity: it is 0 iff the graph is disconnected, and small when
+//! the graph has a sparse cut. An eigenvector of λ2 is a Fiedler vector; splitting
+//! the vertices by the sign of its entries is a simple balanced partitioner.
+//!
+//! The estimate runs power iteration on the shifted matrix `cI - L`, with `c = 2 * max degree + 1`
+//! larger than every eigenvalue of `L`, so that `c - λ2` becomes the largest eigenvalue
+//! once the all-ones direction is projected out at every step. `L x` is computed from
+//! the adjacency lists, so nothing takes more than O(V) extra space, and each iteration
+//! takes O(V + E) time.
+//!
+//! The results are approximate. Power iteration converges at the rate `(c - λ3) / (c - λ2)`, which
+//! is slow when λ2 and λ3 are close, so large or nearly symmetric graphs need more iterations.
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use super::graph::Graph;
+
+const BISECT_ITERATIONS: usize = 1000;
+
+// y = L x
+fn laplacian_times(g: &Graph, x: &[f64], y: &mut [f64]) {
+    for v in 0..g.v() {
+        let adj = g.adj(v);
+        y[v] = adj.len() as f64 * x[v] - adj.iter().map(|&w| x[w]).sum::<f64>();
+    }
+}
+
+fn dot(x: &[f64], y: &[f64]) -> f64 {
+    x.iter().zip(y).map(|(a, b)| a * b).sum()
+}
+
+// removes the component along the all-ones vector, then scales to unit length
+fn project_normalize(x: &mut [f64]) {
+    let mean = x.iter().sum::<f64>() / x.len() as f64;
+    x.iter_mut().for_each(|a| *a -= mean);
+    let norm = dot(x, x).sqrt();
+    if norm > 0.0 {
+        x.iter_mut().for_each(|a| *a /= norm);
+    }
+}
+
+/// Estimates the algebraic connectivity λ2 of `g` and a unit Fiedler vector, by `iterations`
+/// steps of power iteration from a random start drawn with `seed`.
+/// The vector's sign is chosen so that its first nonzero entry is positive.
+/// Graphs with fewer than 2 vertices give `(0.0, zeros)`.
+pub fn fiedler_estimate(g: &Graph, iterations: usize, seed: u64) -> (f64, Vec<f64>) {
+    let n = g.v();
+    if n < 2 {
+        return (0.0, vec![0.0; n]);
+    }
+    let max_degree = (0..n).map(|v| g.adj(v).len()).max().unwrap();
+    let c = 2.0 * max_degree as f64 + 1.0;
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut x = (0..n)
+        .map(|_| rng.gen_range(-1.0..1.0))
+        .collect::<Vec<f64>>();
+    project_normalize(&mut x);
+    let mut lx = vec![0.0; n];
+    for _ in 0..iterations {
+        // x = (cI - L) x
+        laplacian_times(g, &x, &mut lx);
+        for (a, b) in x.iter_mut().zip(&lx) {
+            *a = c * *a - b;
+        }
+        project_normalize(&mut x);
+    }
+
+    // the Rayleigh quotient of a unit vector
+    laplacian_times(g, &x, &mut lx);
+    let lambda = dot(&x, &lx).max(0.0);
+
+    if x.iter().find(|a| **a != 0.0).is_some_and(|a| *a < 0.0) {
+        x.iter_mut().for_each(|a| *a = -*a);
+    }
+    (lambda, x)
+}
+
+/// Splits the vertices into two sides by the sign of an estimated Fiedler vector:
+/// `side[v]` is true iff the entry of v is nonnegative, so vertex 0 is on the true side
+/// unless its entry is 0. It uses a fixed number of iterations and a fixed seed.
+pub fn spectral_bisect(g: &Graph) -> Vec<bool> {
+    let (_, fiedler) = fiedler_estimate(g, BISECT_ITERATIONS, 0);
+    fiedler.into_iter().map(|a| a >= 0.0).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn graph(v: usize, edges: impl IntoIterator<Item = (usize, usize)>) -> Graph {
+        let mut g = Graph::new(v);
+        for (v, w) in edges {
+            g.add_edge(v, w);
+        }
+        g
+    }
+
+    fn cliques(k: usize) -> Graph {
+        let mut g = Graph::new(2 * k);
+        for offset in [0, k] {
+            for v in 0..k {
+                for w in v + 1..k {
+                    g.add_edge(offset + v, offset + w);
+                }
+            }
+        }
+        g.add_edge(k - 1, k);
+        g
+    }
+
+    #[test]
+    fn two_cliques() {
+        let g = cliques(5);
+        let side = spectral_bisect(&g);
+        assert_eq!(side, [[true; 5], [false; 5]].concat());
+
+        let (lambda, fiedler) = fiedler_estimate(&g, 1000, 7);
+        assert!(lambda > 0.0 && lambda < 1.0);
+        // it is an eigenvector
+        let mut lx = vec![0.0; g.v()];
+        laplacian_times(&g, &fiedler, &mut lx);
+        for (a, b) in fiedler.iter().zip(&lx) {
+            assert!((a * lambda - b).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn path() {
+        let n = 10;
+        let g = graph(n, (1..n).map(|v| (v - 1, v)));
+        let (lambda, fiedler) = fiedler_estimate(&g, 2000, 1);
+        let expected = 2.0 * (1.0 - (std::f64::consts::PI / n as f64).cos());
+        assert!((lambda - expected).abs() < 1e-6, "{} {}", lambda, expected);
+        // the Fiedler vector of a path is monotone
+        assert!(fiedler.windows(2).all(|w| w[0] > w[1]));
+        assert_eq!(spectral_bisect(&g), [[true; 5], [false; 5]].concat());
+    }
+
+    #[test]
+    fn disconnected() {
+        let g = graph(6, [(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3)]);
+        let (lambda, _) = fiedler_estimate(&g, 200, 3);
+        assert!(lambda < 1e-9);
+
+        let (lambda, _) = fiedler_estimate(&Graph::new(4), 10, 3);
+        assert!(lambda < 1e-9);
+        assert_eq!(fiedler_estimate(&Graph::new(1), 10, 3), (0.0, vec![0.0]));
+    }
+
+    #[test]
+    fn deterministic() {
+        let g = cliques(4);
+        assert_eq!(fiedler_estimate(&g, 50, 11), fiedler_estimate(&g, 50, 11));
+        assert_eq!(spectral_bisect(&g), spectral_bisect(&g));
+    }
+}
@@ -0,0 +1,193 @@
+//! # Articulation points (cut vertices) of an undirected graph
+//!
+//! A vertex is an articulation point if removing it (and its edges) disconnects
+//! its connected component.
+//!
+//! This implementation uses depth-first search, recording the preorder number `pre[v]` of each vertex
+//! and `low[v]`, the smallest preorder number reachable from v's subtree by one back edge.
+//! A non-root vertex v is an articulation point iff it has a child w with `low[w] >= pre[v]`:
+//! nothing in w's subtree reaches above v. The root is one iff it has more than one child.
+//! The time complexity is O(V + E).
+
+use super::graph::Graph;
+pub struct ArticulationPoints {
+    pre: Vec<usize>,         // pre[v] = order in which dfs examines v
+    low: Vec<usize>,         // low[v] = lowest preorder of any vertex connected to v's subtree
+    articulation: Vec<bool>, // articulation[v] = is v an articulation point?
+    count: usize,            // number of vertices visited so far
+}
+
+impl ArticulationPoints {
+    pub fn new(g: &Graph) -> Self {
+        let mut ap = ArticulationPoints {
+            pre: vec![usize::MAX; g.v()],
+            low: vec![usize::MAX; g.v()],
+            articulation: vec![false; g.v()],
+            count: 0,
+        };
+        for s in 0..g.v() {
+            if ap.pre[s] == usize::MAX {
+                ap.dfs(g, s);
+            }
+        }
+        ap
+    }
+
+    fn visit(&mut self, v: usize) {
+        self.pre[v] = self.count;
+        self.low[v] = self.count;
+        self.count += 1;
+    }
+
+    // iterative, so that long paths do not overflow the call stack
+    fn dfs(&mut self, g: &Graph, s: usize) {
+        let mut root_children = 0;
+        self.visit(s);
+        // (v, parent of v, index of the next edge of v to examine)
+        let mut stack = vec![(s, s, 0)];
+        while let Some((v, parent, idx)) = stack.last_mut() {
+            let (v, parent) = (*v, *parent);
+            if let Some(&w) = g.adj(v).get(*idx) {
+                *idx += 1;
+                if self.pre[w] == usize::MAX {
+                    self.visit(w);
+                    stack.push((w, v, 0));
+                } else if w != parent {
+                    // back edge
+                    self.low[v] = self.low[v].min(self.pre[w]);
+                }
+                continue;
+            }
+
+            stack.pop();
+            if v == s {
+                continue;
+            }
+            self.low[parent] = self.low[parent].min(self.low[v]);
+            if parent == s {
+                root_children += 1;
+            } else if self.low[v] >= self.pre[parent] {
+                self.articulation[parent] = true;
+            }
+        }
+        self.articulation[s] = root_children > 1;
+    }
+
+    /// Is vertex v an articulation point?
+    pub fn is_articulation(&self, v: usize) -> bool {
+        if v >= self.articulation.len() {
+            panic!(
+                "vertex {} is not between 0 and {}",
+                v,
+                self.articulation.len()
+            );
+        }
+        self.articulation[v]
+    }
+
+    /// Returns the articulation points in increasing order.
+    pub fn articulation_points(&self) -> Vec<usize> {
+        (0..self.articulation.len())
+            .filter(|&v| self.articulation[v])
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    use super::*;
+    use crate::graphs::cc::CC;
+
+    // removes v by copying every edge not incident to it
+    fn without(g: &Graph, v: usize) -> Graph {
+        let mut h = Graph::new(g.v());
+        for a in 0..g.v() {
+            for &b in g.adj(a) {
+                if a < b && a != v && b != v {
+                    h.add_edge(a, b);
+                }
+            }
+        }
+        h
+    }
+
+    #[test]
+    fn bridge() {
+        // two triangles joined by the bridge 2-3
+        let mut g = Graph::new(6);
+        for (v, w) in [(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 5), (5, 3)] {
+            g.add_edge(v, w);
+        }
+        let ap = ArticulationPoints::new(&g);
+        assert_eq!(ap.articulation_points(), vec![2, 3]);
+        assert!(!ap.is_articulation(0));
+        assert!(ap.is_articulation(3));
+    }
+
+    #[test]
+    fn single_vertex() {
+        let ap = ArticulationPoints::new(&Graph::new(1));
+        assert!(!ap.is_articulation(0));
+        assert!(ap.articulation_points().is_empty());
+
+        let mut g = Graph::new(1);
+        g.add_edge(0, 0);
+        assert!(ArticulationPoints::new(&g).articulation_points().is_empty());
+    }
+
+    #[test]
+    fn path_and_root() {
+        // 0-1-2-3: every inner vertex, including a root with two children
+        let mut g = Graph::new(4);
+        for (v, w) in [(1, 0), (1, 2), (2, 3)] {
+            g.add_edge(v, w);
+        }
+        assert_eq!(
+            ArticulationPoints::new(&g).articulation_points(),
+            vec![1, 2]
+        );
+
+        let mut g = Graph::new(3);
+        g.add_edge(0, 1);
+        g.add_edge(0, 2);
+        assert_eq!(ArticulationPoints::new(&g).articulation_points(), vec![0]);
+    }
+
+    #[test]
+    fn brute_force() {
+        let mut rng = StdRng::seed_from_u64(45);
+        for _ in 0..30 {
+            let n = 12;
+            let mut g = Graph::new(n);
+            for _ in 0..rng.gen_range(5..20) {
+                g.add_edge(rng.gen_range(0..n), rng.gen_range(0..n));
+            }
+            let ap = ArticulationPoints::new(&g);
+            let components = CC::new(&g).count();
+            for v in 0..n {
+                // count the components of the other vertices, before and after removing v
+                let alone = g.adj(v).iter().all(|&w| w == v);
+                let before = components - alone as usize;
+                let after = CC::new(&without(&g, v)).count() - 1;
+                let expected = after > before;
+                assert_eq!(ap.is_articulation(v), expected, "vertex {}", v);
+            }
+        }
+    }
+
+    #[test]
+    fn long_path() {
+        let n = 500_000;
+        let mut g = Graph::new(n);
+        for v in 1..n {
+            g.add_edge(v - 1, v);
+        }
+        assert_eq!(
+            ArticulationPoints::new(&g).articulation_points().len(),
+            n - 2
+        );
+    }
+}
@@ -0,0 +1,267 @@
+//! # Queue-based Bellman-Ford shortest path algorithm.
+//! Single-source shortest paths from a given source `s` for any edge weighted digraph.
+//! If a negative cycle is reachable from `s`, it finds one instead.
+//!
+//! Only the edges leaving a vertex whose `dist_to` changed in the previous pass can change
+//! another `dist_to`, so those vertices are kept in a FIFO queue.
+//! It takes time proportional to EV in the worst case, but is typically much faster.
+
+use crate::fundamentals::queue::Queue;
+
+use super::{
+    directed_edge::DirectedEdge, weighted_digraph::EdgeWeightedDiagraph,
+    weighted_directed_cycle::EdgeWeightedDirectedCycle,
+};
+pub struct BellmanFordSP {
+    dist_to: Vec<f64>,                  // dist_to[v] = distance of shortest s->v path
+    edge_to: Vec<Option<DirectedEdge>>, // edge_to[v] = last edge on shortest s->v path
+    on_queue: Vec<bool>,                // on_queue[v] = is v currently on the queue?
+    queue: Queue<usize>,                // queue of vertices to relax
+    cost: usize,                        // number of calls to relax()
+    cycle: Vec<DirectedEdge>,           // negative cycle (empty if no such cycle)
+}
+
+impl BellmanFordSP {
+    pub fn new(g: &EdgeWeightedDiagraph, s: usize) -> Self {
+        let mut sp = BellmanFordSP {
+            dist_to: vec![f64::MAX; g.v()],
+            edge_to: vec![None; g.v()],
+            on_queue: vec![false; g.v()],
+            queue: Queue::new(),
+            cost: 0,
+            cycle: Vec::new(),
+        };
+        sp.dist_to[s] = 0.0;
+
+        // Bellman-Ford algorithm
+        sp.queue.enqueue(s);
+        sp.on_queue[s] = true;
+        while let Some(v) = sp.queue.dequeue() {
+            if sp.has_negative_cycle() {
+                break;
+            }
+            sp.on_queue[v] = false;
+            sp.relax(g, v);
+        }
+        sp
+    }
+
+    // relax vertex v and put other endpoints on queue if changed
+    fn relax(&mut self, g: &EdgeWeightedDiagraph, v: usize) {
+        for &e in g.adj_ref(v) {
+            let w = e.to();
+            if self.dist_to[w] > self.dist_to[v] + e.weight() {
+                self.dist_to[w] = self.dist_to[v] + e.weight();
+                self.edge_to[w] = Some(e);
+                if !self.on_queue[w] {
+                    self.queue.enqueue(w);
+                    self.on_queue[w] = true;
+                }
+            }
+            self.cost += 1;
+            if self.cost.is_multiple_of(g.v()) {
+                self.find_negative_cycle(g.v());
+                if self.has_negative_cycle() {
+                    return; // found a negative cycle
+                }
+            }
+        }
+    }
+
+    // by finding a cycle in predecessor graph
+    fn find_negative_cycle(&mut self, v: usize) {
+        let mut spt = EdgeWeightedDiagraph::new(v);
+        for e in self.edge_to.iter().flatten() {
+            spt.add_edge(*e);
+        }
+        let finder = EdgeWeightedDirectedCycle::new(&spt);
+        // `EdgeWeightedDirectedCycle::cycle` is an inherent method, not `Iterator::cycle`
+        self.cycle = EdgeWeightedDirectedCycle::cycle(&finder).collect();
+    }
+
+    /// Is there a negative cycle reachable from the source vertex `s`?
+    pub fn has_negative_cycle(&self) -> bool {
+        !self.cycle.is_empty()
+    }
+
+    /// Returns a negative cycle reachable from the source vertex `s`, or `None` if there is none.
+    pub fn negative_cycle(&self) -> Option<impl Iterator<Item = DirectedEdge> + '_> {
+        if !self.has_negative_cycle() {
+            return None;
+        }
+        Some(self.cycle.iter().copied())
+    }
+
+    /// Returns the length of a shortest path from s to v
+    pub fn dist_to(&self, v: usize) -> f64 {
+        self.validate_no_negative_cycle();
+        self.dist_to[v]
+    }
+
+    /// Returns true if there is a path from s to v
+    pub fn has_path_to(&self, v: usize) -> bool {
+        self.dist_to[v] < f64::MAX
+    }
+
+    pub fn path_to(&self, v: usize) -> std::vec::IntoIter<DirectedEdge> {
+        self.validate_no_negative_cycle();
+        let mut path = Vec::new();
+        if !self.has_path_to(v) {
+            return path.into_iter();
+        }
+
+        let mut vertex = v;
+        while let Some(edge) = self.edge_to[vertex] {
+            vertex = edge.from();
+            path.push(edge);
+        }
+
+        path.reverse();
+        path.into_iter()
+    }
+
+    fn validate_no_negative_cycle(&self) {
+        if self.has_negative_cycle() {
+            panic!("negative cost cycle exists");
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn tiny_ewd(edges: &[(usize, usize, f64)]) -> EdgeWeightedDiagraph {
+        let mut g = EdgeWeightedDiagraph::new(8);
+        for &(v, w, weight) in edges {
+            g.add_edge(DirectedEdge::new(v, w, weight));
+        }
+        g
+    }
+
+    #[test]
+    fn tiny_ewd_positive() {
+        let g = tiny_ewd(&[
+            (4, 5, 0.35),
+            (5, 4, 0.35),
+            (4, 7, 0.37),
+            (5, 7, 0.28),
+            (7, 5, 0.28),
+            (5, 1, 0.32),
+            (0, 4, 0.38),
+            (0, 2, 0.26),
+            (7, 3, 0.39),
+            (1, 3, 0.29),
+            (2, 7, 0.34),
+            (6, 2, 0.40),
+            (3, 6, 0.52),
+            (6, 0, 0.58),
+            (6, 4, 0.93),
+        ]);
+        let sp = BellmanFordSP::new(&g, 0);
+        assert!(!sp.has_negative_cycle());
+        assert!(sp.negative_cycle().is_none());
+
+        let expected = [0.0, 1.05, 0.26, 0.99, 0.38, 0.73, 1.51, 0.60];
+        for (v, &d) in expected.iter().enumerate() {
+            assert!((sp.dist_to(v) - d).abs() < 1e-9);
+        }
+        assert_eq!(
+            sp.path_to(6).map(|e| e.to()).collect::<Vec<usize>>(),
+            vec![2, 7, 3, 6]
+        );
+        assert_eq!(sp.path_to(0).count(), 0);
+    }
+
+    #[test]
+    fn negative_weights() {
+        // tinyEWDn
+        let g = tiny_ewd(&[
+            (4, 5, 0.35),
+            (5, 4, 0.35),
+            (4, 7, 0.37),
+            (5, 7, 0.28),
+            (7, 5, 0.28),
+            (5, 1, 0.32),
+            (0, 4, 0.38),
+            (0, 2, 0.26),
+            (7, 3, 0.39),
+            (1, 3, 0.29),
+            (2, 7, 0.34),
+            (6, 2, -1.20),
+            (3, 6, 0.52),
+            (6, 0, -1.40),
+            (6, 4, -1.25),
+        ]);
+        let sp = BellmanFordSP::new(&g, 0);
+        assert!(!sp.has_negative_cycle());
+
+        let expected = [0.0, 0.93, 0.26, 0.99, 0.26, 0.61, 1.51, 0.60];
+        for (v, &d) in expected.iter().enumerate() {
+            assert!((sp.dist_to(v) - d).abs() < 1e-9);
+        }
+        assert_eq!(
+            sp.path_to(4).map(|e| e.to()).collect::<Vec<usize>>(),
+            vec![2, 7, 3, 6, 4]
+        );
+    }
+
+    #[test]
+    fn negative_cycle() {
+        // tinyEWDnc: 4->5->4 has weight -0.31
+        let g = tiny_ewd(&[
+            (4, 5, 0.35),
+            (5, 4, -0.66),
+            (4, 7, 0.37),
+            (5, 7, 0.28),
+            (7, 5, 0.28),
+            (5, 1, 0.32),
+            (0, 4, 0.38),
+            (0, 2, 0.26),
+            (7, 3, 0.39),
+            (1, 3, 0.29),
+            (2, 7, 0.34),
+            (6, 2, 0.40),
+            (3, 6, 0.52),
+            (6, 0, 0.58),
+            (6, 4, 0.93),
+        ]);
+        let sp = BellmanFordSP::new(&g, 0);
+        assert!(sp.has_negative_cycle());
+
+        let cycle = sp.negative_cycle().unwrap().collect::<Vec<DirectedEdge>>();
+        let weight: f64 = cycle.iter().map(|e| e.weight()).sum();
+        assert!(weight < 0.0);
+        // consecutive edges, and the last one returns to the start
+        for (i, e) in cycle.iter().enumerate() {
+            assert_eq!(e.to(), cycle[(i + 1) % cycle.len()].from());
+        }
+        let mut vertices = cycle.iter().map(|e| e.from()).collect::<Vec<usize>>();
+        vertices.sort_unstable();
+        assert_eq!(vertices, vec![4, 5]);
+    }
+
+    #[test]
+    fn unreachable_negative_cycle() {
+        let mut g = EdgeWeightedDiagraph::new(4);
+        g.add_edge(DirectedEdge::new(0, 1, 2.0));
+        g.add_edge(DirectedEdge::new(2, 3, -1.0));
+        g.add_edge(DirectedEdge::new(3, 2, -1.0));
+        g.add_edge(DirectedEdge::new(3, 1, -5.0));
+        let sp = BellmanFordSP::new(&g, 0);
+        assert!(!sp.has_negative_cycle());
+        assert!(sp.negative_cycle().is_none());
+        assert_eq!(sp.dist_to(1), 2.0);
+        assert!(!sp.has_path_to(2));
+        assert_eq!(sp.path_to(3).count(), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn dist_to_with_negative_cycle() {
+        let mut g = EdgeWeightedDiagraph::new(2);
+        g.add_edge(DirectedEdge::new(0, 1, 1.0));
+        g.add_edge(DirectedEdge::new(1, 0, -2.0));
+        BellmanFordSP::new(&g, 0).dist_to(1);
+    }
+}
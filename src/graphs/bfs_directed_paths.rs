@@ -2,6 +2,7 @@
 //! vertex in the digraph
 //!
 //! This implementation uses breadth-first search.
+//! With several sources, a shortest path starts from whichever source is nearest.
 
 use std::collections::VecDeque;
 
@@ -10,26 +11,36 @@ pub struct BreadthFirstDirectedPaths {
     marked: Vec<bool>,   // is there an s->v path?
     edge_to: Vec<usize>, // last edge on shortest s->v path
     dist_to: Vec<usize>, // length of shortest s->v path
-    s: usize,            // source
 }
 
 impl BreadthFirstDirectedPaths {
     pub fn new(g: &Digraph, s: usize) -> Self {
+        Self::from_sources(g, [s])
+    }
+
+    /// Computes shortest paths from the nearest of the sources to every other vertex.
+    pub fn from_sources(g: &Digraph, sources: impl IntoIterator<Item = usize>) -> Self {
         let mut path = BreadthFirstDirectedPaths {
             marked: vec![false; g.v()],
             edge_to: vec![0; g.v()],
             dist_to: vec![usize::MAX; g.v()],
-            s,
         };
-        path.dfs(g, s);
+        path.bfs(g, sources);
         path
     }
 
-    fn dfs(&mut self, g: &Digraph, s: usize) {
-        self.marked[s] = true;
-        self.dist_to[s] = 0;
+    fn bfs(&mut self, g: &Digraph, sources: impl IntoIterator<Item = usize>) {
         let mut q = VecDeque::new();
-        q.push_back(s);
+        for s in sources {
+            if s >= g.v() {
+                panic!("vertex {} is not between 0 and {}", s, g.v());
+            }
+            if !self.marked[s] {
+                self.marked[s] = true;
+                self.dist_to[s] = 0;
+                q.push_back(s);
+            }
+        }
         while let Some(v) = q.pop_front() {
             for w in g.adj(v).clone() {
                 if !self.marked[w] {
@@ -47,9 +58,13 @@ impl BreadthFirstDirectedPaths {
         self.marked[v]
     }
 
-    /// Returns the number of edges in a shortest path from the source to v
-    pub fn dist_to(&self, v: usize) -> usize {
-        self.dist_to[v]
+    /// Returns the number of edges in a shortest path from the source to v, or `None` if there is no path.
+    pub fn dist_to(&self, v: usize) -> Option<usize> {
+        if self.has_path_to(v) {
+            Some(self.dist_to[v])
+        } else {
+            None
+        }
     }
 
     /// Returns a shortest path from the source to v
@@ -67,11 +82,11 @@ impl Iter {
         let mut stack = Vec::new();
         if path.has_path_to(v) {
             let mut x = v;
-            while x != path.s {
+            while path.dist_to[x] != 0 {
                 stack.push(x);
                 x = path.edge_to[x];
             }
-            stack.push(path.s);
+            stack.push(x);
         }
         Iter { stack }
     }
@@ -118,11 +133,46 @@ mod test {
         let search = BreadthFirstDirectedPaths::new(&digraph, 3);
 
         assert!(search.has_path_to(0));
-        assert_eq!(search.dist_to(0), 2);
+        assert_eq!(search.dist_to(0), Some(2));
         assert_eq!(search.path_to(0).collect::<Vec<usize>>(), vec![3, 2, 0]);
 
         assert!(!search.has_path_to(6));
-        assert_eq!(search.dist_to(6), usize::MAX);
+        assert_eq!(search.dist_to(6), None);
         assert_eq!(search.path_to(6).collect::<Vec<usize>>(), vec![]);
+
+        // the distance to each vertex is the least over the sources
+        let search = BreadthFirstDirectedPaths::from_sources(&digraph, [1, 7, 10]);
+        let singles: Vec<BreadthFirstDirectedPaths> = [1, 7, 10]
+            .iter()
+            .map(|&s| BreadthFirstDirectedPaths::new(&digraph, s))
+            .collect();
+        for v in 0..digraph.v() {
+            let nearest = singles.iter().filter_map(|bfs| bfs.dist_to(v)).min();
+            assert_eq!(search.dist_to(v), nearest);
+            let path = search.path_to(v).collect::<Vec<usize>>();
+            if let Some(d) = nearest {
+                assert_eq!(path.len(), d + 1);
+                assert!([1, 7, 10].contains(&path[0]));
+                assert_eq!(path[d], v);
+            }
+        }
+        assert_eq!(search.dist_to(1), Some(0));
+        assert_eq!(search.path_to(7).collect::<Vec<usize>>(), vec![7]);
+        assert_eq!(search.dist_to(4), Some(2));
+        assert_eq!(search.path_to(4).collect::<Vec<usize>>(), vec![7, 6, 4]);
+        assert_eq!(search.dist_to(0), Some(2));
+        assert_eq!(search.path_to(0).collect::<Vec<usize>>(), vec![7, 6, 0]);
+        assert_eq!(search.dist_to(3), Some(3));
+        // no sources, no paths
+        assert_eq!(
+            BreadthFirstDirectedPaths::from_sources(&digraph, []).dist_to(1),
+            None
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "vertex 13 is not between 0 and 13")]
+    fn source_out_of_range() {
+        BreadthFirstDirectedPaths::from_sources(&Digraph::new(13), [0, 13]);
     }
 }
@@ -2,42 +2,52 @@
 //!
 //! This implementation uses bread-first search.
 //! Note if the shortest paths are more than one, the result relies on `adj` order.
+//! With several sources, a shortest path starts from whichever source is nearest.
 
 use std::{collections::VecDeque, vec};
 
 use super::graph::Graph;
+use crate::fundamentals::bitset::BitSet;
 
 pub struct BreadFirstPaths {
-    marked: Vec<bool>,   // is a shortest path to this vertex known?
+    marked: BitSet,      // is a shortest path to this vertex known?
     edge_to: Vec<usize>, // last vertex on known path to this vertex
     dist_to: Vec<usize>,
-    source: usize,
 }
 
 impl BreadFirstPaths {
     pub fn new(g: &Graph, source: usize) -> BreadFirstPaths {
+        Self::from_sources(g, [source])
+    }
+
+    /// Computes shortest paths from the nearest of the sources to every other vertex.
+    pub fn from_sources(g: &Graph, sources: impl IntoIterator<Item = usize>) -> BreadFirstPaths {
         let mut paths = BreadFirstPaths {
-            marked: vec![false; g.v()],
+            marked: BitSet::new(g.v()),
             edge_to: vec![0; g.v()],
             dist_to: vec![usize::MAX; g.v()],
-            source,
         };
-        paths.bfs(g, source);
+        paths.bfs(g, sources);
         paths
     }
 
-    fn bfs(&mut self, g: &Graph, s: usize) {
+    fn bfs(&mut self, g: &Graph, sources: impl IntoIterator<Item = usize>) {
         let mut queue = VecDeque::new();
-        self.marked[s] = true;
-        self.dist_to[s] = 0;
-        queue.push_back(s);
+        for s in sources {
+            if s >= g.v() {
+                panic!("vertex {} is not between 0 and {}", s, g.v());
+            }
+            if self.marked.insert(s) {
+                self.dist_to[s] = 0;
+                queue.push_back(s);
+            }
+        }
         while let Some(v) = queue.pop_front() {
             for w in g.adj(v).clone() {
-                if !self.marked[w] {
+                if self.marked.insert(w) {
                     // save last edge on a shortest path
                     self.edge_to[w] = v;
                     self.dist_to[w] = self.dist_to[v] + 1;
-                    self.marked[w] = true;
                     queue.push_back(w);
                 }
             }
@@ -45,11 +55,16 @@ impl BreadFirstPaths {
     }
 
     pub fn has_path_to(&self, v: usize) -> bool {
-        self.marked[v]
+        self.marked.contains(v)
     }
 
-    pub fn dist_to(&self, v: usize) -> usize {
-        self.dist_to[v]
+    /// Returns the number of edges in a shortest path from the source to v, or `None` if there is no path.
+    pub fn dist_to(&self, v: usize) -> Option<usize> {
+        if self.has_path_to(v) {
+            Some(self.dist_to[v])
+        } else {
+            None
+        }
     }
 
     pub fn path_to(&self, v: usize) -> Iter {
@@ -66,11 +81,11 @@ impl Iter {
         let mut stack = Vec::new();
         if path.has_path_to(v) {
             let mut x = v;
-            while x != path.source {
+            while path.dist_to[x] != 0 {
                 stack.push(x);
                 x = path.edge_to[x];
             }
-            stack.push(path.source);
+            stack.push(x);
         }
 
         Iter { stack }
@@ -110,11 +125,80 @@ mod test {
 
         let paths = BreadFirstPaths::new(&graph, 0);
 
-        assert_eq!(paths.dist_to(2), 1);
+        assert_eq!(paths.dist_to(2), Some(1));
         assert_eq!(paths.path_to(2).collect::<Vec<usize>>(), vec![0, 2]);
-        assert_eq!(paths.dist_to(3), 2);
+        assert_eq!(paths.dist_to(3), Some(2));
         assert_eq!(paths.path_to(3).collect::<Vec<usize>>(), vec![0, 5, 3]);
-        assert_eq!(paths.dist_to(4), 2);
+        assert_eq!(paths.dist_to(4), Some(2));
         assert_eq!(paths.path_to(4).collect::<Vec<usize>>(), vec![0, 2, 4]);
     }
+
+    #[test]
+    fn from_sources() {
+        // 0-1-2-3-4-5-6 and an isolated 7
+        let mut graph = Graph::new(8);
+        for v in 0..6 {
+            graph.add_edge(v, v + 1);
+        }
+
+        let paths = BreadFirstPaths::from_sources(&graph, [1, 5, 1]);
+        let dist: Vec<Option<usize>> = (0..graph.v()).map(|v| paths.dist_to(v)).collect();
+        assert_eq!(
+            dist,
+            vec![
+                Some(1),
+                Some(0),
+                Some(1),
+                Some(2),
+                Some(1),
+                Some(0),
+                Some(1),
+                None
+            ]
+        );
+        assert_eq!(paths.path_to(0).collect::<Vec<usize>>(), vec![1, 0]);
+        assert_eq!(paths.path_to(6).collect::<Vec<usize>>(), vec![5, 6]);
+        assert_eq!(paths.path_to(5).collect::<Vec<usize>>(), vec![5]);
+        assert_eq!(paths.path_to(3).count(), 3);
+        assert!(!paths.has_path_to(7));
+        assert_eq!(paths.path_to(7).count(), 0);
+    }
+
+    #[test]
+    fn grid_two_sources() {
+        // a 5x4 grid, vertex (r, c) = 4r + c, with sources in opposite corners
+        let (rows, cols) = (5, 4);
+        let mut graph = Graph::new(rows * cols);
+        for r in 0..rows {
+            for c in 0..cols {
+                if c + 1 < cols {
+                    graph.add_edge(r * cols + c, r * cols + c + 1);
+                }
+                if r + 1 < rows {
+                    graph.add_edge(r * cols + c, (r + 1) * cols + c);
+                }
+            }
+        }
+        let corner = rows * cols - 1;
+        let paths = BreadFirstPaths::from_sources(&graph, vec![0, corner]);
+        for r in 0..rows {
+            for c in 0..cols {
+                let v = r * cols + c;
+                // the Manhattan distance to the nearer corner
+                let nearest = (r + c).min(rows - 1 - r + cols - 1 - c);
+                assert_eq!(paths.dist_to(v), Some(nearest));
+                let path = paths.path_to(v).collect::<Vec<usize>>();
+                assert_eq!(path.len(), nearest + 1);
+                assert!(path[0] == 0 || path[0] == corner);
+            }
+        }
+        assert_eq!(paths.path_to(1).collect::<Vec<usize>>(), vec![0, 1]);
+        assert_eq!(paths.path_to(18).collect::<Vec<usize>>(), vec![19, 18]);
+    }
+
+    #[test]
+    #[should_panic(expected = "vertex 8 is not between 0 and 2")]
+    fn source_out_of_range() {
+        BreadFirstPaths::new(&Graph::new(2), 8);
+    }
 }
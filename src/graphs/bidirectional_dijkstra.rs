@@ -0,0 +1,238 @@
+//! # Shortest path between two vertices in an edge-weighted digraph
+//! where the edge weights are non-negative.
+//!
+//! This implementation runs Dijkstra's algorithm forwards from s and backwards from t
+//! (on the reverse digraph), each step advancing the search whose next vertex is closer.
+//! It stops once the two next vertices are together at least as far as the best s->t path
+//! found through a vertex labeled by both searches, which is then a shortest path.
+//! Each search settles roughly the vertices within half the s->t distance,
+//! so it usually explores far fewer vertices than `DijkstraSP`.
+
+use crate::sorting::index_min_pq::IndexMinPQ;
+
+use super::{directed_edge::DirectedEdge, weighted_digraph::EdgeWeightedDiagraph};
+
+// one direction of the search
+struct Search {
+    dist_to: Vec<f64>,                  // dist_to[v] = distance of shortest known path
+    edge_to: Vec<Option<DirectedEdge>>, // edge_to[v] = last edge on shortest known path
+    pq: IndexMinPQ<f64>,                // min priority queue of vertices
+}
+
+impl Search {
+    fn new(n: usize, s: usize) -> Self {
+        let mut search = Search {
+            dist_to: vec![f64::MAX; n],
+            edge_to: vec![None; n],
+            pq: IndexMinPQ::new(n),
+        };
+        search.dist_to[s] = 0.0;
+        search.pq.insert(s, 0.0);
+        search
+    }
+
+    fn min_key(&self) -> f64 {
+        self.pq.min_key().unwrap_or(f64::MAX)
+    }
+
+    // returns the vertices whose distance improved
+    fn step(&mut self, g: &EdgeWeightedDiagraph) -> Vec<usize> {
+        let mut improved = Vec::new();
+        if let Some(v) = self.pq.del_min() {
+            for &e in g.adj_ref(v) {
+                let w = e.to();
+                if self.dist_to[w] > self.dist_to[v] + e.weight() {
+                    self.dist_to[w] = self.dist_to[v] + e.weight();
+                    self.edge_to[w] = Some(e);
+                    if self.pq.contains(w) {
+                        self.pq.decrease_key(w, self.dist_to[w]);
+                    } else {
+                        self.pq.insert(w, self.dist_to[w]);
+                    }
+                    improved.push(w);
+                }
+            }
+        }
+        improved
+    }
+}
+
+pub struct BidirectionalDijkstraSP {
+    forward: Search,  // from s in the digraph
+    backward: Search, // from t in the reverse digraph
+    dist: f64,        // length of shortest s->t path
+    meet: usize,      // a vertex on a shortest s->t path
+}
+
+impl BidirectionalDijkstraSP {
+    pub fn new(g: &EdgeWeightedDiagraph, s: usize, t: usize) -> Self {
+        for v in [s, t] {
+            if v >= g.v() {
+                panic!("vertex {} is not between 0 and {}", v, g.v());
+            }
+        }
+        for e in g.edges() {
+            if e.weight() < 0.0 {
+                panic!("edge {} has negative weight", e);
+            }
+        }
+
+        let reverse = g.reverse();
+        let mut sp = BidirectionalDijkstraSP {
+            forward: Search::new(g.v(), s),
+            backward: Search::new(g.v(), t),
+            dist: f64::MAX,
+            meet: s,
+        };
+        sp.update(s);
+
+        while sp.forward.min_key() + sp.backward.min_key() < sp.dist {
+            let improved = if sp.forward.min_key() <= sp.backward.min_key() {
+                sp.forward.step(g)
+            } else {
+                sp.backward.step(&reverse)
+            };
+            for v in improved {
+                sp.update(v);
+            }
+        }
+        sp
+    }
+
+    // checks the s->t path through v
+    fn update(&mut self, v: usize) {
+        let through = self.forward.dist_to[v] + self.backward.dist_to[v];
+        if through < self.dist {
+            self.dist = through;
+            self.meet = v;
+        }
+    }
+
+    /// Returns the length of a shortest path from s to t, or `f64::MAX` if there is none.
+    pub fn dist(&self) -> f64 {
+        self.dist
+    }
+
+    /// Returns true if there is a path from s to t
+    pub fn has_path(&self) -> bool {
+        self.dist < f64::MAX
+    }
+
+    /// Returns the edges of a shortest path from s to t, empty if there is none or s == t.
+    pub fn path(&self) -> impl Iterator<Item = DirectedEdge> {
+        let mut path = Vec::new();
+        if !self.has_path() {
+            return path.into_iter();
+        }
+
+        // s -> meet
+        let mut vertex = self.meet;
+        while let Some(edge) = self.forward.edge_to[vertex] {
+            vertex = edge.from();
+            path.push(edge);
+        }
+        path.reverse();
+
+        // meet -> t, turning the edges of the reverse digraph around
+        let mut vertex = self.meet;
+        while let Some(edge) = self.backward.edge_to[vertex] {
+            vertex = edge.from();
+            path.push(DirectedEdge::new(edge.to(), edge.from(), edge.weight()));
+        }
+        path.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    use super::*;
+    use crate::graphs::dijkstra_sp::DijkstraSP;
+
+    fn assert_same(g: &EdgeWeightedDiagraph, s: usize, t: usize) {
+        let expected = DijkstraSP::new(g, s);
+        let sp = BidirectionalDijkstraSP::new(g, s, t);
+        assert_eq!(sp.has_path(), expected.has_path_to(t));
+        if !sp.has_path() {
+            assert_eq!(sp.dist(), f64::MAX);
+            assert_eq!(sp.path().count(), 0);
+            return;
+        }
+        assert!((sp.dist() - expected.dist_to(t)).abs() < 1e-9);
+
+        let mut at = s;
+        let mut length = 0.0;
+        for e in sp.path() {
+            assert_eq!(e.from(), at);
+            assert!(g.has_edge(e.from(), e.to()).is_some());
+            at = e.to();
+            length += e.weight();
+        }
+        assert_eq!(at, t);
+        assert!((length - sp.dist()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn tiny_ewg() {
+        let mut g = EdgeWeightedDiagraph::new(8);
+        g.add_edge(DirectedEdge::new(4, 5, 0.35));
+        g.add_edge(DirectedEdge::new(5, 4, 0.35));
+        g.add_edge(DirectedEdge::new(4, 7, 0.37));
+        g.add_edge(DirectedEdge::new(5, 7, 0.28));
+        g.add_edge(DirectedEdge::new(7, 5, 0.28));
+        g.add_edge(DirectedEdge::new(5, 1, 0.32));
+        g.add_edge(DirectedEdge::new(0, 4, 0.38));
+        g.add_edge(DirectedEdge::new(0, 2, 0.26));
+        g.add_edge(DirectedEdge::new(7, 3, 0.39));
+        g.add_edge(DirectedEdge::new(1, 3, 0.29));
+        g.add_edge(DirectedEdge::new(2, 7, 0.34));
+        g.add_edge(DirectedEdge::new(6, 2, 0.40));
+        g.add_edge(DirectedEdge::new(3, 6, 0.52));
+        g.add_edge(DirectedEdge::new(6, 0, 0.58));
+        g.add_edge(DirectedEdge::new(6, 4, 0.93));
+
+        for s in 0..g.v() {
+            for t in 0..g.v() {
+                assert_same(&g, s, t);
+            }
+        }
+
+        let sp = BidirectionalDijkstraSP::new(&g, 0, 6);
+        assert!((sp.dist() - 1.51).abs() < 1e-9);
+        assert_eq!(
+            sp.path().map(|e| e.to()).collect::<Vec<usize>>(),
+            vec![2, 7, 3, 6]
+        );
+        let sp = BidirectionalDijkstraSP::new(&g, 3, 3);
+        assert_eq!(sp.dist(), 0.0);
+        assert_eq!(sp.path().count(), 0);
+    }
+
+    #[test]
+    fn random_digraphs() {
+        let mut rng = StdRng::seed_from_u64(71);
+        for _ in 0..50 {
+            let n = rng.gen_range(1..30);
+            let mut g = EdgeWeightedDiagraph::new(n);
+            for _ in 0..rng.gen_range(0..4 * n) {
+                let v = rng.gen_range(0..n);
+                let w = rng.gen_range(0..n);
+                // small integer weights give many ties
+                g.add_edge(DirectedEdge::new(v, w, rng.gen_range(0..4) as f64));
+            }
+            for _ in 0..10 {
+                assert_same(&g, rng.gen_range(0..n), rng.gen_range(0..n));
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "negative weight")]
+    fn negative_weight() {
+        let mut g = EdgeWeightedDiagraph::new(2);
+        g.add_edge(DirectedEdge::new(0, 1, -1.0));
+        BidirectionalDijkstraSP::new(&g, 0, 1);
+    }
+}
@@ -1,12 +1,18 @@
 //! # determining whether an undirected graph is *bipartite* or whether it has an *odd-length cycle*.
 //!
 //! A graph is bipartite if and only if it has no odd-length cycle.
+//! This implementation uses depth-first search.
+//! `Graph::is_bipartite` and `Graph::bipartite_parts` use breadth-first search instead.
+
+use std::collections::VecDeque;
 
 use super::graph::Graph;
 pub struct Bipartite {
     is_bipartite: bool,
-    color: Vec<bool>,  // color[v] gives vertices on one side of bipartition
-    marked: Vec<bool>, // marked[v] = true iff v has been visited in DFS
+    color: Vec<bool>,    // color[v] gives vertices on one side of bipartition
+    marked: Vec<bool>,   // marked[v] = true iff v has been visited in DFS
+    edge_to: Vec<usize>, // edge_to[v] = last edge on path to v
+    cycle: Vec<usize>,   // stack: odd-length cycle (or empty if bipartite)
 }
 
 impl Bipartite {
@@ -15,9 +21,11 @@ impl Bipartite {
             is_bipartite: true,
             color: vec![false; g.v()],
             marked: vec![false; g.v()],
+            edge_to: vec![0; g.v()],
+            cycle: vec![],
         };
         for s in 0..g.v() {
-            if !bipartite.marked[s] {
+            if !bipartite.marked[s] && bipartite.is_bipartite {
                 bipartite.dfs(g, s);
             }
         }
@@ -28,14 +36,26 @@ impl Bipartite {
         self.marked[v] = true;
 
         for w in g.adj(v).clone() {
+            // short circuit if odd-length cycle found
+            if !self.is_bipartite {
+                return;
+            }
             // found uncolored/unvisited vertex
             if !self.marked[w] {
+                self.edge_to[w] = v;
                 self.color[w] = !self.color[v];
                 self.dfs(g, w);
             } else if self.color[w] == self.color[v] {
-                // if v-w create an odd-length cycle
+                // if v-w create an odd-length cycle, trace it back
+                // w -> v -> ... -> w
                 self.is_bipartite = false;
-                return;
+                self.cycle.push(w);
+                let mut x = v;
+                while x != w {
+                    self.cycle.push(x);
+                    x = self.edge_to[x];
+                }
+                self.cycle.push(w);
             }
         }
     }
@@ -43,14 +63,104 @@ impl Bipartite {
     pub fn is_bipartite(&self) -> bool {
         self.is_bipartite
     }
+
+    /// Returns the side of the bipartition that vertex `v` is on.
+    /// Panics if the graph is not bipartite.
+    pub fn color(&self, v: usize) -> bool {
+        if !self.is_bipartite {
+            panic!("graph is not bipartite");
+        }
+        self.color[v]
+    }
+
+    /// Returns an odd-length cycle if the graph is not bipartite,
+    /// starting and ending at the same vertex; otherwise it is empty.
+    /// Note the result relies on adj orders.
+    pub fn odd_cycle(&self) -> Iter {
+        Iter::new(self)
+    }
+}
+
+pub struct Iter {
+    stack: Vec<usize>,
+}
+
+impl Iter {
+    pub fn new(bipartite: &Bipartite) -> Self {
+        Iter {
+            stack: bipartite.cycle.clone(),
+        }
+    }
+}
+
+impl Iterator for Iter {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.stack.pop()
+    }
+}
+
+impl Graph {
+    /// Is the graph bipartite? It takes O(V + E).
+    pub fn is_bipartite(&self) -> bool {
+        two_coloring(self).is_some()
+    }
+
+    /// Returns the two sides of a bipartition, or `None` if the graph is not bipartite.
+    /// The first vertex of each connected component is on the first side.
+    pub fn bipartite_parts(&self) -> Option<(Vec<usize>, Vec<usize>)> {
+        let color = two_coloring(self)?;
+        Some((0..self.v()).partition(|&v| !color[v]))
+    }
+}
+
+// colors each connected component by breadth-first search from its first vertex
+fn two_coloring(g: &Graph) -> Option<Vec<bool>> {
+    let mut color = vec![false; g.v()];
+    let mut marked = vec![false; g.v()];
+    let mut queue = VecDeque::new();
+    for s in 0..g.v() {
+        if marked[s] {
+            continue;
+        }
+        marked[s] = true;
+        queue.push_back(s);
+        while let Some(v) = queue.pop_front() {
+            for &w in g.adj(v) {
+                if !marked[w] {
+                    marked[w] = true;
+                    color[w] = !color[v];
+                    queue.push_back(w);
+                } else if color[w] == color[v] {
+                    // v-w joins two vertices at even distance: an odd-length cycle
+                    return None;
+                }
+            }
+        }
+    }
+    Some(color)
 }
 
 #[cfg(test)]
 mod test {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
     use crate::graphs::graph_generator;
 
     use super::*;
 
+    // checks `cycle` is a closed walk along edges of `g` with an odd number of edges
+    fn assert_odd_cycle(g: &Graph, cycle: &[usize]) {
+        assert!(cycle.len() >= 4);
+        assert_eq!(cycle.first(), cycle.last());
+        assert_eq!((cycle.len() - 1) % 2, 1);
+        for e in cycle.windows(2) {
+            assert!(g.adj(e[0]).contains(&e[1]));
+        }
+    }
+
     #[test]
     fn one_color() {
         let mut graph = Graph::new(6);
@@ -65,6 +175,7 @@ mod test {
 
         let bipartite = Bipartite::new(&graph);
         assert!(!bipartite.is_bipartite());
+        assert_odd_cycle(&graph, &bipartite.odd_cycle().collect::<Vec<usize>>());
     }
 
     #[test]
@@ -87,4 +198,145 @@ mod test {
         let bipartite = Bipartite::new(&graph);
         assert!(bipartite.is_bipartite());
     }
+
+    #[test]
+    fn grid() {
+        // 3 x 4 grid, vertex (r, c) is r * 4 + c
+        let mut graph = Graph::new(12);
+        for r in 0..3 {
+            for c in 0..4 {
+                if c + 1 < 4 {
+                    graph.add_edge(r * 4 + c, r * 4 + c + 1);
+                }
+                if r + 1 < 3 {
+                    graph.add_edge(r * 4 + c, (r + 1) * 4 + c);
+                }
+            }
+        }
+
+        let bipartite = Bipartite::new(&graph);
+        assert!(bipartite.is_bipartite());
+        assert_eq!(bipartite.odd_cycle().count(), 0);
+        for v in 0..graph.v() {
+            for &w in graph.adj(v) {
+                assert_ne!(bipartite.color(v), bipartite.color(w));
+            }
+        }
+    }
+
+    #[test]
+    fn triangle() {
+        let mut graph = Graph::new(3);
+        graph.add_edge(0, 1);
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 0);
+
+        let bipartite = Bipartite::new(&graph);
+        assert!(!bipartite.is_bipartite());
+        let cycle = bipartite.odd_cycle().collect::<Vec<usize>>();
+        assert_eq!(cycle.len(), 4);
+        assert_odd_cycle(&graph, &cycle);
+    }
+
+    #[test]
+    fn disconnected() {
+        // a bipartite path 0-1-2 and a 5-cycle 3-4-5-6-7
+        let mut graph = Graph::new(8);
+        graph.add_edge(0, 1);
+        graph.add_edge(1, 2);
+        for v in 3..8 {
+            graph.add_edge(v, if v == 7 { 3 } else { v + 1 });
+        }
+
+        let bipartite = Bipartite::new(&graph);
+        assert!(!bipartite.is_bipartite());
+        let cycle = bipartite.odd_cycle().collect::<Vec<usize>>();
+        assert_eq!(cycle.len(), 6);
+        assert!(cycle.iter().all(|&v| v >= 3));
+        assert_odd_cycle(&graph, &cycle);
+    }
+
+    #[test]
+    #[should_panic]
+    fn color_not_bipartite() {
+        let mut graph = Graph::new(3);
+        graph.add_edge(0, 1);
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 0);
+
+        Bipartite::new(&graph).color(0);
+    }
+
+    #[test]
+    fn even_cycle_and_triangle() {
+        let mut graph = Graph::new(6);
+        for v in 0..6 {
+            graph.add_edge(v, (v + 1) % 6);
+        }
+        let bipartite = Bipartite::new(&graph);
+        assert!(bipartite.is_bipartite());
+        assert_eq!(bipartite.odd_cycle().count(), 0);
+        for v in 0..6 {
+            assert_ne!(bipartite.color(v), bipartite.color((v + 1) % 6));
+        }
+
+        let mut graph = Graph::new(3);
+        graph.add_edge(0, 1);
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 0);
+        let bipartite = Bipartite::new(&graph);
+        assert!(!bipartite.is_bipartite());
+        let cycle = bipartite.odd_cycle().collect::<Vec<usize>>();
+        assert_odd_cycle(&graph, &cycle);
+        // three distinct vertices, with the first repeated at the end
+        let mut vertices = cycle[..3].to_vec();
+        vertices.sort_unstable();
+        assert_eq!(vertices, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn bipartite_parts() {
+        let mut graph = Graph::new(7);
+        graph.add_edge(0, 1);
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 3);
+        graph.add_edge(3, 0);
+        graph.add_edge(4, 5);
+        assert!(graph.is_bipartite());
+        let (left, right) = graph.bipartite_parts().unwrap();
+        assert_eq!(left, vec![0, 2, 4, 6]);
+        assert_eq!(right, vec![1, 3, 5]);
+        for v in 0..7 {
+            for &w in graph.adj(v) {
+                assert_ne!(left.contains(&v), left.contains(&w));
+            }
+        }
+
+        // a triangle
+        graph.add_edge(5, 6);
+        graph.add_edge(6, 4);
+        assert!(!graph.is_bipartite());
+        assert_eq!(graph.bipartite_parts(), None);
+
+        let mut graph = Graph::new(1);
+        assert_eq!(graph.bipartite_parts(), Some((vec![0], vec![])));
+        graph.add_edge(0, 0);
+        assert!(!graph.is_bipartite());
+    }
+
+    #[test]
+    fn same_as_dfs() {
+        let mut rng = StdRng::seed_from_u64(40);
+        for e in 10..30 {
+            let mut graph = Graph::new(20);
+            for _ in 0..e {
+                graph.add_edge(rng.gen_range(0..20), rng.gen_range(0..20));
+            }
+            assert_eq!(graph.is_bipartite(), Bipartite::new(&graph).is_bipartite());
+        }
+
+        let graph = graph_generator::bipartite(5, 8, 18);
+        let (left, right) = graph.bipartite_parts().unwrap();
+        assert_eq!(left.len() + right.len(), 13);
+    }
 }
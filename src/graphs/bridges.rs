@@ -0,0 +1,170 @@
+//! # Bridges of an undirected graph
+//!
+//! A bridge is an edge whose removal disconnects its connected component.
+//!
+//! As with articulation points, depth-first search records `pre[v]` and `low[v]`.
+//! A tree edge v-w (w a child of v) is a bridge iff `low[w] > pre[v]`: no edge from w's subtree
+//! reaches v or above. Only the tree edge itself is skipped when looking back at the parent,
+//! so a parallel edge counts as a back edge, and two vertices joined twice are never a bridge.
+//! The time complexity is O(V + E).
+
+use super::graph::Graph;
+pub struct Bridges {
+    pre: Vec<usize>,              // pre[v] = order in which dfs examines v
+    low: Vec<usize>,              // low[v] = lowest preorder of any vertex connected to v's subtree
+    bridges: Vec<(usize, usize)>, // bridges as (v, w) with v < w, sorted
+    count: usize,                 // number of vertices visited so far
+}
+
+impl Bridges {
+    pub fn new(g: &Graph) -> Self {
+        let mut b = Bridges {
+            pre: vec![usize::MAX; g.v()],
+            low: vec![usize::MAX; g.v()],
+            bridges: Vec::new(),
+            count: 0,
+        };
+        for s in 0..g.v() {
+            if b.pre[s] == usize::MAX {
+                b.dfs(g, s);
+            }
+        }
+        b.bridges.sort_unstable();
+        b
+    }
+
+    fn visit(&mut self, v: usize) {
+        self.pre[v] = self.count;
+        self.low[v] = self.count;
+        self.count += 1;
+    }
+
+    // iterative, so that long paths do not overflow the call stack
+    fn dfs(&mut self, g: &Graph, s: usize) {
+        self.visit(s);
+        // (v, parent of v, index of the next edge of v to examine, has the tree edge to parent been skipped?)
+        let mut stack = vec![(s, s, 0, true)];
+        while let Some((v, parent, idx, skipped)) = stack.last_mut() {
+            let (v, parent) = (*v, *parent);
+            if let Some(&w) = g.adj(v).get(*idx) {
+                *idx += 1;
+                if self.pre[w] == usize::MAX {
+                    self.visit(w);
+                    stack.push((w, v, 0, false));
+                } else if w == parent && !*skipped {
+                    *skipped = true;
+                } else {
+                    // back edge, or another edge parallel to the tree edge
+                    self.low[v] = self.low[v].min(self.pre[w]);
+                }
+                continue;
+            }
+
+            stack.pop();
+            if v == s {
+                continue;
+            }
+            self.low[parent] = self.low[parent].min(self.low[v]);
+            if self.low[v] > self.pre[parent] {
+                self.bridges.push((parent.min(v), parent.max(v)));
+            }
+        }
+    }
+
+    fn validate_vertex(&self, v: usize) {
+        if v >= self.pre.len() {
+            panic!("vertex {} is not between 0 and {}", v, self.pre.len());
+        }
+    }
+
+    /// Is the edge v-w a bridge? It is false if there is no such edge.
+    pub fn is_bridge(&self, v: usize, w: usize) -> bool {
+        self.validate_vertex(v);
+        self.validate_vertex(w);
+        self.bridges.binary_search(&(v.min(w), v.max(w))).is_ok()
+    }
+
+    /// Returns the bridges as pairs `(v, w)` with `v < w`, in increasing order.
+    pub fn bridges(&self) -> Vec<(usize, usize)> {
+        self.bridges.clone()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    use super::*;
+    use crate::graphs::cc::CC;
+
+    fn graph(v: usize, edges: &[(usize, usize)]) -> Graph {
+        let mut g = Graph::new(v);
+        for &(v, w) in edges {
+            g.add_edge(v, w);
+        }
+        g
+    }
+
+    #[test]
+    fn tree() {
+        let edges = [(0, 1), (0, 2), (2, 3), (2, 4), (4, 5)];
+        let b = Bridges::new(&graph(6, &edges));
+        assert_eq!(b.bridges(), edges.to_vec());
+        for (v, w) in edges {
+            assert!(b.is_bridge(w, v));
+        }
+        assert!(!b.is_bridge(1, 2));
+    }
+
+    #[test]
+    fn cycle() {
+        let b = Bridges::new(&graph(5, &[(0, 1), (1, 2), (2, 3), (3, 4), (4, 0)]));
+        assert!(b.bridges().is_empty());
+        assert!(!b.is_bridge(0, 1));
+    }
+
+    #[test]
+    fn parallel_edges_and_self_loops() {
+        // 0=1 is doubled, 1-2 is a bridge, 2 has a self-loop
+        let b = Bridges::new(&graph(3, &[(0, 1), (1, 0), (1, 2), (2, 2)]));
+        assert_eq!(b.bridges(), vec![(1, 2)]);
+        assert!(!b.is_bridge(0, 1));
+        assert!(!b.is_bridge(2, 2));
+    }
+
+    #[test]
+    fn two_triangles() {
+        let b = Bridges::new(&graph(
+            7,
+            &[(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 5), (5, 3)],
+        ));
+        assert_eq!(b.bridges(), vec![(2, 3)]);
+    }
+
+    #[test]
+    fn brute_force() {
+        let mut rng = StdRng::seed_from_u64(49);
+        for _ in 0..30 {
+            let n = 10;
+            let edges = (0..rng.gen_range(5..16))
+                .map(|_| (rng.gen_range(0..n), rng.gen_range(0..n)))
+                .collect::<Vec<(usize, usize)>>();
+            let b = Bridges::new(&graph(n, &edges));
+            let components = CC::new(&graph(n, &edges)).count();
+            for i in 0..edges.len() {
+                let mut rest = edges.clone();
+                let (v, w) = rest.remove(i);
+                let expected = CC::new(&graph(n, &rest)).count() > components;
+                assert_eq!(b.is_bridge(v, w), expected, "edge {}-{}", v, w);
+            }
+        }
+    }
+
+    #[test]
+    fn long_path() {
+        let n = 500_000;
+        let edges = (1..n).map(|v| (v - 1, v)).collect::<Vec<(usize, usize)>>();
+        assert_eq!(Bridges::new(&graph(n, &edges)).bridges().len(), n - 1);
+    }
+}
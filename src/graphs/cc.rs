@@ -5,7 +5,8 @@ use super::graph::Graph;
 
 pub struct CC {
     marked: Vec<bool>,
-    id: Vec<usize>,
+    id: Vec<usize>,   // id[v] = id of connected component containing v
+    size: Vec<usize>, // size[id] = number of vertices in given component
     count: usize,
 }
 
@@ -14,10 +15,12 @@ impl CC {
         let mut cc = CC {
             marked: vec![false; g.v()],
             id: vec![0; g.v()],
+            size: vec![],
             count: 0,
         };
         for s in 0..g.v() {
             if !cc.marked[s] {
+                cc.size.push(0);
                 cc.dfs(g, s);
                 cc.count += 1;
             }
@@ -28,6 +31,7 @@ impl CC {
     fn dfs(&mut self, g: &Graph, v: usize) {
         self.marked[v] = true;
         self.id[v] = self.count;
+        self.size[self.count] += 1;
         for w in g.adj(v).clone() {
             if !self.marked[w] {
                 self.dfs(g, w);
@@ -46,6 +50,14 @@ impl CC {
     pub fn count(&self) -> usize {
         self.count
     }
+
+    /// Returns the number of vertices in the component with the given id.
+    pub fn size(&self, id: usize) -> usize {
+        if id >= self.count {
+            panic!("component {} is not between 0 and {}", id, self.count);
+        }
+        self.size[id]
+    }
 }
 
 #[cfg(test)]
@@ -85,5 +97,33 @@ mod test {
             components,
             vec![vec![0, 1, 2, 3, 4, 5, 6], vec![7, 8], vec![9, 10, 11, 12]]
         );
+        assert_eq!(cc.size(cc.id(0)), 7);
+        assert_eq!(cc.size(cc.id(8)), 2);
+        assert_eq!(cc.size(cc.id(12)), 4);
+    }
+
+    #[test]
+    fn two_components() {
+        let mut graph = Graph::new(6);
+        graph.add_edge(0, 1);
+        graph.add_edge(1, 2);
+        graph.add_edge(3, 4);
+        graph.add_edge(4, 5);
+        graph.add_edge(5, 3);
+
+        let cc = CC::new(&graph);
+        assert_eq!(cc.count(), 2);
+        assert!(cc.connected(0, 2));
+        assert!(cc.connected(5, 3));
+        assert!(!cc.connected(2, 3));
+        assert_eq!(cc.size(0), 3);
+        assert_eq!(cc.size(1), 3);
+        assert_ne!(cc.id(1), cc.id(4));
+    }
+
+    #[test]
+    #[should_panic]
+    fn size_out_of_range() {
+        CC::new(&Graph::new(2)).size(2);
     }
 }
@@ -0,0 +1,87 @@
+//! # Condensation of a digraph
+//!
+//! The condensation (kernel DAG) has one vertex per strong component, and an edge
+//! from component a to component b whenever some edge of the digraph goes from a to b.
+//! It is always acyclic: a cycle of components would make them one component.
+
+use std::collections::HashSet;
+
+use super::digraph::Digraph;
+use super::kosaraju_scc::KosarajuSCC;
+
+/// Returns the condensation of `g`, whose vertex `i` is the strong component of `scc` with id `i`.
+/// Edges inside a component are dropped, and parallel edges between components are added once.
+pub fn condensation(g: &Digraph, scc: &KosarajuSCC) -> Digraph {
+    let mut dag = Digraph::new(scc.count());
+    let mut seen = HashSet::new();
+    for v in 0..g.v() {
+        for &w in g.adj(v) {
+            let (a, b) = (scc.id(v), scc.id(w));
+            if a != b && seen.insert((a, b)) {
+                dag.add_edge(a, b);
+            }
+        }
+    }
+    dag
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::graphs::directed_cycle::DirectedCycle;
+
+    #[test]
+    fn tiny_dg() {
+        let digraph = Digraph::from_edges(
+            13,
+            &[
+                (4, 2),
+                (2, 3),
+                (3, 2),
+                (6, 0),
+                (0, 1),
+                (2, 0),
+                (11, 12),
+                (12, 9),
+                (9, 10),
+                (9, 11),
+                (8, 9),
+                (10, 12),
+                (11, 4),
+                (4, 3),
+                (3, 5),
+                (7, 8),
+                (8, 7),
+                (5, 4),
+                (0, 5),
+                (6, 4),
+                (6, 9),
+                (7, 6),
+            ],
+        );
+        let scc = KosarajuSCC::new(&digraph);
+        let dag = condensation(&digraph, &scc);
+
+        assert_eq!(dag.v(), 5);
+        // 6->0 and 6->4 both become one edge
+        assert_eq!(dag.e(), 6);
+        assert!(!DirectedCycle::new(&dag).has_cycle());
+        for v in 0..digraph.v() {
+            for &w in digraph.adj(v) {
+                let (a, b) = (scc.id(v), scc.id(w));
+                assert_eq!(a == b, scc.strongly_connected(v, w));
+                if a != b {
+                    assert!(dag.has_edge(a, b));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn strongly_connected() {
+        let digraph = Digraph::from_edges(3, &[(0, 1), (1, 2), (2, 0), (1, 0)]);
+        let dag = condensation(&digraph, &KosarajuSCC::new(&digraph));
+        assert_eq!(dag.v(), 1);
+        assert_eq!(dag.e(), 0);
+    }
+}
@@ -0,0 +1,139 @@
+//! # Critical path method for parallel precedence-constrained job scheduling
+//!
+//! Each job becomes a start vertex and an end vertex joined by an edge weighted with its
+//! duration; a source leads to every start, every end leads to a sink, and each constraint
+//! is a zero-weight edge from the end of a job to the start of its successor.
+//! The earliest start time of a job is then the length of a longest path from the source
+//! to its start vertex, computed with `AcyclicLP`. The time complexity is O(V + E).
+
+use super::{
+    acyclic_lp::AcyclicLP, directed_edge::DirectedEdge, weighted_digraph::EdgeWeightedDiagraph,
+};
+
+pub struct Cpm {
+    n: usize,      // number of jobs
+    lp: AcyclicLP, // longest paths from the source
+}
+
+/// The book's name for `Cpm`.
+pub type CriticalPathMethod = Cpm;
+
+impl Cpm {
+    /// Schedules the jobs given as `(duration, jobs that must start after this one finishes)`.
+    /// Panics if the constraints have a cycle.
+    pub fn new(jobs: &[(f64, &[usize])]) -> Self {
+        let n = jobs.len();
+        let (source, sink) = (2 * n, 2 * n + 1);
+        let mut g = EdgeWeightedDiagraph::new(2 * n + 2);
+        // so that the finish time is 0 with no jobs
+        g.add_edge(DirectedEdge::new(source, sink, 0.0));
+        for (i, &(duration, successors)) in jobs.iter().enumerate() {
+            g.add_edge(DirectedEdge::new(source, i, 0.0));
+            g.add_edge(DirectedEdge::new(i + n, sink, 0.0));
+            g.add_edge(DirectedEdge::new(i, i + n, duration));
+            for &j in successors {
+                if j >= n {
+                    panic!("job {} is not between 0 and {}", j, n);
+                }
+                g.add_edge(DirectedEdge::new(i + n, j, 0.0));
+            }
+        }
+        Cpm {
+            n,
+            lp: AcyclicLP::new(&g, source),
+        }
+    }
+
+    /// Returns the earliest time the job can start.
+    pub fn start_time(&self, job: usize) -> f64 {
+        if job >= self.n {
+            panic!("job {} is not between 0 and {}", job, self.n);
+        }
+        self.lp.dist_to(job)
+    }
+
+    /// Returns the time when all the jobs are done.
+    pub fn finish_time(&self) -> f64 {
+        self.lp.dist_to(2 * self.n + 1)
+    }
+
+    /// Returns a longest chain of jobs, in order; delaying any of them delays the finish time.
+    pub fn critical_path(&self) -> impl Iterator<Item = usize> + '_ {
+        // the duration edges i -> i + n on a longest path to the sink
+        self.lp
+            .path_to(2 * self.n + 1)
+            .filter(move |e| e.from() < self.n && e.to() == e.from() + self.n)
+            .map(|e| e.from())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // jobsPC.txt
+    const JOBS: [(f64, &[usize]); 10] = [
+        (41.0, &[1, 7, 9]),
+        (51.0, &[2]),
+        (50.0, &[]),
+        (36.0, &[]),
+        (38.0, &[]),
+        (45.0, &[]),
+        (21.0, &[3, 8]),
+        (32.0, &[3, 8]),
+        (32.0, &[2]),
+        (29.0, &[4, 6]),
+    ];
+
+    #[test]
+    fn jobs_pc() {
+        let cpm = Cpm::new(&JOBS);
+        let expected = [0.0, 41.0, 123.0, 91.0, 70.0, 0.0, 70.0, 41.0, 91.0, 41.0];
+        for (job, &start) in expected.iter().enumerate() {
+            assert!((cpm.start_time(job) - start).abs() < 1e-9, "job {}", job);
+        }
+        assert!((cpm.finish_time() - 173.0).abs() < 1e-9);
+        assert_eq!(
+            cpm.critical_path().collect::<Vec<usize>>(),
+            vec![0, 9, 6, 8, 2]
+        );
+
+        // every constraint holds
+        for (i, &(duration, successors)) in JOBS.iter().enumerate() {
+            for &j in successors {
+                assert!(cpm.start_time(i) + duration <= cpm.start_time(j) + 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn critical_path_method_alias() {
+        let cpm = CriticalPathMethod::new(&JOBS);
+        // the critical jobs run back to back, so their durations add up to the finish time
+        let length: f64 = cpm.critical_path().map(|job| JOBS[job].0).sum();
+        assert!((length - cpm.finish_time()).abs() < 1e-9);
+        let mut at = 0.0;
+        for job in cpm.critical_path() {
+            assert!((cpm.start_time(job) - at).abs() < 1e-9);
+            at += JOBS[job].0;
+        }
+    }
+
+    #[test]
+    fn independent_jobs() {
+        let cpm = Cpm::new(&[(3.0, &[]), (5.0, &[]), (4.0, &[])]);
+        assert_eq!(cpm.start_time(2), 0.0);
+        assert_eq!(cpm.finish_time(), 5.0);
+        assert_eq!(cpm.critical_path().collect::<Vec<usize>>(), vec![1]);
+
+        let none = Cpm::new(&[]);
+        assert_eq!(none.finish_time(), 0.0);
+        assert_eq!(none.critical_path().count(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "not acyclic")]
+    fn cyclic_constraints() {
+        Cpm::new(&[(1.0, &[1]), (1.0, &[0])]);
+    }
+}
@@ -1,42 +1,118 @@
 //! # Detect a cycle in an undirected graph
 //!
-//! Assume no self-loops and parallel edges.
 //! An undirected graph has a cycle if and only if a depth-first search finds an edge that points to an already-visited vertex (a back edge).
+//!
+//! Self-loops and parallel edges are cycles too (of length 1 and 2), so they are checked first.
 
 use super::graph::Graph;
 pub struct Cycle {
     marked: Vec<bool>,
-    has_cycle: bool,
+    edge_to: Vec<usize>, // previous vertex on path to v
+    cycle: Vec<usize>,   // stack: cycle (or empty if no such cycle)
 }
 
 impl Cycle {
     pub fn new(g: &Graph) -> Cycle {
         let mut c = Cycle {
             marked: vec![false; g.v()],
-            has_cycle: false,
+            edge_to: vec![0; g.v()],
+            cycle: vec![],
         };
+        if c.has_self_loop(g) || c.has_parallel_edges(g) {
+            return c;
+        }
         for s in 0..g.v() {
-            if !c.marked[s] {
+            if !c.marked[s] && c.cycle.is_empty() {
                 c.dfs(g, s, s);
             }
         }
         c
     }
 
+    // a self-loop v-v is a cycle of length 1
+    fn has_self_loop(&mut self, g: &Graph) -> bool {
+        for v in 0..g.v() {
+            if g.adj(v).contains(&v) {
+                self.cycle.push(v);
+                self.cycle.push(v);
+                return true;
+            }
+        }
+        false
+    }
+
+    // two parallel edges v-w form a cycle of length 2
+    fn has_parallel_edges(&mut self, g: &Graph) -> bool {
+        for v in 0..g.v() {
+            // check for parallel edges incident to v
+            for &w in g.adj(v) {
+                if self.marked[w] {
+                    self.cycle.push(v);
+                    self.cycle.push(w);
+                    self.cycle.push(v);
+                    return true;
+                }
+                self.marked[w] = true;
+            }
+            // reset so marked[] is all false again
+            for &w in g.adj(v) {
+                self.marked[w] = false;
+            }
+        }
+        false
+    }
+
     fn dfs(&mut self, g: &Graph, v: usize, parent: usize) {
         self.marked[v] = true;
-        for w in g.adj(v).clone() {
+        for &w in g.adj(v) {
+            // short circuit if cycle already found
+            if !self.cycle.is_empty() {
+                return;
+            }
             if !self.marked[w] {
+                self.edge_to[w] = v;
                 self.dfs(g, w, v);
             } else if w != parent {
-                self.has_cycle = true;
-                return;
+                // trace back cycle: w -> ... -> v -> w
+                let mut x = v;
+                while x != w {
+                    self.cycle.push(x);
+                    x = self.edge_to[x];
+                }
+                self.cycle.push(w);
+                self.cycle.push(v);
             }
         }
     }
 
+    /// Does the graph have a cycle?
     pub fn has_cycle(&self) -> bool {
-        self.has_cycle
+        !self.cycle.is_empty()
+    }
+
+    /// Returns a cycle if the graph has one; the first vertex is repeated at the end.
+    pub fn cycle(&self) -> Iter {
+        Iter::new(self)
+    }
+}
+
+pub struct Iter {
+    stack: Vec<usize>,
+}
+
+impl Iter {
+    pub fn new(c: &Cycle) -> Self {
+        Iter {
+            stack: c.cycle.clone(),
+        }
+    }
+}
+
+impl Iterator for Iter {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.stack.pop()
     }
 }
 
@@ -44,6 +120,16 @@ impl Cycle {
 mod test {
     use super::*;
 
+    fn assert_cycle(g: &Graph, cycle: &[usize]) {
+        assert!(cycle.len() >= 2);
+        assert_eq!(cycle.first(), cycle.last());
+        for e in cycle.windows(2) {
+            assert!(g.adj(e[0]).contains(&e[1]));
+        }
+    }
+
+    // `Cycle::cycle(&c)` rather than `c.cycle()`: clippy takes the latter for `Iterator::cycle`
+
     #[test]
     fn detect_cycle() {
         let mut graph = Graph::new(13);
@@ -63,6 +149,13 @@ mod test {
 
         let cycle = Cycle::new(&graph);
         assert!(cycle.has_cycle());
+        let c = Cycle::cycle(&cycle).collect::<Vec<usize>>();
+        assert_cycle(&graph, &c);
+        // a simple cycle: no vertex repeated except the endpoints
+        let mut inner = c[1..].to_vec();
+        inner.sort_unstable();
+        inner.dedup();
+        assert_eq!(inner.len(), c.len() - 1);
     }
 
     #[test]
@@ -76,5 +169,50 @@ mod test {
 
         let cycle = Cycle::new(&graph);
         assert!(!cycle.has_cycle());
+        assert_eq!(Cycle::cycle(&cycle).count(), 0);
+    }
+
+    #[test]
+    fn one_cycle() {
+        // the only cycle is 1-2-3-4-1, with trees hanging off it
+        let mut graph = Graph::new(7);
+        for (v, w) in [(0, 1), (1, 2), (2, 3), (3, 4), (4, 1), (3, 5), (5, 6)] {
+            graph.add_edge(v, w);
+        }
+
+        let cycle = Cycle::new(&graph);
+        assert!(cycle.has_cycle());
+        let c = Cycle::cycle(&cycle).collect::<Vec<usize>>();
+        assert_eq!(c.len(), 5);
+        assert_cycle(&graph, &c);
+        let mut inner = c[1..].to_vec();
+        inner.sort_unstable();
+        assert_eq!(inner, vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn self_loop() {
+        let mut graph = Graph::new(3);
+        graph.add_edge(0, 1);
+        graph.add_edge(2, 2);
+
+        let cycle = Cycle::new(&graph);
+        assert!(cycle.has_cycle());
+        assert_eq!(Cycle::cycle(&cycle).collect::<Vec<usize>>(), vec![2, 2]);
+    }
+
+    #[test]
+    fn parallel_edges() {
+        let mut graph = Graph::new(4);
+        graph.add_edge(0, 1);
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 1);
+        graph.add_edge(2, 3);
+
+        let cycle = Cycle::new(&graph);
+        assert!(cycle.has_cycle());
+        let c = Cycle::cycle(&cycle).collect::<Vec<usize>>();
+        assert_eq!(c.len(), 3);
+        assert_cycle(&graph, &c);
     }
 }
@@ -1,6 +1,8 @@
 //! # Find directed paths from a source vertex to every other vertex in the digraph.
 //!
 //! This implementation uses depth-first search.
+//! `DepthFirstDirectedPaths` is recursive, and `DepthFirstDirectedPathsIterative` uses an explicit stack;
+//! both visit the neighbors of a vertex in `adj` order, first added first, so they find the same paths.
 
 use super::digraph::Digraph;
 pub struct DepthFirstDirectedPaths {
@@ -43,20 +45,76 @@ impl DepthFirstDirectedPaths {
     }
 }
 
+/// Same paths as `DepthFirstDirectedPaths`, but with an explicit stack instead of recursion,
+/// so that long paths (e.g. a chain of 100k vertices) do not overflow the call stack.
+pub struct DepthFirstDirectedPathsIterative {
+    marked: Vec<bool>,   // marked[v] = true iff v is reachable from s
+    edge_to: Vec<usize>, // edge_to[v] = last edge on path from s to v
+    s: usize,            // source
+}
+
+impl DepthFirstDirectedPathsIterative {
+    pub fn new(g: &Digraph, s: usize) -> DepthFirstDirectedPathsIterative {
+        let mut path = DepthFirstDirectedPathsIterative {
+            marked: vec![false; g.v()],
+            edge_to: vec![0; g.v()],
+            s,
+        };
+        path.dfs(g);
+        path
+    }
+
+    fn dfs(&mut self, g: &Digraph) {
+        // (v, index of the next neighbor of v to examine)
+        let mut stack = vec![(self.s, 0)];
+        self.marked[self.s] = true;
+        while let Some((v, i)) = stack.last_mut() {
+            let v = *v;
+            match g.adj(v).get(*i) {
+                Some(&w) => {
+                    *i += 1;
+                    if !self.marked[w] {
+                        self.marked[w] = true;
+                        self.edge_to[w] = v;
+                        stack.push((w, 0));
+                    }
+                }
+                None => {
+                    stack.pop();
+                }
+            }
+        }
+    }
+
+    /// Is there a directed path from the source to v?
+    pub fn has_path_to(&self, v: usize) -> bool {
+        self.marked[v]
+    }
+
+    /// Returns a directed path, the same one as `DepthFirstDirectedPaths::path_to`.
+    pub fn path_to(&self, v: usize) -> Iter {
+        Iter::trace(&self.edge_to, self.s, v, self.has_path_to(v))
+    }
+}
+
 pub struct Iter {
     stack: Vec<usize>,
 }
 
 impl Iter {
     pub fn new(path: &DepthFirstDirectedPaths, v: usize) -> Self {
+        Self::trace(&path.edge_to, path.s, v, path.has_path_to(v))
+    }
+
+    fn trace(edge_to: &[usize], s: usize, v: usize, has_path: bool) -> Self {
         let mut stack = Vec::new();
-        if path.has_path_to(v) {
+        if has_path {
             let mut x = v;
-            while x != path.s {
+            while x != s {
                 stack.push(x);
-                x = path.edge_to[x];
+                x = edge_to[x];
             }
-            stack.push(path.s);
+            stack.push(s);
         }
         Iter { stack }
     }
@@ -73,8 +131,7 @@ impl Iterator for Iter {
 mod test {
     use super::*;
 
-    #[test]
-    fn tiny_dg() {
+    fn tiny_dg() -> Digraph {
         let mut digraph = Digraph::new(13);
         digraph.add_edge(4, 2);
         digraph.add_edge(2, 3);
@@ -98,7 +155,12 @@ mod test {
         digraph.add_edge(6, 4);
         digraph.add_edge(6, 9);
         digraph.add_edge(7, 6);
+        digraph
+    }
 
+    #[test]
+    fn tiny_dg_paths() {
+        let digraph = tiny_dg();
         let search = DepthFirstDirectedPaths::new(&digraph, 3);
         assert!(search.has_path_to(0));
         assert_eq!(search.path_to(0).collect::<Vec<usize>>(), vec![3, 2, 0]);
@@ -108,4 +170,57 @@ mod test {
         assert!(!search.has_path_to(6));
         assert_eq!(search.path_to(6).collect::<Vec<usize>>(), vec![]);
     }
+
+    #[test]
+    fn iterative() {
+        let digraph = tiny_dg();
+        for s in 0..digraph.v() {
+            let recursive = DepthFirstDirectedPaths::new(&digraph, s);
+            let iterative = DepthFirstDirectedPathsIterative::new(&digraph, s);
+            for v in 0..digraph.v() {
+                assert_eq!(recursive.has_path_to(v), iterative.has_path_to(v));
+                assert!(recursive.path_to(v).eq(iterative.path_to(v)));
+            }
+        }
+
+        // tinyDAG
+        let mut dag = Digraph::new(13);
+        for (v, w) in [
+            (2, 3),
+            (0, 6),
+            (0, 1),
+            (2, 0),
+            (11, 12),
+            (9, 12),
+            (9, 11),
+            (9, 10),
+            (3, 5),
+            (8, 7),
+            (5, 4),
+            (0, 5),
+            (6, 4),
+            (6, 9),
+            (7, 6),
+        ] {
+            dag.add_edge(v, w);
+        }
+        let search = DepthFirstDirectedPathsIterative::new(&dag, 2);
+        assert_eq!(
+            search.path_to(12).collect::<Vec<usize>>(),
+            vec![2, 0, 6, 9, 12]
+        );
+        assert_eq!(search.path_to(4).collect::<Vec<usize>>(), vec![2, 3, 5, 4]);
+        assert!(!search.has_path_to(7));
+    }
+
+    #[test]
+    fn long_chain() {
+        let n = 500_000;
+        let mut digraph = Digraph::new(n);
+        for v in 0..n - 1 {
+            digraph.add_edge(v, v + 1);
+        }
+        let search = DepthFirstDirectedPathsIterative::new(&digraph, 0);
+        assert!(search.path_to(n - 1).eq(0..n));
+    }
 }
@@ -12,6 +12,10 @@ pub struct DepthFirstOrder {
                      // reverse postorder (a stack) can be obtained from post
 }
 
+/// `DepthFirstOrder` already searches with an explicit stack, visiting the neighbors
+/// of a vertex in `adj` order, first added first, as the recursive search does.
+pub type DepthFirstOrderIterative = DepthFirstOrder;
+
 impl DepthFirstOrder {
     pub fn new(g: &Digraph) -> Self {
         let mut dfs_order = DepthFirstOrder {
@@ -44,27 +48,54 @@ impl DepthFirstOrder {
         dfs_order
     }
 
-    fn dfs(&mut self, g: &Digraph, v: usize) {
-        self.pre.push(v);
-        self.marked[v] = true;
-        for w in g.adj(v).clone() {
-            if !self.marked[w] {
-                self.dfs(g, w);
+    // The searches are iterative, so that long paths do not overflow the call stack.
+    // A stack frame keeps its position in adj, so a vertex is post-visited exactly
+    // when its frame is popped, after all its descendants: the orders match the recursive search.
+
+    fn dfs(&mut self, g: &Digraph, s: usize) {
+        // (v, index of the next neighbor of v to examine)
+        let mut stack = vec![(s, 0)];
+        self.pre.push(s);
+        self.marked[s] = true;
+        while let Some((v, i)) = stack.last_mut() {
+            match g.adj(*v).get(*i) {
+                Some(&w) => {
+                    *i += 1;
+                    if !self.marked[w] {
+                        self.pre.push(w);
+                        self.marked[w] = true;
+                        stack.push((w, 0));
+                    }
+                }
+                None => {
+                    self.post.push(*v);
+                    stack.pop();
+                }
             }
         }
-        self.post.push(v);
     }
 
-    fn dfs_weighted_digraph(&mut self, g: &EdgeWeightedDiagraph, v: usize) {
-        self.pre.push(v);
-        self.marked[v] = true;
-        for e in g.adj(v) {
-            let w = e.to();
-            if !self.marked[w] {
-                self.dfs_weighted_digraph(g, w);
+    fn dfs_weighted_digraph(&mut self, g: &EdgeWeightedDiagraph, s: usize) {
+        // (v, remaining edges out of v)
+        let mut stack = vec![(s, g.adj(s))];
+        self.pre.push(s);
+        self.marked[s] = true;
+        while let Some((v, adj)) = stack.last_mut() {
+            match adj.next() {
+                Some(e) => {
+                    let w = e.to();
+                    if !self.marked[w] {
+                        self.pre.push(w);
+                        self.marked[w] = true;
+                        stack.push((w, g.adj(w)));
+                    }
+                }
+                None => {
+                    self.post.push(*v);
+                    stack.pop();
+                }
             }
         }
-        self.post.push(v);
     }
 
     /// Returns the vertices in preorder.
@@ -121,6 +152,7 @@ impl Iterator for RevPostIter {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::graphs::directed_edge::DirectedEdge;
 
     #[test]
     fn tiny_dag() {
@@ -155,5 +187,53 @@ mod test {
             order.rev_post().collect::<Vec<usize>>(),
             vec![8, 7, 2, 3, 0, 5, 1, 6, 9, 10, 11, 12, 4]
         );
+
+        let iterative = DepthFirstOrderIterative::new(&dag);
+        assert!(iterative.pre().eq(order.pre()));
+        assert!(iterative.post().eq(order.post()));
+    }
+
+    #[test]
+    fn long_chain() {
+        let n = 500_000;
+        let mut g = Digraph::new(n);
+        for v in 0..n - 1 {
+            g.add_edge(v, v + 1);
+        }
+        let order = DepthFirstOrderIterative::new(&g);
+        assert!(order.pre().eq(0..n));
+        assert!(order.post().eq((0..n).rev()));
+    }
+
+    #[test]
+    fn tiny_ewdag() {
+        let mut g = EdgeWeightedDiagraph::new(8);
+        for (v, w, weight) in [
+            (5, 4, 0.35),
+            (4, 7, 0.37),
+            (5, 7, 0.28),
+            (5, 1, 0.32),
+            (4, 0, 0.38),
+            (0, 2, 0.26),
+            (3, 7, 0.39),
+            (1, 3, 0.29),
+            (7, 2, 0.34),
+            (6, 2, 0.40),
+            (3, 6, 0.52),
+            (6, 0, 0.58),
+            (6, 4, 0.93),
+        ] {
+            g.add_edge(DirectedEdge::new(v, w, weight));
+        }
+
+        let order = DepthFirstOrder::from_weighted_diagraph(&g);
+        assert_eq!(
+            order.pre().collect::<Vec<usize>>(),
+            vec![0, 2, 1, 3, 7, 6, 4, 5]
+        );
+        assert_eq!(
+            order.post().collect::<Vec<usize>>(),
+            vec![2, 0, 7, 4, 6, 3, 1, 5]
+        );
     }
 }
@@ -40,18 +40,91 @@ impl DepthFirstPaths {
         self.marked[v]
     }
 
-    pub fn path_to(&self, v: usize) -> Vec<usize> {
-        if !self.has_path_to(v) {
-            return Vec::new();
+    pub fn path_to(&self, v: usize) -> Iter {
+        Iter::new(self, v)
+    }
+}
+
+/// Same paths as `DepthFirstPaths`, but with an explicit stack instead of recursion,
+/// so that long paths (e.g. a path graph of 100k vertices) do not overflow the call stack.
+pub struct DepthFirstPathsIterative {
+    marked: Vec<bool>,
+    edge_to: Vec<usize>, // last vertex on known path to this vertex
+    source: usize,
+}
+
+impl DepthFirstPathsIterative {
+    pub fn new(g: &Graph, s: usize) -> DepthFirstPathsIterative {
+        let mut paths = DepthFirstPathsIterative {
+            marked: vec![false; g.v()],
+            edge_to: vec![0; g.v()],
+            source: s,
+        };
+        paths.dfs(g);
+        paths
+    }
+
+    fn dfs(&mut self, g: &Graph) {
+        // (v, index of the next neighbor of v to examine)
+        // keeping the index visits vertices in the same order as the recursive version
+        let mut stack = vec![(self.source, 0)];
+        self.marked[self.source] = true;
+        while let Some((v, i)) = stack.last_mut() {
+            let v = *v;
+            match g.adj(v).get(*i) {
+                Some(&w) => {
+                    *i += 1;
+                    if !self.marked[w] {
+                        self.marked[w] = true;
+                        self.edge_to[w] = v;
+                        stack.push((w, 0));
+                    }
+                }
+                None => {
+                    stack.pop();
+                }
+            }
         }
-        let mut paths = Vec::new();
-        let mut x = v;
-        while x != self.source {
-            paths.insert(0, x);
-            x = self.edge_to[x];
+    }
+
+    pub fn has_path_to(&self, v: usize) -> bool {
+        self.marked[v]
+    }
+
+    pub fn path_to(&self, v: usize) -> Iter {
+        Iter::trace(&self.edge_to, self.source, v, self.has_path_to(v))
+    }
+}
+
+pub struct Iter {
+    stack: Vec<usize>,
+}
+
+impl Iter {
+    pub fn new(path: &DepthFirstPaths, v: usize) -> Self {
+        Self::trace(&path.edge_to, path.source, v, path.has_path_to(v))
+    }
+
+    fn trace(edge_to: &[usize], source: usize, v: usize, has_path: bool) -> Self {
+        let mut stack = Vec::new();
+        if has_path {
+            let mut x = v;
+            while x != source {
+                stack.push(x);
+                x = edge_to[x];
+            }
+            stack.push(source);
         }
-        paths.insert(0, self.source);
-        paths
+
+        Iter { stack }
+    }
+}
+
+impl Iterator for Iter {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.stack.pop()
     }
 }
 
@@ -59,8 +132,7 @@ impl DepthFirstPaths {
 mod test {
     use super::*;
 
-    #[test]
-    fn dfs_path() {
+    fn tiny_cg() -> Graph {
         let mut graph = Graph::new(6);
         graph.add_edge(0, 5);
         graph.add_edge(2, 4);
@@ -70,7 +142,12 @@ mod test {
         graph.add_edge(3, 4);
         graph.add_edge(3, 5);
         graph.add_edge(0, 2);
+        graph
+    }
 
+    #[test]
+    fn dfs_path() {
+        let graph = tiny_cg();
         // 0: 5 2 1
         // 1: 2 0
         // 2: 4 3 1 0
@@ -80,7 +157,73 @@ mod test {
 
         let df_path = DepthFirstPaths::new(&graph, 0);
 
-        assert_eq!(df_path.path_to(5), vec![0, 5]);
-        assert_eq!(df_path.path_to(2), vec![0, 5, 3, 2]);
+        assert_eq!(df_path.path_to(5).collect::<Vec<usize>>(), vec![0, 5]);
+        assert_eq!(df_path.path_to(2).collect::<Vec<usize>>(), vec![0, 5, 3, 2]);
+    }
+
+    #[test]
+    fn all_paths() {
+        let graph = tiny_cg();
+        let df_path = DepthFirstPaths::new(&graph, 0);
+        let paths = (0..graph.v())
+            .map(|v| df_path.path_to(v).collect::<Vec<usize>>())
+            .collect::<Vec<Vec<usize>>>();
+        assert_eq!(
+            paths,
+            vec![
+                vec![0],
+                vec![0, 5, 3, 2, 1],
+                vec![0, 5, 3, 2],
+                vec![0, 5, 3],
+                vec![0, 5, 3, 2, 4],
+                vec![0, 5],
+            ]
+        );
+        for path in &paths {
+            for e in path.windows(2) {
+                assert!(graph.has_edge(e[0], e[1]));
+            }
+        }
+    }
+
+    #[test]
+    fn no_path() {
+        let mut graph = Graph::new(4);
+        graph.add_edge(0, 1);
+        graph.add_edge(2, 3);
+
+        let df_path = DepthFirstPaths::new(&graph, 0);
+        assert!(!df_path.has_path_to(3));
+        assert_eq!(df_path.path_to(3).count(), 0);
+        assert_eq!(df_path.path_to(0).collect::<Vec<usize>>(), vec![0]);
+    }
+
+    #[test]
+    fn iterative_same_paths() {
+        let graph = tiny_cg();
+        let recursive = DepthFirstPaths::new(&graph, 0);
+        let iterative = DepthFirstPathsIterative::new(&graph, 0);
+        for v in 0..graph.v() {
+            assert_eq!(
+                recursive.path_to(v).collect::<Vec<usize>>(),
+                iterative.path_to(v).collect::<Vec<usize>>()
+            );
+        }
+    }
+
+    #[test]
+    fn iterative_long_path() {
+        // deep enough to overflow the stack with the recursive version
+        let n = 100_000;
+        let mut graph = Graph::new(n);
+        for v in 0..n - 1 {
+            graph.add_edge(v, v + 1);
+        }
+
+        let df_path = DepthFirstPathsIterative::new(&graph, 0);
+        assert!(df_path.has_path_to(n - 1));
+        let path = df_path.path_to(n - 1).collect::<Vec<usize>>();
+        assert_eq!(path.len(), n);
+        assert!(path.iter().enumerate().all(|(i, &v)| i == v));
     }
 }
@@ -2,6 +2,10 @@
 //!
 //! This implementation uses an `adjacency-lists` representation.
 use std::fmt;
+use std::io::BufRead;
+
+use super::graph_reader::{self, Tokens};
+use super::interop;
 pub struct Digraph {
     v: usize,
     e: usize,
@@ -20,6 +24,29 @@ impl Digraph {
         }
     }
 
+    /// Reads a digraph in the format `V`, `E`, then `E` pairs of vertices.
+    pub fn from_reader(r: impl BufRead) -> graph_reader::Result<Digraph> {
+        let mut tokens = Tokens::read(r)?;
+        let (v, e) = tokens.counts()?;
+        let mut digraph = Digraph::new(v);
+        for _ in 0..e {
+            let from = tokens.vertex()?;
+            let to = tokens.vertex()?;
+            digraph.add_edge(from, to);
+        }
+        Ok(digraph)
+    }
+
+    /// Initializes a digraph with v vertices and the directed edges `v→w`, added in order.
+    /// Panics if an endpoint is not less than v.
+    pub fn from_edges(v: usize, edges: &[(usize, usize)]) -> Digraph {
+        let mut digraph = Digraph::new(v);
+        for &(v, w) in edges {
+            digraph.add_edge(v, w);
+        }
+        digraph
+    }
+
     /// Returns the number of vertices in this digraph.
     pub fn v(&self) -> usize {
         self.v
@@ -36,6 +63,14 @@ impl Digraph {
         }
     }
 
+    /// Adds a new vertex with no edges and returns its index.
+    pub fn add_vertex(&mut self) -> usize {
+        self.adj.push(Vec::new());
+        self.in_degree.push(0);
+        self.v += 1;
+        self.v - 1
+    }
+
     /// Adds the directed edge v→w to this digraph.
     pub fn add_edge(&mut self, v: usize, w: usize) {
         self.validate_vertex(v);
@@ -52,6 +87,24 @@ impl Digraph {
         &self.adj[v]
     }
 
+    /// Is there an edge v→w? It takes time proportional to the outdegree of v.
+    pub fn has_edge(&self, v: usize, w: usize) -> bool {
+        self.validate_vertex(w);
+        self.adj(v).contains(&w)
+    }
+
+    /// Removes the first edge v→w, keeping the order of the other edges from v.
+    /// It does nothing if there is no such edge.
+    pub fn remove_edge(&mut self, v: usize, w: usize) {
+        self.validate_vertex(v);
+        self.validate_vertex(w);
+        if let Some(i) = self.adj[v].iter().position(|&x| x == w) {
+            self.adj[v].remove(i);
+            self.in_degree[w] -= 1;
+            self.e -= 1;
+        }
+    }
+
     /// Returns the reverse of the digraph.
     pub fn reverse(&self) -> Digraph {
         let mut r = Digraph::new(self.v);
@@ -74,17 +127,28 @@ impl Digraph {
         self.validate_vertex(v);
         self.in_degree[v]
     }
+
+    /// Returns the digraph in Graphviz DOT format, each edge as `v -> w`.
+    pub fn to_dot(&self) -> String {
+        self.to_dot_with_highlighted_edges(&[])
+    }
+
+    /// Returns the digraph in Graphviz DOT format, drawing the given edges in red.
+    pub fn to_dot_with_highlighted_edges(&self, edges: &[(usize, usize)]) -> String {
+        let list = (0..self.v).flat_map(|v| self.adj[v].iter().map(move |&w| (v, w, None)));
+        interop::write_dot(true, self.v, list, edges)
+    }
 }
 
+/// Writes the digraph in the format read by `from_reader`.
 impl fmt::Display for Digraph {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        writeln!(f, "{} vertices, {} edges", self.v, self.e)?;
-        for (i, adj) in self.adj.iter().enumerate() {
-            write!(f, "{}: ", i)?;
-            for v in adj {
-                write!(f, "{} ", v)?;
+        writeln!(f, "{}", self.v)?;
+        writeln!(f, "{}", self.e)?;
+        for (v, adj) in self.adj.iter().enumerate() {
+            for w in adj {
+                writeln!(f, "{} {}", v, w)?;
             }
-            writeln!(f)?;
         }
         Ok(())
     }
@@ -129,6 +193,217 @@ mod test {
         tmp.sort_unstable();
         assert_eq!(tmp, vec![0, 4, 9]);
 
+        assert!(digraph.has_edge(4, 2));
+        assert!(!digraph.has_edge(2, 4));
+        assert!(digraph.has_edge(7, 8) && digraph.has_edge(8, 7));
+        assert!(!digraph.has_edge(1, 1));
+
         println!("{}", digraph);
     }
+
+    const TINY_DG: &str = "13
+22
+ 4  2
+ 2  3
+ 3  2
+ 6  0
+ 0  1
+ 2  0
+11 12
+12  9
+ 9 10
+ 9 11
+ 7  9
+10 12
+11  4
+ 4  3
+ 3  5
+ 6  8
+ 8  6
+ 5  4
+ 0  5
+ 6  4
+ 6  9
+ 7  6
+";
+
+    #[test]
+    fn add_vertex() {
+        let mut digraph = Digraph::new(1);
+        for _ in 0..4 {
+            let v = digraph.add_vertex();
+            digraph.add_edge(v - 1, v);
+        }
+        let v = digraph.add_vertex();
+        digraph.add_edge(v, 0);
+        assert_eq!(digraph.v(), 6);
+        assert_eq!(digraph.e(), 5);
+        assert_eq!(digraph.adj(3), &vec![4]);
+        assert_eq!(digraph.in_degree(0), 1);
+        assert_eq!(digraph.out_degree(4), 0);
+
+        let dfs = crate::graphs::directed_dfs::DirectedDFS::new(&digraph, 2);
+        assert_eq!(dfs.count(), 3);
+        assert!(dfs.marked(4));
+        assert!(!dfs.marked(5));
+    }
+
+    #[test]
+    fn remove_edge() {
+        let mut g = Digraph::new(4);
+        g.add_edge(0, 1);
+        g.add_edge(0, 2);
+        g.add_edge(0, 1);
+        g.add_edge(0, 3);
+        g.add_edge(2, 1);
+        g.add_edge(3, 3);
+        assert!(g.has_edge(0, 1));
+        assert!(!g.has_edge(1, 0));
+
+        // the first of the parallel edges goes
+        g.remove_edge(0, 1);
+        assert_eq!(g.adj(0), &vec![2, 1, 3]);
+        assert_eq!(g.e(), 5);
+        assert_eq!(g.in_degree(1), 2);
+        assert_eq!(g.out_degree(0), 3);
+        assert!(g.has_edge(0, 1));
+
+        g.remove_edge(0, 1);
+        assert!(!g.has_edge(0, 1));
+        assert_eq!(g.adj(0), &vec![2, 3]);
+
+        // absent edges are ignored
+        g.remove_edge(0, 1);
+        g.remove_edge(1, 2);
+        assert_eq!(g.e(), 4);
+
+        g.remove_edge(3, 3);
+        assert_eq!(g.in_degree(3), 1);
+        assert_eq!(g.out_degree(3), 0);
+
+        let in_degrees: usize = (0..g.v()).map(|v| g.in_degree(v)).sum();
+        let out_degrees: usize = (0..g.v()).map(|v| g.out_degree(v)).sum();
+        assert_eq!(in_degrees, g.e());
+        assert_eq!(out_degrees, g.e());
+    }
+
+    #[test]
+    #[should_panic(expected = "vertex 4 is not between 0 and 4")]
+    fn remove_edge_out_of_range() {
+        Digraph::new(4).remove_edge(0, 4);
+    }
+
+    #[test]
+    fn from_edges() {
+        let digraph = Digraph::from_edges(6, &[(0, 1), (4, 2), (1, 4)]);
+        assert_eq!(digraph.v(), 6);
+        assert_eq!(digraph.e(), 3);
+        assert_eq!(digraph.in_degree(2), 1);
+        assert_eq!(digraph.adj(3), &vec![]);
+
+        let empty = Digraph::from_edges(0, &[]);
+        assert_eq!(empty.v(), 0);
+    }
+
+    #[test]
+    fn from_edges_tiny_dg() {
+        let edges = [
+            (4, 2),
+            (2, 3),
+            (3, 2),
+            (6, 0),
+            (0, 1),
+            (2, 0),
+            (11, 12),
+            (12, 9),
+            (9, 10),
+            (9, 11),
+            (8, 9),
+            (10, 12),
+            (11, 4),
+            (4, 3),
+            (3, 5),
+            (7, 8),
+            (8, 7),
+            (5, 4),
+            (0, 5),
+            (6, 4),
+            (6, 9),
+            (7, 6),
+        ];
+        let digraph = Digraph::from_edges(13, &edges);
+        let mut expected = Digraph::new(13);
+        for &(v, w) in &edges {
+            expected.add_edge(v, w);
+        }
+        assert_eq!(digraph.e(), 22);
+        assert_eq!(digraph.adj(6), &vec![0, 4, 9]);
+        assert_eq!(digraph.adj(2), expected.adj(2));
+        assert_eq!(digraph.in_degree(9), expected.in_degree(9));
+        assert_eq!(digraph.to_string(), expected.to_string());
+    }
+
+    #[test]
+    #[should_panic(expected = "vertex 13 is not between 0 and 13")]
+    fn from_edges_out_of_range() {
+        Digraph::from_edges(13, &[(0, 1), (12, 13)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "vertex 2 is not between 0 and 2")]
+    fn add_edge_out_of_range() {
+        let mut digraph = Digraph::new(1);
+        digraph.add_vertex();
+        digraph.add_edge(0, 2);
+    }
+
+    #[test]
+    fn from_reader() {
+        let digraph = Digraph::from_reader(TINY_DG.as_bytes()).unwrap();
+        assert_eq!(digraph.v(), 13);
+        assert_eq!(digraph.e(), 22);
+        assert_eq!(digraph.adj(6), &vec![0, 8, 4, 9]);
+        assert_eq!(digraph.adj(1), &vec![]);
+        assert_eq!(digraph.in_degree(4), 3);
+
+        let text = digraph.to_string();
+        let copy = Digraph::from_reader(text.as_bytes()).unwrap();
+        assert_eq!(copy.to_string(), text);
+        for v in 0..13 {
+            assert_eq!(copy.adj(v), digraph.adj(v));
+        }
+    }
+
+    #[test]
+    fn from_reader_errors() {
+        use crate::graphs::graph_reader::ParseGraphError;
+
+        let err = |s: &str| Digraph::from_reader(s.as_bytes()).err().unwrap();
+        assert!(matches!(err("13"), ParseGraphError::UnexpectedEof));
+        assert!(matches!(
+            err("13\n22\n4 2\n2 3\n"),
+            ParseGraphError::UnexpectedEof
+        ));
+        assert!(matches!(err("-13 0"), ParseGraphError::InvalidCount(_)));
+        assert!(matches!(
+            err("13 -2"),
+            ParseGraphError::NegativeEdgeCount(-2)
+        ));
+        assert!(matches!(
+            err("2 1 1 7"),
+            ParseGraphError::VertexOutOfRange { vertex: 7, v: 2 }
+        ));
+    }
+
+    #[test]
+    fn to_dot() {
+        let g = Digraph::from_edges(3, &[(0, 1), (1, 0), (1, 2)]);
+        assert_eq!(
+            g.to_dot(),
+            "digraph {\n  0;\n  1;\n  2;\n  0 -> 1;\n  1 -> 0;\n  1 -> 2;\n}\n"
+        );
+        let dot = g.to_dot_with_highlighted_edges(&[(1, 0)]);
+        assert!(dot.contains("  1 -> 0 [color=red, penwidth=2];\n"));
+        assert!(dot.contains("  0 -> 1;\n"));
+    }
 }
@@ -0,0 +1,176 @@
+//! # Single source shortest paths in edge-weighted digraphs with non-negative integer weights
+//!
+//! This implementation uses Dijkstra's algorithm with a radix heap.
+//! Distances only grow as vertices are removed, which is the monotone order a radix heap needs.
+//! The heap has no decrease-key, so a vertex may be queued more than once, and stale entries are skipped.
+//! The time complexity is O(E + V log(C)), where C is the largest edge weight.
+//! Weights must be integers of at most (u64::MAX - 1) / V, so that no distance overflows.
+
+use crate::sorting::radix_heap::RadixHeap;
+
+use super::{directed_edge::DirectedEdge, weighted_digraph::EdgeWeightedDiagraph};
+pub struct DijkstraRadixSP {
+    dist_to: Vec<u64>,                  // dist_to[v] = distance of shortest s->v path
+    edge_to: Vec<Option<DirectedEdge>>, // edge_to[v] = last edge on shortest s->v path
+}
+
+impl DijkstraRadixSP {
+    pub fn new(g: &EdgeWeightedDiagraph, s: usize) -> Self {
+        // a relaxed distance is a shortest path plus one edge, so at most V edges; below
+        // this bound it cannot overflow, or reach u64::MAX, which marks unreached vertices
+        let max_weight = (u64::MAX - 1) / g.v().max(1) as u64;
+        for e in g.edges() {
+            let weight = e.weight();
+            if weight < 0.0 || weight.fract() != 0.0 {
+                panic!("edge {} does not have a non-negative integer weight", e);
+            }
+            if weight as u64 > max_weight {
+                panic!("edge {} has a weight above {}", e, max_weight);
+            }
+        }
+
+        let mut sp = DijkstraRadixSP {
+            dist_to: vec![u64::MAX; g.v()],
+            edge_to: vec![None; g.v()],
+        };
+        sp.dist_to[s] = 0;
+
+        let mut pq = RadixHeap::new();
+        pq.push(0, s);
+        while let Some((d, v)) = pq.pop() {
+            // a shorter path to v has already been relaxed
+            if d > sp.dist_to[v] {
+                continue;
+            }
+            for &e in g.adj_ref(v) {
+                let w = e.to();
+                let dist = d + e.weight() as u64;
+                if dist < sp.dist_to[w] {
+                    sp.dist_to[w] = dist;
+                    sp.edge_to[w] = Some(e);
+                    pq.push(dist, w);
+                }
+            }
+        }
+        sp
+    }
+
+    /// Returns the length of a shortest path from s to v
+    pub fn dist_to(&self, v: usize) -> u64 {
+        self.dist_to[v]
+    }
+
+    /// Returns true if there is a path from s to v
+    pub fn has_path_to(&self, v: usize) -> bool {
+        self.dist_to[v] < u64::MAX
+    }
+
+    pub fn path_to(&self, v: usize) -> std::vec::IntoIter<DirectedEdge> {
+        let mut path = Vec::new();
+        if !self.has_path_to(v) {
+            return path.into_iter();
+        }
+
+        let mut vertex = v;
+        while let Some(edge) = self.edge_to[vertex] {
+            vertex = edge.from();
+            path.push(edge);
+        }
+
+        path.reverse();
+        path.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    use super::*;
+    use crate::graphs::dijkstra_sp::DijkstraSP;
+
+    #[test]
+    fn tiny_ewd() {
+        // tinyEWD with weights scaled by 100
+        let mut g = EdgeWeightedDiagraph::new(8);
+        for (v, w, weight) in [
+            (4, 5, 35.0),
+            (5, 4, 35.0),
+            (4, 7, 37.0),
+            (5, 7, 28.0),
+            (7, 5, 28.0),
+            (5, 1, 32.0),
+            (0, 4, 38.0),
+            (0, 2, 26.0),
+            (7, 3, 39.0),
+            (1, 3, 29.0),
+            (2, 7, 34.0),
+            (6, 2, 40.0),
+            (3, 6, 52.0),
+            (6, 0, 58.0),
+            (6, 4, 93.0),
+        ] {
+            g.add_edge(DirectedEdge::new(v, w, weight));
+        }
+
+        let sp = DijkstraRadixSP::new(&g, 0);
+        let dist = (0..8).map(|v| sp.dist_to(v)).collect::<Vec<u64>>();
+        assert_eq!(dist, vec![0, 105, 26, 99, 38, 73, 151, 60]);
+        assert_eq!(
+            sp.path_to(6).map(|e| e.to()).collect::<Vec<usize>>(),
+            vec![2, 7, 3, 6]
+        );
+    }
+
+    #[test]
+    fn same_as_dijkstra() {
+        let mut rng = StdRng::seed_from_u64(9);
+        for _ in 0..20 {
+            let n = 50;
+            let mut g = EdgeWeightedDiagraph::new(n);
+            for _ in 0..200 {
+                let (v, w) = (rng.gen_range(0..n), rng.gen_range(0..n));
+                g.add_edge(DirectedEdge::new(v, w, rng.gen_range(0..1000) as f64));
+            }
+            let radix = DijkstraRadixSP::new(&g, 0);
+            let binary = DijkstraSP::new(&g, 0);
+            for v in 0..n {
+                assert_eq!(radix.has_path_to(v), binary.has_path_to(v));
+                if radix.has_path_to(v) {
+                    assert_eq!(radix.dist_to(v) as f64, binary.dist_to(v));
+                    let len: f64 = radix.path_to(v).map(|e| e.weight()).sum();
+                    assert_eq!(len, binary.dist_to(v));
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn fractional_weight() {
+        let mut g = EdgeWeightedDiagraph::new(2);
+        g.add_edge(DirectedEdge::new(0, 1, 0.5));
+        DijkstraRadixSP::new(&g, 0);
+    }
+
+    #[test]
+    fn large_weights() {
+        // 2^62 is below (2^64 - 2) / 3, so even a path of all three vertices fits
+        let mut g = EdgeWeightedDiagraph::new(3);
+        g.add_edge(DirectedEdge::new(0, 1, (1u64 << 62) as f64));
+        g.add_edge(DirectedEdge::new(1, 2, (1u64 << 62) as f64));
+        g.add_edge(DirectedEdge::new(2, 0, (1u64 << 62) as f64));
+        let sp = DijkstraRadixSP::new(&g, 0);
+        assert_eq!(sp.dist_to(2), 1 << 63);
+    }
+
+    #[test]
+    #[should_panic(expected = "has a weight above")]
+    fn weight_too_large() {
+        let mut g = EdgeWeightedDiagraph::new(3);
+        g.add_edge(DirectedEdge::new(0, 1, (1u64 << 63) as f64));
+        g.add_edge(DirectedEdge::new(1, 2, (1u64 << 63) as f64));
+        DijkstraRadixSP::new(&g, 0);
+    }
+}
@@ -28,7 +28,7 @@ impl DijkstraSP {
         // relax vertices in order of distance from s
         sp.pq.insert(s, sp.dist_to[s]);
         while let Some(v) = sp.pq.del_min() {
-            for edge in g.adj(v) {
+            for &edge in g.adj_ref(v) {
                 sp.relax(edge);
             }
         }
@@ -26,30 +26,42 @@ impl DirectedCycle {
         dc
     }
 
-    fn dfs(&mut self, g: &Digraph, v: usize) {
-        self.on_stack[v] = true;
-        self.marked[v] = true;
-        for w in g.adj(v).clone() {
-            // short circuit if directed cycle found
-            if !self.cycle.is_empty() {
-                return;
-            }
-            if !self.marked[w] {
-                self.edge_to[w] = v;
-                self.dfs(g, w);
-            } else if self.on_stack[w] {
-                // trace back directed cycle
-                // v -> w -> ... -> v
-                let mut x = v;
-                while x != w {
-                    self.cycle.push(x);
-                    x = self.edge_to[x];
+    // iterative, so that long paths do not overflow the call stack
+    fn dfs(&mut self, g: &Digraph, s: usize) {
+        // (v, index of the next neighbor of v to examine)
+        let mut stack = vec![(s, 0)];
+        self.on_stack[s] = true;
+        self.marked[s] = true;
+        while let Some((v, i)) = stack.last_mut() {
+            let v = *v;
+            match g.adj(v).get(*i) {
+                Some(&w) => {
+                    *i += 1;
+                    if !self.marked[w] {
+                        self.edge_to[w] = v;
+                        self.on_stack[w] = true;
+                        self.marked[w] = true;
+                        stack.push((w, 0));
+                    } else if self.on_stack[w] {
+                        // trace back directed cycle
+                        // v -> w -> ... -> v
+                        let mut x = v;
+                        while x != w {
+                            self.cycle.push(x);
+                            x = self.edge_to[x];
+                        }
+                        self.cycle.push(w);
+                        self.cycle.push(v);
+                        // short circuit since directed cycle found
+                        return;
+                    }
+                }
+                None => {
+                    self.on_stack[v] = false;
+                    stack.pop();
                 }
-                self.cycle.push(w);
-                self.cycle.push(v);
             }
         }
-        self.on_stack[v] = false;
     }
 
     /// Does the digraph have a directed cycle?
@@ -116,7 +128,11 @@ mod test {
 
         let dc = DirectedCycle::new(&digraph);
         assert!(dc.has_cycle());
-        // assert_eq!(dc.cycle().collect::<Vec<usize>>(), vec![3, 2, 3]);
+        // `DirectedCycle::cycle(&dc)` rather than `dc.cycle()`: clippy takes the latter for `Iterator::cycle`
+        assert_eq!(
+            DirectedCycle::cycle(&dc).collect::<Vec<usize>>(),
+            vec![3, 2, 3]
+        );
     }
 
     #[test]
@@ -142,4 +158,15 @@ mod test {
         assert!(!dc.has_cycle());
         // assert_eq!(dc.cycle().collect::<Vec<usize>>(), vec![]);
     }
+
+    #[test]
+    fn long_cycle() {
+        let n = 500_000;
+        let mut digraph = Digraph::new(n);
+        for v in 0..n {
+            digraph.add_edge(v, (v + 1) % n);
+        }
+        let dc = DirectedCycle::new(&digraph);
+        assert_eq!(DirectedCycle::cycle(&dc).count(), n + 1);
+    }
 }
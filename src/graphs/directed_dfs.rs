@@ -1,16 +1,17 @@
 //! # Determine single-source or multiple-source reachability in a digraph using depth first search.
 
 use super::digraph::Digraph;
+use crate::fundamentals::bitset::BitSet;
 
 pub struct DirectedDFS {
-    marked: Vec<bool>, // marked[v] = true iff v is reachable from source
-    count: usize,      // number of vertices reachable from source
+    marked: BitSet, // v is in marked iff v is reachable from source
+    count: usize,   // number of vertices reachable from source
 }
 
 impl DirectedDFS {
     pub fn new(g: &Digraph, s: usize) -> DirectedDFS {
         let mut directed_dfs = DirectedDFS {
-            marked: vec![false; g.v()],
+            marked: BitSet::new(g.v()),
             count: 0,
         };
         directed_dfs.dfs(g, s);
@@ -19,7 +20,7 @@ impl DirectedDFS {
 
     pub fn from_sources(g: &Digraph, sources: Vec<usize>) -> DirectedDFS {
         let mut directed_dfs = DirectedDFS {
-            marked: vec![false; g.v()],
+            marked: BitSet::new(g.v()),
             count: 0,
         };
         for v in sources {
@@ -30,19 +31,36 @@ impl DirectedDFS {
         directed_dfs
     }
 
-    fn dfs(&mut self, g: &Digraph, v: usize) {
-        self.marked[v] = true;
+    // iterative, so that long paths do not overflow the call stack
+    fn dfs(&mut self, g: &Digraph, s: usize) {
+        // (v, index of the next neighbor of v to examine)
+        let mut stack = vec![(s, 0)];
+        self.marked.insert(s);
         self.count += 1;
-        for w in g.adj(v).clone() {
-            if !self.marked[w] {
-                self.dfs(g, w);
+        while let Some((v, i)) = stack.last_mut() {
+            match g.adj(*v).get(*i) {
+                Some(&w) => {
+                    *i += 1;
+                    if self.marked.insert(w) {
+                        self.count += 1;
+                        stack.push((w, 0));
+                    }
+                }
+                None => {
+                    stack.pop();
+                }
             }
         }
     }
 
     /// Is there a directed path from the source vertex to v?
     pub fn marked(&self, v: usize) -> bool {
-        self.marked[v]
+        self.marked.contains(v)
+    }
+
+    /// Returns the set of vertices reachable from the sources.
+    pub(crate) fn into_marked(self) -> BitSet {
+        self.marked
     }
 
     /// Returns the number of vertices reachable from the source vertex
@@ -137,4 +155,15 @@ mod test {
         tmp.sort_unstable();
         assert_eq!(tmp, vec![0, 1, 2, 3, 4, 5, 6, 9, 10, 11, 12]);
     }
+
+    #[test]
+    fn long_chain() {
+        let n = 500_000;
+        let mut digraph = Digraph::new(n);
+        for v in 0..n - 1 {
+            digraph.add_edge(v, v + 1);
+        }
+        assert_eq!(DirectedDFS::new(&digraph, 0).count(), n);
+        assert_eq!(DirectedDFS::new(&digraph, n / 2).count(), n - n / 2);
+    }
 }
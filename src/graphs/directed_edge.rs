@@ -2,6 +2,11 @@
 //!
 //! Each edge consists of two integers (naming the two vertices) and a
 //! real-value weight.
+//!
+//! Directed edges are ordered by weight, using the IEEE 754 total order, then by tail and head vertex.
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+
 #[derive(Clone, Debug, Copy, Default)]
 pub struct DirectedEdge {
     v: usize,
@@ -10,7 +15,11 @@ pub struct DirectedEdge {
 }
 
 impl DirectedEdge {
+    /// Panics if the weight is NaN.
     pub fn new(v: usize, w: usize, weight: f64) -> Self {
+        if weight.is_nan() {
+            panic!("weight of edge {}->{} is NaN", v, w);
+        }
         DirectedEdge { v, w, weight }
     }
 
@@ -30,6 +39,35 @@ impl DirectedEdge {
     }
 }
 
+impl PartialOrd for DirectedEdge {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for DirectedEdge {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.weight
+            .total_cmp(&other.weight)
+            .then_with(|| (self.v, self.w).cmp(&(other.v, other.w)))
+    }
+}
+
+impl PartialEq for DirectedEdge {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for DirectedEdge {}
+
+impl Hash for DirectedEdge {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.weight.to_bits().hash(state);
+        (self.v, self.w).hash(state);
+    }
+}
+
 impl std::fmt::Display for DirectedEdge {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}->{} {:5.2}", self.v, self.w, self.weight)
@@ -44,5 +82,15 @@ mod test {
     fn edge() {
         let edge = DirectedEdge::new(12, 34, 5.67);
         println!("{}", edge);
+        assert_eq!(edge, DirectedEdge::new(12, 34, 5.67));
+        assert_ne!(edge, DirectedEdge::new(34, 12, 5.67));
+        assert!(edge < DirectedEdge::new(12, 35, 5.67));
+        assert!(edge > DirectedEdge::new(40, 41, -0.5));
+    }
+
+    #[test]
+    #[should_panic(expected = "NaN")]
+    fn nan_weight() {
+        DirectedEdge::new(0, 1, f64::NAN);
     }
 }
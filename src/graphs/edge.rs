@@ -3,7 +3,12 @@
 //! Each edge consists of two integers (naming the two vertices)
 //! and a real-value weight.
 //!
-//! Compares two edges by weight.
+//! Compares two edges by weight, using the IEEE 754 total order, and breaks ties
+//! by the endpoints, so that sorting and heaps give the same result on every run.
+//! Two edges are equal iff they join the same two vertices with the same weight.
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+
 #[derive(Debug, Clone)]
 pub struct Edge {
     v: usize,
@@ -12,7 +17,11 @@ pub struct Edge {
 }
 
 impl Edge {
+    /// Panics if the weight is NaN.
     pub fn new(v: usize, w: usize, weight: f64) -> Self {
+        if weight.is_nan() {
+            panic!("weight of edge {}-{} is NaN", v, w);
+        }
         Edge { v, w, weight }
     }
 
@@ -36,28 +45,43 @@ impl Edge {
             panic!("Illegal endpoint");
         }
     }
+
+    // the endpoints, smaller first, as v-w and w-v are the same edge
+    fn endpoints(&self) -> (usize, usize) {
+        (self.v.min(self.w), self.v.max(self.w))
+    }
 }
 
 impl PartialOrd for Edge {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        self.weight.partial_cmp(&other.weight)
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
     }
 }
 
 impl PartialEq for Edge {
     fn eq(&self, other: &Self) -> bool {
-        self.weight == other.weight
+        self.cmp(other) == Ordering::Equal
     }
 }
 
 impl Ord for Edge {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.partial_cmp(other).unwrap()
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.weight
+            .total_cmp(&other.weight)
+            .then_with(|| self.endpoints().cmp(&other.endpoints()))
     }
 }
 
 impl Eq for Edge {}
 
+impl Hash for Edge {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // the bits tell apart exactly the weights that `total_cmp` does
+        self.weight.to_bits().hash(state);
+        self.endpoints().hash(state);
+    }
+}
+
 impl std::fmt::Display for Edge {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}-{} {:.5}", self.v, self.w, self.weight)
@@ -66,7 +90,8 @@ impl std::fmt::Display for Edge {
 
 #[cfg(test)]
 mod test {
-    use std::cmp::Ordering;
+    use std::cmp::Reverse;
+    use std::collections::{BinaryHeap, HashSet};
 
     use super::*;
 
@@ -74,12 +99,67 @@ mod test {
     fn one_edge() {
         let edge = Edge::new(12, 34, 5.67);
 
+        // equal weights are ordered by endpoints
         let edge2 = Edge::new(10, 11, 5.67);
-        assert_eq!(edge.partial_cmp(&edge2), Some(Ordering::Equal));
-        assert_eq!(edge, edge2);
+        assert_eq!(edge.partial_cmp(&edge2), Some(Ordering::Greater));
+        assert_ne!(edge, edge2);
+        assert_eq!(edge, Edge::new(34, 12, 5.67));
 
         let edge3 = Edge::new(10, 11, 8.0);
         assert_eq!(edge.partial_cmp(&edge3), Some(Ordering::Less));
         assert!(edge < edge3);
     }
+
+    #[test]
+    fn total_order() {
+        let mut edges = [
+            Edge::new(0, 1, 0.0),
+            Edge::new(0, 1, 2.0),
+            Edge::new(0, 1, -1.0),
+            Edge::new(0, 1, f64::INFINITY),
+            Edge::new(0, 1, -0.0),
+        ];
+        edges.sort_unstable();
+        let weights = edges.iter().map(|e| e.weight()).collect::<Vec<f64>>();
+        assert_eq!(weights, [-1.0, -0.0, 0.0, 2.0, f64::INFINITY]);
+        assert!(weights[1].is_sign_negative());
+    }
+
+    #[test]
+    #[should_panic(expected = "NaN")]
+    fn nan_weight() {
+        Edge::new(0, 1, f64::NAN);
+    }
+
+    #[test]
+    fn equal_weights_in_heap() {
+        let edges = [(3, 4), (0, 2), (2, 1), (1, 0), (4, 0)];
+        let pop_order = |edges: &mut dyn Iterator<Item = (usize, usize)>| {
+            let mut pq = edges
+                .map(|(v, w)| Reverse(Edge::new(v, w, 1.0)))
+                .collect::<BinaryHeap<_>>();
+            let mut order = vec![];
+            while let Some(Reverse(e)) = pq.pop() {
+                order.push(e.endpoints());
+            }
+            order
+        };
+        let expected = vec![(0, 1), (0, 2), (0, 4), (1, 2), (3, 4)];
+        assert_eq!(pop_order(&mut edges.into_iter()), expected);
+        assert_eq!(pop_order(&mut edges.into_iter().rev()), expected);
+    }
+
+    #[test]
+    fn hash() {
+        let set = [
+            Edge::new(0, 1, 0.5),
+            Edge::new(1, 0, 0.5),
+            Edge::new(0, 1, 0.25),
+            Edge::new(0, 2, 0.5),
+        ]
+        .into_iter()
+        .collect::<HashSet<Edge>>();
+        assert_eq!(set.len(), 3);
+        assert!(set.contains(&Edge::new(2, 0, 0.5)));
+    }
 }
@@ -0,0 +1,406 @@
+//! # Eulerian paths and circuits
+//!
+//! An Eulerian path uses every edge exactly once; an Eulerian circuit is one that ends
+//! where it starts. Isolated vertices are ignored, and a graph with no edges has neither.
+//!
+//! An undirected graph has a circuit iff the vertices with edges are connected and every
+//! degree is even, and a path iff there are also at most two vertices of odd degree.
+//! A digraph has a circuit iff the vertices with edges are strongly connected and every
+//! indegree equals the outdegree, and a path iff there is also at most one vertex with one
+//! more edge out than in, and one with one more in than out.
+//!
+//! This implementation uses Hierholzer's algorithm, which walks from the start until it is stuck,
+//! then splices in the walks from the vertices along the way that still have unused edges.
+//! The degrees decide where the walk starts; connectivity is checked by whether it used every edge.
+//! The time complexity is O(V + E).
+
+use super::{digraph::Digraph, graph::Graph, interop};
+
+pub struct EulerianCircuit {
+    path: Option<Vec<usize>>, // vertices on an Eulerian path
+    is_circuit: bool,         // does the path end where it starts?
+}
+
+impl EulerianCircuit {
+    /// Finds an Eulerian path or circuit in an undirected graph.
+    pub fn new(g: &Graph) -> Self {
+        // each edge once, as its two endpoints
+        let edges = interop::edges(g);
+        // incident[v] = the edges incident to v
+        let mut incident = vec![Vec::new(); g.v()];
+        for (i, &(v, w)) in edges.iter().enumerate() {
+            incident[v].push(i);
+            if v != w {
+                incident[w].push(i);
+            }
+        }
+
+        let odd: Vec<usize> = (0..g.v()).filter(|&v| g.degree(v) % 2 == 1).collect();
+        let start = match odd.len() {
+            0 => (0..g.v()).find(|&v| g.degree(v) > 0),
+            2 => Some(odd[0]),
+            _ => None,
+        };
+
+        let path = start.and_then(|s| {
+            let mut used = vec![false; edges.len()];
+            let mut next = vec![0; g.v()];
+            Self::hierholzer(s, edges.len(), |v| {
+                while let Some(&i) = incident[v].get(next[v]) {
+                    next[v] += 1;
+                    if !used[i] {
+                        used[i] = true;
+                        let (x, y) = edges[i];
+                        return Some(if x == v { y } else { x });
+                    }
+                }
+                None
+            })
+        });
+        EulerianCircuit {
+            path,
+            is_circuit: odd.is_empty(),
+        }
+    }
+
+    /// Finds an Eulerian path or circuit in a digraph.
+    pub fn from_digraph(g: &Digraph) -> Self {
+        let mut start = None;
+        let mut end = None;
+        let mut balanced = true;
+        for v in 0..g.v() {
+            let (out, into) = (g.out_degree(v), g.in_degree(v));
+            if out == into + 1 && start.is_none() {
+                start = Some(v);
+            } else if into == out + 1 && end.is_none() {
+                end = Some(v);
+            } else if out != into {
+                balanced = false;
+            }
+        }
+        let is_circuit = balanced && start.is_none() && end.is_none();
+        if start.is_none() && end.is_none() {
+            start = (0..g.v()).find(|&v| g.out_degree(v) > 0);
+        }
+
+        let path = start.filter(|_| balanced).and_then(|s| {
+            let mut next = vec![0; g.v()];
+            Self::hierholzer(s, g.e(), |v| {
+                let w = g.adj(v).get(next[v]).copied();
+                next[v] += 1;
+                w
+            })
+        });
+        EulerianCircuit { path, is_circuit }
+    }
+
+    // `next_edge(v)` takes an unused edge from v and returns its other end.
+    // Returns the path, or None if it did not use all `e` edges.
+    fn hierholzer(
+        s: usize,
+        e: usize,
+        mut next_edge: impl FnMut(usize) -> Option<usize>,
+    ) -> Option<Vec<usize>> {
+        // iterative, so that long paths do not overflow the call stack
+        let mut stack = vec![s];
+        let mut path = Vec::with_capacity(e + 1);
+        while let Some(&v) = stack.last() {
+            match next_edge(v) {
+                Some(w) => stack.push(w),
+                None => {
+                    stack.pop();
+                    path.push(v);
+                }
+            }
+        }
+        path.reverse();
+        if path.len() == e + 1 {
+            Some(path)
+        } else {
+            None
+        }
+    }
+
+    /// Does the graph have an Eulerian circuit?
+    pub fn has_circuit(&self) -> bool {
+        self.is_circuit && self.path.is_some()
+    }
+
+    /// Returns the vertices of an Eulerian circuit, the first and last being the same.
+    pub fn circuit(&self) -> Option<impl Iterator<Item = usize> + '_> {
+        if self.is_circuit {
+            self.path()
+        } else {
+            None
+        }
+    }
+
+    /// Does the graph have an Eulerian path? A circuit is one too.
+    pub fn has_path(&self) -> bool {
+        self.path.is_some()
+    }
+
+    /// Returns the vertices of an Eulerian path.
+    pub fn path(&self) -> Option<impl Iterator<Item = usize> + '_> {
+        self.path.as_ref().map(|path| path.iter().copied())
+    }
+}
+
+/// The book's `EulerianCycle`: an Eulerian circuit, if there is one.
+pub struct EulerianCycle(EulerianCircuit);
+
+impl EulerianCycle {
+    pub fn new(g: &Graph) -> Self {
+        EulerianCycle(EulerianCircuit::new(g))
+    }
+
+    pub fn from_digraph(g: &Digraph) -> Self {
+        EulerianCycle(EulerianCircuit::from_digraph(g))
+    }
+
+    pub fn has_eulerian_cycle(&self) -> bool {
+        self.0.has_circuit()
+    }
+
+    /// Returns the vertices of an Eulerian cycle, the first and last being the same.
+    pub fn cycle(&self) -> Option<impl Iterator<Item = usize> + '_> {
+        self.0.circuit()
+    }
+}
+
+/// The book's `EulerianPath`: an Eulerian path, which may be a circuit, if there is one.
+pub struct EulerianPath(EulerianCircuit);
+
+impl EulerianPath {
+    pub fn new(g: &Graph) -> Self {
+        EulerianPath(EulerianCircuit::new(g))
+    }
+
+    pub fn from_digraph(g: &Digraph) -> Self {
+        EulerianPath(EulerianCircuit::from_digraph(g))
+    }
+
+    pub fn has_eulerian_path(&self) -> bool {
+        self.0.has_path()
+    }
+
+    pub fn path(&self) -> Option<impl Iterator<Item = usize> + '_> {
+        self.0.path()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // counts[v][w] = number of times w appears in the adjacency list of v
+    fn counts<'a>(n: usize, adj: impl Fn(usize) -> &'a Vec<usize>) -> Vec<Vec<usize>> {
+        let mut counts = vec![vec![0; n]; n];
+        for (v, row) in counts.iter_mut().enumerate() {
+            for &w in adj(v) {
+                row[w] += 1;
+            }
+        }
+        counts
+    }
+
+    // each consecutive pair is an edge, and every edge is used once
+    fn assert_graph_path(g: &Graph, path: &[usize]) {
+        let mut counts = counts(g.v(), |v| g.adj(v));
+        for e in path.windows(2) {
+            let (v, w) = (e[0], e[1]);
+            assert!(counts[v][w] > 0, "{}-{} used too often", v, w);
+            // a self-loop is in the list twice, other edges once at each end
+            counts[v][w] -= 1;
+            counts[w][v] -= 1;
+        }
+        assert_eq!(path.len(), g.e() + 1);
+    }
+
+    fn assert_digraph_path(g: &Digraph, path: &[usize]) {
+        let mut counts = counts(g.v(), |v| g.adj(v));
+        for e in path.windows(2) {
+            assert!(counts[e[0]][e[1]] > 0, "{}->{} used too often", e[0], e[1]);
+            counts[e[0]][e[1]] -= 1;
+        }
+        assert_eq!(path.len(), g.e() + 1);
+    }
+
+    #[test]
+    fn konigsberg() {
+        // the four land masses A, B, C, D and the seven bridges
+        let mut g = Graph::new(4);
+        for (v, w) in [(0, 1), (0, 1), (0, 2), (0, 2), (0, 3), (1, 3), (2, 3)] {
+            g.add_edge(v, w);
+        }
+        let euler = EulerianCircuit::new(&g);
+        assert!(!euler.has_circuit());
+        assert!(!euler.has_path());
+        assert!(euler.circuit().is_none());
+        assert!(euler.path().is_none());
+
+        // an eighth bridge between B and C leaves two odd land masses, A and D
+        g.add_edge(1, 2);
+        let euler = EulerianCircuit::new(&g);
+        assert!(!euler.has_circuit());
+        let path = euler.path().unwrap().collect::<Vec<usize>>();
+        assert_graph_path(&g, &path);
+        assert_eq!((path[0], path[8]), (0, 3));
+
+        // and a ninth between A and D makes every degree even
+        g.add_edge(0, 3);
+        let euler = EulerianCircuit::new(&g);
+        assert!(euler.has_circuit());
+        let circuit = euler.circuit().unwrap().collect::<Vec<usize>>();
+        assert_graph_path(&g, &circuit);
+        assert_eq!(circuit.first(), circuit.last());
+    }
+
+    #[test]
+    fn undirected_special_cases() {
+        // a self-loop and an isolated vertex
+        let mut g = Graph::new(4);
+        g.add_edge(0, 1);
+        g.add_edge(1, 1);
+        g.add_edge(1, 2);
+        g.add_edge(2, 0);
+        let euler = EulerianCircuit::new(&g);
+        let circuit = euler.circuit().unwrap().collect::<Vec<usize>>();
+        assert_eq!(circuit.len(), 5);
+        assert_graph_path(&g, &circuit);
+
+        // two triangles apart: every degree is even, but no circuit
+        let mut g = Graph::new(6);
+        for (v, w) in [(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3)] {
+            g.add_edge(v, w);
+        }
+        assert!(!EulerianCircuit::new(&g).has_circuit());
+        assert!(!EulerianCircuit::new(&g).has_path());
+
+        assert!(!EulerianCircuit::new(&Graph::new(3)).has_path());
+    }
+
+    #[test]
+    fn directed_cycle() {
+        let mut g = Digraph::new(5);
+        for v in 0..5 {
+            g.add_edge(v, (v + 1) % 5);
+        }
+        let euler = EulerianCircuit::from_digraph(&g);
+        assert!(euler.has_circuit());
+        assert!(euler.has_path());
+        assert_eq!(
+            euler.circuit().unwrap().collect::<Vec<usize>>(),
+            vec![0, 1, 2, 3, 4, 0]
+        );
+
+        // without the edge 4->0, the path runs from 0 to 4
+        g.remove_edge(4, 0);
+        let euler = EulerianCircuit::from_digraph(&g);
+        assert!(!euler.has_circuit());
+        assert!(euler.circuit().is_none());
+        assert_eq!(
+            euler.path().unwrap().collect::<Vec<usize>>(),
+            vec![0, 1, 2, 3, 4]
+        );
+
+        // it has to start at 1 and end at 0
+        let g = Digraph::from_edges(3, &[(0, 1), (0, 2), (1, 0), (2, 0), (1, 0)]);
+        let path = EulerianCircuit::from_digraph(&g)
+            .path()
+            .unwrap()
+            .collect::<Vec<usize>>();
+        assert_digraph_path(&g, &path);
+        assert_eq!((path[0], path[5]), (1, 0));
+
+        // both 0->1 and 0->2 would have to come first
+        let g = Digraph::from_edges(3, &[(0, 1), (0, 2)]);
+        assert!(!EulerianCircuit::from_digraph(&g).has_path());
+    }
+
+    #[test]
+    fn directed_figure_eight() {
+        // two cycles through 0, with a side trip 2->3->2
+        let g = Digraph::from_edges(
+            6,
+            &[
+                (0, 1),
+                (1, 2),
+                (2, 0),
+                (0, 4),
+                (4, 5),
+                (5, 0),
+                (2, 3),
+                (3, 2),
+            ],
+        );
+        let euler = EulerianCircuit::from_digraph(&g);
+        assert!(euler.has_circuit());
+        let circuit = euler.circuit().unwrap().collect::<Vec<usize>>();
+        assert_digraph_path(&g, &circuit);
+        assert_eq!((circuit[0], circuit[8]), (0, 0));
+
+        // balanced, but not strongly connected
+        let g = Digraph::from_edges(4, &[(0, 1), (1, 0), (2, 3), (3, 2)]);
+        assert!(!EulerianCircuit::from_digraph(&g).has_path());
+    }
+
+    #[test]
+    fn cycle_and_path() {
+        // two triangles sharing vertex 2, with a doubled edge 0-1 and a self-loop
+        let mut g = Graph::from_edges(
+            5,
+            &[
+                (0, 1),
+                (1, 2),
+                (2, 0),
+                (2, 3),
+                (3, 4),
+                (4, 2),
+                (0, 1),
+                (3, 3),
+            ],
+        );
+        assert!(!EulerianCycle::new(&g).has_eulerian_cycle());
+        // 0 and 1 have odd degree
+        let euler = EulerianPath::new(&g);
+        assert!(euler.has_eulerian_path());
+        let path = euler.path().unwrap().collect::<Vec<usize>>();
+        assert_graph_path(&g, &path);
+        assert_eq!(path[0], 0);
+        assert_eq!(path[8], 1);
+
+        g.add_edge(0, 1);
+        let euler = EulerianCycle::new(&g);
+        assert!(euler.has_eulerian_cycle());
+        let cycle = euler.cycle().unwrap().collect::<Vec<usize>>();
+        assert_graph_path(&g, &cycle);
+        assert_eq!(cycle.first(), cycle.last());
+        assert!(EulerianPath::new(&g).has_eulerian_path());
+
+        // edges in two components
+        let g = Graph::from_edges(5, &[(0, 1), (1, 0), (2, 3), (3, 4), (4, 2)]);
+        assert!(!EulerianCycle::new(&g).has_eulerian_cycle());
+        assert!(EulerianCycle::new(&g).cycle().is_none());
+        assert!(!EulerianPath::new(&g).has_eulerian_path());
+
+        let g = Digraph::from_edges(3, &[(0, 1), (1, 2), (2, 0), (0, 2)]);
+        assert!(!EulerianCycle::from_digraph(&g).has_eulerian_cycle());
+        let path = EulerianPath::from_digraph(&g)
+            .path()
+            .unwrap()
+            .collect::<Vec<usize>>();
+        assert_digraph_path(&g, &path);
+    }
+
+    #[test]
+    fn long_cycle() {
+        let n = 500_000;
+        let mut g = Digraph::new(n);
+        for v in 0..n {
+            g.add_edge(v, (v + 1) % n);
+        }
+        let euler = EulerianCircuit::from_digraph(&g);
+        assert!(euler.circuit().unwrap().eq((0..n).chain(0..1)));
+    }
+}
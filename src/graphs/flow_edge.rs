@@ -0,0 +1,147 @@
+//! # A capacitated edge with a flow in a `FlowNetwork`.
+//!
+//! Each edge consists of two integers (naming the two vertices), a real-valued capacity,
+//! and a real-valued flow between 0 and the capacity.
+
+// flows within this of 0 or the capacity are rounded to them
+const FLOATING_POINT_EPSILON: f64 = 1e-10;
+
+#[derive(Debug, Clone, Copy)]
+pub struct FlowEdge {
+    v: usize, // from
+    w: usize, // to
+    capacity: f64,
+    flow: f64,
+}
+
+impl FlowEdge {
+    /// Initializes an edge from v to w with the given capacity and no flow.
+    pub fn new(v: usize, w: usize, capacity: f64) -> Self {
+        Self::with_flow(v, w, capacity, 0.0)
+    }
+
+    /// Initializes an edge from v to w with the given capacity and flow.
+    pub fn with_flow(v: usize, w: usize, capacity: f64, flow: f64) -> Self {
+        if capacity.is_nan() || capacity < 0.0 {
+            panic!("edge capacity must be non-negative");
+        }
+        if !(0.0..=capacity).contains(&flow) {
+            panic!("flow must be between 0 and the capacity");
+        }
+        FlowEdge {
+            v,
+            w,
+            capacity,
+            flow,
+        }
+    }
+
+    /// Returns the tail vertex of the edge.
+    pub fn from(&self) -> usize {
+        self.v
+    }
+
+    /// Returns the head vertex of the edge.
+    pub fn to(&self) -> usize {
+        self.w
+    }
+
+    pub fn capacity(&self) -> f64 {
+        self.capacity
+    }
+
+    pub fn flow(&self) -> f64 {
+        self.flow
+    }
+
+    /// Returns the endpoint of the edge that is different from the given vertex.
+    pub fn other(&self, vertex: usize) -> usize {
+        if vertex == self.v {
+            self.w
+        } else if vertex == self.w {
+            self.v
+        } else {
+            panic!("Illegal endpoint");
+        }
+    }
+
+    /// Returns the residual capacity of the edge in the direction to the given vertex:
+    /// the remaining capacity forward, or the flow that can be pushed back.
+    pub fn residual_capacity_to(&self, vertex: usize) -> f64 {
+        if vertex == self.v {
+            // backward edge
+            self.flow
+        } else if vertex == self.w {
+            // forward edge
+            self.capacity - self.flow
+        } else {
+            panic!("Illegal endpoint");
+        }
+    }
+
+    /// Increases the flow on the edge in the direction to the given vertex by `delta`.
+    pub fn add_residual_flow_to(&mut self, vertex: usize, delta: f64) {
+        if delta.is_nan() || delta < 0.0 {
+            panic!("delta must be non-negative");
+        }
+        if vertex == self.v {
+            self.flow -= delta;
+        } else if vertex == self.w {
+            self.flow += delta;
+        } else {
+            panic!("Illegal endpoint");
+        }
+
+        // round flow to 0 or capacity if within floating-point precision
+        if self.flow.abs() <= FLOATING_POINT_EPSILON {
+            self.flow = 0.0;
+        }
+        if (self.flow - self.capacity).abs() <= FLOATING_POINT_EPSILON {
+            self.flow = self.capacity;
+        }
+
+        if !(0.0..=self.capacity).contains(&self.flow) {
+            panic!("flow must be between 0 and the capacity");
+        }
+    }
+}
+
+impl std::fmt::Display for FlowEdge {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}->{} {}/{}", self.v, self.w, self.flow, self.capacity)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn residual() {
+        let mut e = FlowEdge::new(1, 2, 3.0);
+        assert_eq!(e.residual_capacity_to(2), 3.0);
+        assert_eq!(e.residual_capacity_to(1), 0.0);
+
+        e.add_residual_flow_to(2, 2.0);
+        assert_eq!(e.flow(), 2.0);
+        assert_eq!(e.residual_capacity_to(2), 1.0);
+        assert_eq!(e.residual_capacity_to(1), 2.0);
+
+        e.add_residual_flow_to(1, 0.5);
+        assert_eq!(e.flow(), 1.5);
+        assert_eq!(e.other(1), 2);
+        assert_eq!(e.to_string(), "1->2 1.5/3");
+    }
+
+    #[test]
+    #[should_panic(expected = "flow must be between 0 and the capacity")]
+    fn over_capacity() {
+        FlowEdge::new(0, 1, 1.0).add_residual_flow_to(1, 2.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity must be non-negative")]
+    fn nan_capacity() {
+        FlowEdge::new(0, 1, f64::NAN);
+    }
+}
@@ -0,0 +1,109 @@
+//! # A capacitated network of vertices named 0 to (v-1)
+//!
+//! It is implemented using adjacency lists. An edge v->w is on the lists of both v and w,
+//! so that it can be followed backwards in the residual network. The edges are stored once,
+//! and the lists hold their indices, so a change of flow is seen from either end.
+
+use super::flow_edge::FlowEdge;
+pub struct FlowNetwork {
+    v: usize,
+    edges: Vec<FlowEdge>, // edges[i] = edge with index i
+    adj: Vec<Vec<usize>>, // adj[v] = indices of the edges incident to v
+}
+
+impl FlowNetwork {
+    pub fn new(v: usize) -> Self {
+        FlowNetwork {
+            v,
+            edges: Vec::new(),
+            adj: vec![vec![]; v],
+        }
+    }
+
+    /// Returns the number of vertices in this flow network.
+    pub fn v(&self) -> usize {
+        self.v
+    }
+
+    /// Returns the number of edges in this flow network.
+    pub fn e(&self) -> usize {
+        self.edges.len()
+    }
+
+    fn validate_vertex(&self, v: usize) {
+        if v >= self.v {
+            panic!("vertex {} is not between 0 and {}", v, self.v);
+        }
+    }
+
+    /// Adds the edge to this flow network.
+    pub fn add_edge(&mut self, e: FlowEdge) {
+        let (v, w) = (e.from(), e.to());
+        self.validate_vertex(v);
+        self.validate_vertex(w);
+        self.adj[v].push(self.edges.len());
+        self.adj[w].push(self.edges.len());
+        self.edges.push(e);
+    }
+
+    /// Returns the edges incident on vertex v, pointing from or to it.
+    pub fn adj(&self, v: usize) -> impl Iterator<Item = &FlowEdge> {
+        self.validate_vertex(v);
+        self.adj[v].iter().map(move |&i| &self.edges[i])
+    }
+
+    /// Returns the indices of the edges incident on vertex v.
+    pub(crate) fn adj_indices(&self, v: usize) -> &[usize] {
+        &self.adj[v]
+    }
+
+    pub(crate) fn edge(&self, i: usize) -> &FlowEdge {
+        &self.edges[i]
+    }
+
+    pub(crate) fn edge_mut(&mut self, i: usize) -> &mut FlowEdge {
+        &mut self.edges[i]
+    }
+
+    /// Returns all edges in this flow network, in the order they were added.
+    pub fn edges(&self) -> impl Iterator<Item = &FlowEdge> {
+        self.edges.iter()
+    }
+}
+
+impl std::fmt::Display for FlowNetwork {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{} {}", self.v, self.e())?;
+        for v in 0..self.v {
+            write!(f, "{}:", v)?;
+            for e in self.adj(v).filter(|e| e.from() == v) {
+                write!(f, "  {}", e)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn adj() {
+        let mut g = FlowNetwork::new(3);
+        g.add_edge(FlowEdge::new(0, 1, 2.0));
+        g.add_edge(FlowEdge::new(1, 2, 1.0));
+        assert_eq!(g.e(), 2);
+        assert_eq!(
+            g.adj(1).map(|e| e.other(1)).collect::<Vec<usize>>(),
+            vec![0, 2]
+        );
+
+        // a change of flow shows on both lists
+        let i = g.adj_indices(1)[1];
+        g.edge_mut(i).add_residual_flow_to(2, 1.0);
+        assert_eq!(g.adj(2).next().unwrap().flow(), 1.0);
+        assert_eq!(g.to_string(), "3 2\n0:  0->1 0/2\n1:  1->2 1/1\n2:\n");
+    }
+}
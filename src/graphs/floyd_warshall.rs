@@ -0,0 +1,311 @@
+//! # All-pairs shortest paths in an edge-weighted digraph
+//!
+//! This implementation uses the Floyd-Warshall algorithm: after round i, `dist_to[v][w]` is the
+//! length of a shortest v->w path whose intermediate vertices are all less than i + 1.
+//! Edge weights may be negative; a negative cycle shows up as `dist_to[v][v] < 0`.
+//! The time complexity is O(V^3) and the space is O(V^2), whatever the number of edges,
+//! so it suits dense digraphs.
+
+use super::{directed_edge::DirectedEdge, weighted_digraph::EdgeWeightedDiagraph};
+pub struct FloydWarshall {
+    dist_to: Vec<Vec<f64>>,                  // length of shortest v->w path
+    edge_to: Vec<Vec<Option<DirectedEdge>>>, // last edge on shortest v->w path
+    has_negative_cycle: bool,                // is there a negative cycle?
+}
+
+impl FloydWarshall {
+    pub fn new(g: &EdgeWeightedDiagraph) -> Self {
+        let n = g.v();
+        let mut fw = FloydWarshall {
+            dist_to: vec![vec![f64::INFINITY; n]; n],
+            edge_to: vec![vec![None; n]; n],
+            has_negative_cycle: false,
+        };
+
+        // initialize distances using the lightest edge between each pair
+        for e in g.edges() {
+            let (v, w) = (e.from(), e.to());
+            if e.weight() < fw.dist_to[v][w] {
+                fw.dist_to[v][w] = e.weight();
+                fw.edge_to[v][w] = Some(e);
+            }
+        }
+        // in case of self-loops
+        for v in 0..n {
+            if fw.dist_to[v][v] >= 0.0 {
+                fw.dist_to[v][v] = 0.0;
+                fw.edge_to[v][v] = None;
+            }
+        }
+
+        for i in 0..n {
+            // compute shortest paths using only 0, 1, ..., i as intermediate vertices
+            for v in 0..n {
+                if fw.dist_to[v][i] == f64::INFINITY {
+                    // no v->i path yet, so no improvement through i
+                    continue;
+                }
+                for w in 0..n {
+                    let through = fw.dist_to[v][i] + fw.dist_to[i][w];
+                    if through < fw.dist_to[v][w] {
+                        fw.dist_to[v][w] = through;
+                        fw.edge_to[v][w] = fw.edge_to[i][w];
+                    }
+                }
+                // check for negative cycle
+                if fw.dist_to[v][v] < 0.0 {
+                    fw.has_negative_cycle = true;
+                    return fw;
+                }
+            }
+        }
+        fw
+    }
+
+    /// Is there a negative cycle?
+    pub fn has_negative_cycle(&self) -> bool {
+        self.has_negative_cycle
+    }
+
+    /// Is there a path from s to t?
+    pub fn has_path(&self, s: usize, t: usize) -> bool {
+        self.validate_vertex(s);
+        self.validate_vertex(t);
+        self.dist_to[s][t] < f64::INFINITY
+    }
+
+    /// Returns the length of a shortest path from s to t, or infinity if there is none.
+    /// Panics if there is a negative cycle.
+    pub fn dist(&self, s: usize, t: usize) -> f64 {
+        self.validate_vertex(s);
+        self.validate_vertex(t);
+        self.validate_no_negative_cycle();
+        self.dist_to[s][t]
+    }
+
+    /// Returns the edges of a shortest path from s to t, empty if there is none or s == t.
+    /// Panics if there is a negative cycle.
+    pub fn path(&self, s: usize, t: usize) -> Vec<DirectedEdge> {
+        self.validate_vertex(s);
+        self.validate_vertex(t);
+        self.validate_no_negative_cycle();
+        let mut path = Vec::new();
+        let mut edge = self.edge_to[s][t];
+        while let Some(e) = edge {
+            path.push(e);
+            edge = self.edge_to[s][e.from()];
+        }
+        path.reverse();
+        path
+    }
+
+    /// Returns the vertices of a shortest path from s to t, starting with s and ending with t,
+    /// or nothing if there is no path. Panics if there is a negative cycle.
+    pub fn vertices(&self, s: usize, t: usize) -> impl Iterator<Item = usize> {
+        let path = self.path(s, t);
+        let start = self.has_path(s, t).then_some(s);
+        start.into_iter().chain(path.into_iter().map(|e| e.to()))
+    }
+
+    fn validate_vertex(&self, v: usize) {
+        if v >= self.dist_to.len() {
+            panic!("vertex {} is not between 0 and {}", v, self.dist_to.len());
+        }
+    }
+
+    fn validate_no_negative_cycle(&self) {
+        if self.has_negative_cycle {
+            panic!("negative cost cycle exists");
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::graphs::bellman_ford_sp::BellmanFordSP;
+    use crate::graphs::dijkstra_sp::DijkstraSP;
+
+    fn tiny_ewd(edges: &[(usize, usize, f64)]) -> EdgeWeightedDiagraph {
+        let mut g = EdgeWeightedDiagraph::new(8);
+        for &(v, w, weight) in edges {
+            g.add_edge(DirectedEdge::new(v, w, weight));
+        }
+        g
+    }
+
+    fn assert_path(fw: &FloydWarshall, s: usize, t: usize) {
+        let path = fw.path(s, t);
+        let mut at = s;
+        for e in &path {
+            assert_eq!(e.from(), at);
+            at = e.to();
+        }
+        assert_eq!(at, t);
+        let length = path.iter().map(|e| e.weight()).sum::<f64>();
+        assert!((length - fw.dist(s, t)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn tiny_ewd_positive() {
+        let g = tiny_ewd(&[
+            (4, 5, 0.35),
+            (5, 4, 0.35),
+            (4, 7, 0.37),
+            (5, 7, 0.28),
+            (7, 5, 0.28),
+            (5, 1, 0.32),
+            (0, 4, 0.38),
+            (0, 2, 0.26),
+            (7, 3, 0.39),
+            (1, 3, 0.29),
+            (2, 7, 0.34),
+            (6, 2, 0.40),
+            (3, 6, 0.52),
+            (6, 0, 0.58),
+            (6, 4, 0.93),
+        ]);
+        let fw = FloydWarshall::new(&g);
+        assert!(!fw.has_negative_cycle());
+        for s in 0..g.v() {
+            let sp = DijkstraSP::new(&g, s);
+            for t in 0..g.v() {
+                assert!(fw.has_path(s, t));
+                assert!((fw.dist(s, t) - sp.dist_to(t)).abs() < 1e-9);
+                assert_path(&fw, s, t);
+            }
+        }
+        assert!((fw.dist(0, 6) - 1.51).abs() < 1e-9);
+        assert_eq!(
+            fw.path(0, 6).iter().map(|e| e.to()).collect::<Vec<usize>>(),
+            vec![2, 7, 3, 6]
+        );
+        assert!(fw.path(3, 3).is_empty());
+    }
+
+    #[test]
+    fn tiny_ewg() {
+        // each undirected edge of tinyEWG in both directions
+        let edges = [
+            (4, 5, 0.35),
+            (4, 7, 0.37),
+            (5, 7, 0.28),
+            (0, 7, 0.16),
+            (1, 5, 0.32),
+            (0, 4, 0.38),
+            (2, 3, 0.17),
+            (1, 7, 0.19),
+            (0, 2, 0.26),
+            (1, 2, 0.36),
+            (1, 3, 0.29),
+            (2, 7, 0.34),
+            (6, 2, 0.40),
+            (3, 6, 0.52),
+            (6, 0, 0.58),
+            (6, 4, 0.93),
+        ];
+        let mut g = EdgeWeightedDiagraph::new(8);
+        for &(v, w, weight) in &edges {
+            g.add_edge(DirectedEdge::new(v, w, weight));
+            g.add_edge(DirectedEdge::new(w, v, weight));
+        }
+        let fw = FloydWarshall::new(&g);
+        for s in 0..g.v() {
+            let sp = DijkstraSP::new(&g, s);
+            for t in 0..g.v() {
+                assert!((fw.dist(s, t) - sp.dist_to(t)).abs() < 1e-9);
+                // symmetric weights give symmetric distances
+                assert!((fw.dist(s, t) - fw.dist(t, s)).abs() < 1e-9);
+                let vertices = fw.vertices(s, t).collect::<Vec<usize>>();
+                assert_eq!(vertices.first(), Some(&s));
+                assert_eq!(vertices.last(), Some(&t));
+                assert_eq!(vertices.len(), fw.path(s, t).len() + 1);
+            }
+        }
+        assert_eq!(fw.vertices(0, 3).collect::<Vec<usize>>(), vec![0, 2, 3]);
+        assert_eq!(fw.vertices(5, 5).collect::<Vec<usize>>(), vec![5]);
+    }
+
+    #[test]
+    fn negative_weights() {
+        // tinyEWDn
+        let g = tiny_ewd(&[
+            (4, 5, 0.35),
+            (5, 4, 0.35),
+            (4, 7, 0.37),
+            (5, 7, 0.28),
+            (7, 5, 0.28),
+            (5, 1, 0.32),
+            (0, 4, 0.38),
+            (0, 2, 0.26),
+            (7, 3, 0.39),
+            (1, 3, 0.29),
+            (2, 7, 0.34),
+            (6, 2, -1.20),
+            (3, 6, 0.52),
+            (6, 0, -1.40),
+            (6, 4, -1.25),
+        ]);
+        let fw = FloydWarshall::new(&g);
+        assert!(!fw.has_negative_cycle());
+        for s in 0..g.v() {
+            let sp = BellmanFordSP::new(&g, s);
+            for t in 0..g.v() {
+                assert!((fw.dist(s, t) - sp.dist_to(t)).abs() < 1e-9);
+                assert_path(&fw, s, t);
+            }
+        }
+    }
+
+    #[test]
+    fn negative_cycle() {
+        // tinyEWDnc: 5->4->7->5 has length -0.66 + 0.37 + 0.28 < 0
+        let g = tiny_ewd(&[
+            (4, 5, 0.35),
+            (5, 4, -0.66),
+            (4, 7, 0.37),
+            (5, 7, 0.28),
+            (7, 5, 0.28),
+            (5, 1, 0.32),
+            (0, 4, 0.38),
+            (0, 2, 0.26),
+            (7, 3, 0.39),
+            (1, 3, 0.29),
+            (2, 7, 0.34),
+            (6, 2, 0.40),
+            (3, 6, 0.52),
+            (6, 0, 0.58),
+            (6, 4, 0.93),
+        ]);
+        assert!(FloydWarshall::new(&g).has_negative_cycle());
+
+        let mut g = EdgeWeightedDiagraph::new(2);
+        g.add_edge(DirectedEdge::new(1, 1, -1.0));
+        assert!(FloydWarshall::new(&g).has_negative_cycle());
+    }
+
+    #[test]
+    fn unreachable() {
+        let mut g = EdgeWeightedDiagraph::new(3);
+        g.add_edge(DirectedEdge::new(0, 1, 2.0));
+        g.add_edge(DirectedEdge::new(0, 1, 1.0));
+        g.add_edge(DirectedEdge::new(2, 2, 5.0));
+        let fw = FloydWarshall::new(&g);
+        assert!(!fw.has_path(1, 0));
+        assert_eq!(fw.dist(1, 0), f64::INFINITY);
+        assert!(fw.path(1, 0).is_empty());
+        assert_eq!(fw.vertices(1, 0).count(), 0);
+        // the lighter parallel edge, and no self-loop
+        assert_eq!(fw.dist(0, 1), 1.0);
+        assert_eq!(fw.dist(2, 2), 0.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "negative cost cycle")]
+    fn dist_with_negative_cycle() {
+        let mut g = EdgeWeightedDiagraph::new(2);
+        g.add_edge(DirectedEdge::new(0, 1, -1.0));
+        g.add_edge(DirectedEdge::new(1, 0, -1.0));
+        FloydWarshall::new(&g).dist(0, 1);
+    }
+}
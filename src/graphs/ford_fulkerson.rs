@@ -0,0 +1,191 @@
+//! # Maximum flow and minimum cut in a flow network
+//!
+//! This implementation uses the Ford-Fulkerson algorithm with the shortest augmenting path
+//! heuristic (Edmonds-Karp): breadth-first search finds an s->t path with the fewest edges in
+//! the residual network, and the flow along it is increased by its bottleneck capacity,
+//! until no such path is left. The vertices still reachable from s then form a minimum cut.
+//! It takes at most O(V E) augmentations, so the time complexity is O(V E^2).
+
+use super::flow_network::FlowNetwork;
+use crate::fundamentals::queue::Queue;
+pub struct FordFulkerson {
+    marked: Vec<bool>,           // marked[v] = true iff s->v path in residual network
+    edge_to: Vec<Option<usize>>, // edge_to[v] = index of last edge on shortest residual s->v path
+    value: f64,                  // current value of max flow
+}
+
+impl FordFulkerson {
+    /// Computes a maximum flow from s to t in `g`, recording the flow on its edges.
+    /// Any flow already on the edges is the starting point.
+    pub fn new(g: &mut FlowNetwork, s: usize, t: usize) -> Self {
+        if s >= g.v() || t >= g.v() {
+            panic!("vertex is not between 0 and {}", g.v());
+        }
+        if s == t {
+            panic!("source equals sink");
+        }
+        let mut ff = FordFulkerson {
+            marked: vec![false; g.v()],
+            edge_to: vec![None; g.v()],
+            value: Self::excess(g, t),
+        };
+
+        while ff.has_augmenting_path(g, s, t) {
+            // compute bottleneck capacity
+            let mut bottle = f64::INFINITY;
+            let mut v = t;
+            while let Some(i) = ff.edge_to[v] {
+                let e = g.edge(i);
+                bottle = bottle.min(e.residual_capacity_to(v));
+                v = e.other(v);
+            }
+
+            // augment flow
+            let mut v = t;
+            while let Some(i) = ff.edge_to[v] {
+                let e = g.edge_mut(i);
+                e.add_residual_flow_to(v, bottle);
+                v = e.other(v);
+            }
+
+            ff.value += bottle;
+        }
+        ff
+    }
+
+    // is there an augmenting path? If so, following edge_to back from t gives it.
+    // it leaves marked[v] true iff v is reachable from s in the residual network.
+    fn has_augmenting_path(&mut self, g: &FlowNetwork, s: usize, t: usize) -> bool {
+        self.marked.iter_mut().for_each(|m| *m = false);
+        self.edge_to.iter_mut().for_each(|e| *e = None);
+
+        // breadth-first search
+        let mut queue = Queue::new();
+        queue.enqueue(s);
+        self.marked[s] = true;
+        while let Some(v) = queue.dequeue() {
+            if self.marked[t] {
+                break;
+            }
+            for &i in g.adj_indices(v) {
+                let e = g.edge(i);
+                let w = e.other(v);
+                // if residual capacity from v to w
+                if e.residual_capacity_to(w) > 0.0 && !self.marked[w] {
+                    self.edge_to[w] = Some(i);
+                    self.marked[w] = true;
+                    queue.enqueue(w);
+                }
+            }
+        }
+        self.marked[t]
+    }
+
+    // net flow into v
+    fn excess(g: &FlowNetwork, v: usize) -> f64 {
+        g.adj(v)
+            .map(|e| if v == e.from() { -e.flow() } else { e.flow() })
+            .sum()
+    }
+
+    /// Returns the value of the maximum flow.
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    /// Is v on the s side of the minimum cut?
+    pub fn in_cut(&self, v: usize) -> bool {
+        if v >= self.marked.len() {
+            panic!("vertex {} is not between 0 and {}", v, self.marked.len());
+        }
+        self.marked[v]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    use super::*;
+    use crate::graphs::flow_edge::FlowEdge;
+
+    fn network(v: usize, edges: &[(usize, usize, f64)]) -> FlowNetwork {
+        let mut g = FlowNetwork::new(v);
+        for &(v, w, capacity) in edges {
+            g.add_edge(FlowEdge::new(v, w, capacity));
+        }
+        g
+    }
+
+    // checks the capacity and conservation constraints, and that the cut has the flow's value
+    fn assert_optimal(g: &FlowNetwork, ff: &FordFulkerson, s: usize, t: usize) {
+        for v in 0..g.v() {
+            let excess = FordFulkerson::excess(g, v);
+            if v == s {
+                assert!((excess + ff.value()).abs() < 1e-9);
+            } else if v == t {
+                assert!((excess - ff.value()).abs() < 1e-9);
+            } else {
+                assert!(excess.abs() < 1e-9);
+            }
+        }
+        assert!(ff.in_cut(s));
+        assert!(!ff.in_cut(t));
+        let cut = g
+            .edges()
+            .filter(|e| ff.in_cut(e.from()) && !ff.in_cut(e.to()))
+            .map(|e| e.capacity())
+            .sum::<f64>();
+        assert!((cut - ff.value()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn tiny_fn() {
+        let mut g = network(
+            6,
+            &[
+                (0, 1, 2.0),
+                (0, 2, 3.0),
+                (1, 3, 3.0),
+                (1, 4, 1.0),
+                (2, 3, 1.0),
+                (2, 4, 1.0),
+                (3, 5, 2.0),
+                (4, 5, 3.0),
+            ],
+        );
+        let ff = FordFulkerson::new(&mut g, 0, 5);
+        assert_eq!(ff.value(), 4.0);
+        assert_eq!(
+            (0..6).filter(|&v| ff.in_cut(v)).collect::<Vec<usize>>(),
+            vec![0, 2]
+        );
+        assert_optimal(&g, &ff, 0, 5);
+    }
+
+    #[test]
+    fn no_path() {
+        let mut g = network(3, &[(0, 1, 5.0), (2, 1, 5.0)]);
+        let ff = FordFulkerson::new(&mut g, 0, 2);
+        assert_eq!(ff.value(), 0.0);
+        assert!(ff.in_cut(1));
+    }
+
+    #[test]
+    fn random() {
+        let mut rng = StdRng::seed_from_u64(63);
+        for _ in 0..20 {
+            let n = 10;
+            let edges = (0..30)
+                .map(|_| {
+                    let capacity = rng.gen_range(0..20) as f64;
+                    (rng.gen_range(0..n), rng.gen_range(0..n), capacity)
+                })
+                .collect::<Vec<(usize, usize, f64)>>();
+            let mut g = network(n, &edges);
+            let ff = FordFulkerson::new(&mut g, 0, n - 1);
+            assert_optimal(&g, &ff, 0, n - 1);
+        }
+    }
+}
@@ -0,0 +1,253 @@
+//! # Cycle structure of a functional graph
+//!
+//! In a functional graph every vertex has exactly one outgoing edge, given by a successor array.
+//! Following successors from any vertex traces a *rho* shape: a tail that runs into a cycle.
+//!
+//! The rho shape of every vertex is computed in O(V) by walking paths and marking the vertices
+//! on the current walk, memoizing results so each vertex is walked only once. Floyd's and Brent's
+//! algorithms find the same cycle without extra marks, but would re-walk shared tails.
+//!
+//! `kth_successor` uses binary lifting: `up[i][v]` is the `2^i`-th successor of `v`.
+//! Once past its tail a walk repeats with the period of the cycle, so any step count reduces
+//! to fewer than V steps, and the table needs only about log V levels.
+
+pub struct FunctionalGraph {
+    succ: Vec<usize>,
+    tail: Vec<usize>,      // tail[v] = steps from v to the first vertex on a cycle
+    cycle_len: Vec<usize>, // cycle_len[v] = length of the cycle v runs into
+    entry: Vec<usize>,     // entry[v] = first vertex on a cycle reached from v
+    up: Vec<Vec<usize>>,   // binary lifting table
+}
+
+impl FunctionalGraph {
+    pub fn new(succ: &[usize]) -> Self {
+        let n = succ.len();
+        for (v, &w) in succ.iter().enumerate() {
+            if w >= n {
+                panic!("successor {} of vertex {} is not between 0 and {}", w, v, n);
+            }
+        }
+
+        let mut fg = FunctionalGraph {
+            succ: succ.to_vec(),
+            tail: vec![0; n],
+            cycle_len: vec![0; n],
+            entry: vec![0; n],
+            up: Vec::new(),
+        };
+        fg.rho();
+        fg.lifting();
+        fg
+    }
+
+    fn rho(&mut self) {
+        let n = self.succ.len();
+        // 0 = unvisited, 1 = on the current walk, 2 = done
+        let mut state = vec![0u8; n];
+        let mut path = Vec::new();
+        for s in 0..n {
+            if state[s] != 0 {
+                continue;
+            }
+            let mut x = s;
+            while state[x] == 0 {
+                state[x] = 1;
+                path.push(x);
+                x = self.succ[x];
+            }
+            if state[x] == 1 {
+                // found a new cycle: x and everything after it on the walk
+                let start = path.iter().position(|&y| y == x).unwrap();
+                let len = path.len() - start;
+                for &y in &path[start..] {
+                    self.tail[y] = 0;
+                    self.cycle_len[y] = len;
+                    self.entry[y] = y;
+                    state[y] = 2;
+                }
+                path.truncate(start);
+            }
+            // the rest of the walk is a tail into memoized vertices
+            while let Some(y) = path.pop() {
+                let next = self.succ[y];
+                self.tail[y] = self.tail[next] + 1;
+                self.cycle_len[y] = self.cycle_len[next];
+                self.entry[y] = self.entry[next];
+                state[y] = 2;
+            }
+        }
+    }
+
+    fn lifting(&mut self) {
+        // enough levels for any step count below V
+        let levels = (usize::BITS - self.succ.len().leading_zeros()) as usize;
+        if levels == 0 {
+            return;
+        }
+        self.up.push(self.succ.clone());
+        for i in 1..levels {
+            let prev = &self.up[i - 1];
+            let next = prev.iter().map(|&v| prev[v]).collect();
+            self.up.push(next);
+        }
+    }
+
+    fn validate_vertex(&self, v: usize) {
+        if v >= self.succ.len() {
+            panic!("vertex {} is not between 0 and {}", v, self.succ.len());
+        }
+    }
+
+    /// Returns the number of vertices.
+    pub fn v(&self) -> usize {
+        self.succ.len()
+    }
+
+    /// Returns the successor of vertex `v`.
+    pub fn successor(&self, v: usize) -> usize {
+        self.validate_vertex(v);
+        self.succ[v]
+    }
+
+    /// Returns the number of steps from `v` to the first vertex on a cycle.
+    pub fn tail_length(&self, v: usize) -> usize {
+        self.validate_vertex(v);
+        self.tail[v]
+    }
+
+    /// Returns the length of the cycle that `v` eventually runs into.
+    pub fn cycle_length(&self, v: usize) -> usize {
+        self.validate_vertex(v);
+        self.cycle_len[v]
+    }
+
+    /// Returns the first vertex on a cycle reached from `v`.
+    pub fn cycle_entry(&self, v: usize) -> usize {
+        self.validate_vertex(v);
+        self.entry[v]
+    }
+
+    /// Is `v` on a cycle?
+    pub fn on_cycle(&self, v: usize) -> bool {
+        self.tail_length(v) == 0
+    }
+
+    /// Returns the vertex reached from `v` after `k` steps, in O(log k) time.
+    pub fn kth_successor(&self, v: usize, k: u64) -> usize {
+        self.validate_vertex(v);
+        let tail = self.tail[v] as u64;
+        let k = if k <= tail {
+            k
+        } else {
+            tail + (k - tail) % self.cycle_len[v] as u64
+        };
+        let mut x = v;
+        for (i, level) in self.up.iter().enumerate() {
+            if k >> i & 1 == 1 {
+                x = level[x];
+            }
+        }
+        x
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    use super::*;
+
+    // k steps using the rho shape instead of the lifting table
+    fn reference(fg: &FunctionalGraph, v: usize, k: u64) -> usize {
+        let tail = fg.tail_length(v) as u64;
+        let steps = if k <= tail {
+            k
+        } else {
+            tail + (k - tail) % fg.cycle_length(v) as u64
+        };
+        let mut x = v;
+        for _ in 0..steps {
+            x = fg.successor(x);
+        }
+        x
+    }
+
+    #[test]
+    fn rho() {
+        // 0 -> 1 -> 2 -> 3 -> 4 -> 2, and 5 -> 1, 6 -> 6
+        let fg = FunctionalGraph::new(&[1, 2, 3, 4, 2, 1, 6]);
+
+        assert_eq!(fg.tail_length(0), 2);
+        assert_eq!(fg.tail_length(5), 2);
+        assert_eq!(fg.tail_length(1), 1);
+        assert_eq!(fg.tail_length(3), 0);
+        assert_eq!(fg.cycle_length(0), 3);
+        assert_eq!(fg.cycle_length(4), 3);
+        assert_eq!(fg.cycle_entry(0), 2);
+        assert_eq!(fg.cycle_entry(5), 2);
+        assert_eq!(fg.cycle_entry(3), 3);
+        assert!(!fg.on_cycle(1));
+
+        // self-loop
+        assert_eq!(fg.tail_length(6), 0);
+        assert_eq!(fg.cycle_length(6), 1);
+        assert_eq!(fg.cycle_entry(6), 6);
+        assert_eq!(fg.kth_successor(6, 1_000_000_000_000), 6);
+    }
+
+    #[test]
+    fn permutation() {
+        // cycles (0 3 5), (1 2), (4)
+        let fg = FunctionalGraph::new(&[3, 2, 1, 5, 4, 0]);
+        for v in 0..fg.v() {
+            assert!(fg.on_cycle(v));
+            assert_eq!(fg.cycle_entry(v), v);
+        }
+        assert_eq!(fg.cycle_length(0), 3);
+        assert_eq!(fg.cycle_length(2), 2);
+        assert_eq!(fg.cycle_length(4), 1);
+        assert_eq!(fg.kth_successor(0, 2), 5);
+        assert_eq!(fg.kth_successor(0, 3), 0);
+        assert_eq!(fg.kth_successor(0, u64::MAX), 0);
+    }
+
+    #[test]
+    fn kth_successor() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let n = 200;
+        let succ: Vec<usize> = (0..n).map(|_| rng.gen_range(0..n)).collect();
+        let fg = FunctionalGraph::new(&succ);
+        // 200 < 2^8
+        assert_eq!(fg.up.len(), 8);
+
+        // small k against plain stepping
+        for v in 0..n {
+            let mut x = v;
+            for k in 0..3 * n as u64 {
+                assert_eq!(fg.kth_successor(v, k), x);
+                x = succ[x];
+            }
+        }
+        // large k against the rho shape
+        for _ in 0..1000 {
+            let v = rng.gen_range(0..n);
+            let k = rng.gen_range(0..=1_000_000_000_000u64);
+            assert_eq!(fg.kth_successor(v, k), reference(&fg, v, k));
+        }
+    }
+
+    #[test]
+    fn tiny() {
+        let fg = FunctionalGraph::new(&[0]);
+        assert_eq!(fg.kth_successor(0, u64::MAX), 0);
+        let empty = FunctionalGraph::new(&[]);
+        assert_eq!(empty.v(), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn out_of_range() {
+        FunctionalGraph::new(&[1, 2, 3]);
+    }
+}
@@ -3,6 +3,13 @@
 //! This implementation uses an `adjacency-lists` representation.
 
 use std::fmt;
+use std::io::BufRead;
+
+use crate::fundamentals::quick_union_uf::UF;
+
+use super::graph_reader::{self, Tokens};
+use super::interop;
+use super::simple_graph::SimpleGraph;
 pub struct Graph {
     v: usize,
     e: usize,
@@ -28,10 +35,40 @@ impl Graph {
 
     fn validate_vertex(&self, vx: usize) {
         if vx >= self.v {
-            panic!("vertex cannot >=, {}", self.v);
+            panic!("vertex {} is not between 0 and {}", vx, self.v);
         }
     }
 
+    /// Reads a graph in the format `V`, `E`, then `E` pairs of vertices.
+    pub fn from_reader(r: impl BufRead) -> graph_reader::Result<Graph> {
+        let mut tokens = Tokens::read(r)?;
+        let (v, e) = tokens.counts()?;
+        let mut graph = Graph::new(v);
+        for _ in 0..e {
+            let i = tokens.vertex()?;
+            let j = tokens.vertex()?;
+            graph.add_edge(i, j);
+        }
+        Ok(graph)
+    }
+
+    /// Initializes a graph with v vertices and the undirected edges `i-j`, added in order.
+    /// Panics if an endpoint is not less than v.
+    pub fn from_edges(v: usize, edges: &[(usize, usize)]) -> Graph {
+        let mut graph = Graph::new(v);
+        for &(i, j) in edges {
+            graph.add_edge(i, j);
+        }
+        graph
+    }
+
+    /// Adds a new isolated vertex and returns its index.
+    pub fn add_vertex(&mut self) -> usize {
+        self.adj.push(Vec::new());
+        self.v += 1;
+        self.v - 1
+    }
+
     /// Adds the undirected edge i-j to this graph
     pub fn add_edge(&mut self, i: usize, j: usize) {
         self.validate_vertex(i);
@@ -46,21 +83,133 @@ impl Graph {
         &self.adj[i]
     }
 
-    /// Returns the degree of vertex `i`
+    /// Is there an edge i-j? It scans the shorter of the two adjacency lists.
+    pub fn has_edge(&self, i: usize, j: usize) -> bool {
+        self.validate_vertex(i);
+        self.validate_vertex(j);
+        if self.adj[i].len() <= self.adj[j].len() {
+            self.adj[i].contains(&j)
+        } else {
+            self.adj[j].contains(&i)
+        }
+    }
+
+    /// Returns the degree of vertex `i`; a self-loop counts 2.
     pub fn degree(&self, i: usize) -> usize {
+        self.validate_vertex(i);
         self.adj[i].len()
     }
+
+    /// Returns the largest degree, or 0 if there are no vertices.
+    pub fn max_degree(&self) -> usize {
+        self.adj.iter().map(|adj| adj.len()).max().unwrap_or(0)
+    }
+
+    /// Returns the average degree, 2E / V, or 0 if there are no vertices.
+    pub fn average_degree(&self) -> f64 {
+        if self.v == 0 {
+            return 0.0;
+        }
+        2.0 * self.e as f64 / self.v as f64
+    }
+
+    /// Returns the number of self-loops.
+    pub fn number_of_self_loops(&self) -> usize {
+        let ends = self
+            .adj
+            .iter()
+            .enumerate()
+            .map(|(i, adj)| adj.iter().filter(|&&j| j == i).count())
+            .sum::<usize>();
+        // each self-loop appears twice in its adjacency list
+        ends / 2
+    }
+
+    /// Does the graph have no self-loops and no parallel edges?
+    pub fn is_simple(&self) -> bool {
+        // seen[j] = i + 1 iff j has already appeared in adj[i]
+        let mut seen = vec![0; self.v];
+        for (i, adj) in self.adj.iter().enumerate() {
+            for &j in adj {
+                if j == i || seen[j] == i + 1 {
+                    return false;
+                }
+                seen[j] = i + 1;
+            }
+        }
+        true
+    }
+
+    /// Returns a copy without self-loops and parallel edges, and the number of edges dropped.
+    pub fn to_simple(&self) -> (SimpleGraph, usize) {
+        let mut simple = SimpleGraph::new(self.v);
+        for (i, adj) in self.adj.iter().enumerate() {
+            for &j in adj {
+                if i < j {
+                    // a parallel edge is rejected, and left out
+                    let _ = simple.try_add_edge(i, j);
+                }
+            }
+        }
+        let dropped = self.e - simple.e();
+        (simple, dropped)
+    }
+
+    /// Is every vertex reachable from every other one? A graph with no vertices is connected.
+    /// It runs one depth-first search from vertex 0, in O(V + E).
+    pub fn is_connected(&self) -> bool {
+        if self.v == 0 {
+            return true;
+        }
+        let mut marked = vec![false; self.v];
+        marked[0] = true;
+        let mut count = 1;
+        let mut stack = vec![0];
+        while let Some(i) = stack.pop() {
+            for &j in &self.adj[i] {
+                if !marked[j] {
+                    marked[j] = true;
+                    count += 1;
+                    stack.push(j);
+                }
+            }
+        }
+        count == self.v
+    }
+
+    /// Returns the number of connected components, by a union of the endpoints of every edge.
+    pub fn connected_component_count(&self) -> usize {
+        let mut uf = UF::new(self.v);
+        for (i, adj) in self.adj.iter().enumerate() {
+            for &j in adj {
+                if i < j {
+                    uf.union(i, j);
+                }
+            }
+        }
+        uf.count()
+    }
+
+    /// Returns the graph in Graphviz DOT format, each edge once as `v -- w`.
+    pub fn to_dot(&self) -> String {
+        self.to_dot_with_highlighted_edges(&[])
+    }
+
+    /// Returns the graph in Graphviz DOT format, drawing the given edges
+    /// (in either orientation) in red.
+    pub fn to_dot_with_highlighted_edges(&self, edges: &[(usize, usize)]) -> String {
+        let list = interop::edges(self).into_iter().map(|(v, w)| (v, w, None));
+        interop::write_dot(false, self.v, list, edges)
+    }
 }
 
+/// Writes the graph in the format read by `from_reader`, with each edge once.
 impl fmt::Display for Graph {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        writeln!(f, "{} vertices, {} edges", self.v, self.e)?;
-        for (i, adj) in self.adj.iter().enumerate() {
-            write!(f, "{}: ", i)?;
-            for v in adj {
-                write!(f, "{} ", v)?;
-            }
-            writeln!(f)?;
+        writeln!(f, "{}", self.v)?;
+        writeln!(f, "{}", self.e)?;
+        for (v, w) in interop::edges(self) {
+            writeln!(f, "{} {}", v, w)?;
         }
         Ok(())
     }
@@ -95,6 +244,225 @@ mod tests {
         tmp.sort_unstable();
         assert_eq!(tmp, vec![10, 11, 12]);
 
+        assert!(graph.has_edge(0, 5));
+        assert!(graph.has_edge(5, 0));
+        assert!(!graph.has_edge(0, 3));
+        assert!(!graph.has_edge(7, 7));
+
+        assert!(!graph.is_connected());
+        assert_eq!(graph.connected_component_count(), 3);
+
         println!("{}", graph);
     }
+
+    const TINY_G: &str = "13
+13
+0 5
+4 3
+0 1
+9 12
+6 4
+5 4
+0 2
+11 12
+9 10
+0 6
+7 8
+9 11
+5 3
+";
+
+    #[test]
+    fn simple() {
+        let mut graph = Graph::new(4);
+        graph.add_edge(0, 1);
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 0);
+        assert!(graph.is_simple());
+        let (simple, dropped) = graph.to_simple();
+        assert_eq!((simple.e(), dropped), (3, 0));
+
+        graph.add_edge(1, 0);
+        assert!(!graph.is_simple());
+        graph.add_edge(3, 3);
+        graph.add_edge(0, 1);
+        graph.add_edge(2, 3);
+        assert_eq!(graph.e(), 7);
+
+        let (simple, dropped) = graph.to_simple();
+        assert_eq!((simple.e(), dropped), (4, 3));
+        assert!(simple.is_simple());
+        assert_eq!(simple.degree(0), 2);
+        assert_eq!(simple.adj(3), &vec![2]);
+
+        let mut graph = Graph::new(1);
+        graph.add_edge(0, 0);
+        assert!(!graph.is_simple());
+    }
+
+    #[test]
+    fn connected() {
+        let mut graph = Graph::new(0);
+        assert!(graph.is_connected());
+        assert_eq!(graph.connected_component_count(), 0);
+
+        graph.add_vertex();
+        assert!(graph.is_connected());
+        assert_eq!(graph.connected_component_count(), 1);
+
+        let mut graph = Graph::new(5);
+        graph.add_edge(0, 1);
+        graph.add_edge(2, 3);
+        graph.add_edge(3, 3);
+        assert!(!graph.is_connected());
+        assert_eq!(graph.connected_component_count(), 3);
+        graph.add_edge(1, 2);
+        graph.add_edge(4, 0);
+        assert!(graph.is_connected());
+        assert_eq!(graph.connected_component_count(), 1);
+    }
+
+    #[test]
+    fn add_vertex() {
+        let mut graph = Graph::new(0);
+        let a = graph.add_vertex();
+        let b = graph.add_vertex();
+        graph.add_edge(a, b);
+        let c = graph.add_vertex();
+        graph.add_edge(c, a);
+        graph.add_edge(c, c);
+        assert_eq!((a, b, c), (0, 1, 2));
+        assert_eq!(graph.v(), 3);
+        assert_eq!(graph.e(), 3);
+        assert_eq!(graph.adj(0), &vec![1, 2]);
+        assert_eq!(graph.degree(2), 3);
+
+        let cc = crate::graphs::cc::CC::new(&graph);
+        assert!(cc.connected(1, 2));
+    }
+
+    #[test]
+    fn from_reader() {
+        let graph = Graph::from_reader(TINY_G.as_bytes()).unwrap();
+        assert_eq!(graph.v(), 13);
+        assert_eq!(graph.e(), 13);
+        assert_eq!(graph.adj(0), &vec![5, 1, 2, 6]);
+        assert_eq!(graph.adj(12), &vec![9, 11]);
+
+        // to_string round-trips, a self-loop included
+        let mut graph = graph;
+        graph.add_edge(3, 3);
+        let text = graph.to_string();
+        let copy = Graph::from_reader(text.as_bytes()).unwrap();
+        assert_eq!(copy.e(), 14);
+        assert_eq!(copy.degree(3), graph.degree(3));
+        assert_eq!(copy.to_string(), text);
+    }
+
+    #[test]
+    fn from_reader_errors() {
+        use crate::graphs::graph_reader::ParseGraphError;
+
+        let err = |s: &str| Graph::from_reader(s.as_bytes()).err().unwrap();
+        assert!(matches!(err(""), ParseGraphError::UnexpectedEof));
+        assert!(matches!(
+            err("13\n3\n0 5\n4"),
+            ParseGraphError::UnexpectedEof
+        ));
+        assert!(matches!(err("x 1"), ParseGraphError::InvalidCount(_)));
+        assert!(matches!(
+            err("3 -1"),
+            ParseGraphError::NegativeEdgeCount(-1)
+        ));
+        assert!(matches!(err("3 1 0 a"), ParseGraphError::InvalidVertex(_)));
+        assert!(matches!(
+            err("3 1 0 3"),
+            ParseGraphError::VertexOutOfRange { vertex: 3, v: 3 }
+        ));
+    }
+
+    #[test]
+    fn from_edges() {
+        let edges = [
+            (0, 5),
+            (4, 3),
+            (0, 1),
+            (9, 12),
+            (6, 4),
+            (5, 4),
+            (0, 2),
+            (11, 12),
+            (9, 10),
+            (0, 6),
+            (7, 8),
+            (9, 11),
+            (5, 3),
+        ];
+        let graph = Graph::from_edges(13, &edges);
+        let mut expected = Graph::new(13);
+        for &(i, j) in &edges {
+            expected.add_edge(i, j);
+        }
+        assert_eq!(graph.e(), 13);
+        assert_eq!(graph.adj(0), &vec![5, 1, 2, 6]);
+        assert_eq!(graph.adj(4), expected.adj(4));
+        assert_eq!(graph.to_string(), expected.to_string());
+
+        assert_eq!(Graph::from_edges(3, &[]).v(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "vertex 3 is not between 0 and 3")]
+    fn from_edges_out_of_range() {
+        Graph::from_edges(3, &[(3, 0)]);
+    }
+
+    #[test]
+    fn degrees() {
+        let mut graph = Graph::new(5);
+        graph.add_edge(0, 1);
+        graph.add_edge(0, 2);
+        graph.add_edge(0, 3);
+        graph.add_edge(1, 2);
+        graph.add_edge(3, 3);
+
+        let degrees: Vec<usize> = (0..graph.v()).map(|v| graph.degree(v)).collect();
+        assert_eq!(degrees, vec![3, 2, 2, 3, 0]);
+        assert_eq!(graph.max_degree(), 3);
+        // 2 * 5 edges over 5 vertices
+        assert_eq!(graph.average_degree(), 2.0);
+        assert_eq!(graph.number_of_self_loops(), 1);
+
+        graph.add_edge(4, 4);
+        graph.add_edge(4, 4);
+        assert_eq!(graph.degree(4), 4);
+        assert_eq!(graph.max_degree(), 4);
+        assert_eq!(graph.number_of_self_loops(), 3);
+
+        let empty = Graph::new(0);
+        assert_eq!(empty.max_degree(), 0);
+        assert_eq!(empty.average_degree(), 0.0);
+        assert_eq!(empty.number_of_self_loops(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "vertex 5 is not between 0 and 5")]
+    fn degree_out_of_range() {
+        Graph::new(5).degree(5);
+    }
+
+    #[test]
+    fn to_dot() {
+        let mut g = Graph::new(4);
+        g.add_edge(0, 1);
+        g.add_edge(1, 2);
+        g.add_edge(2, 2);
+        assert_eq!(
+            g.to_dot(),
+            "graph {\n  0;\n  1;\n  2;\n  3;\n  0 -- 1;\n  1 -- 2;\n  2 -- 2;\n}\n"
+        );
+        let dot = g.to_dot_with_highlighted_edges(&[(2, 1)]);
+        assert!(dot.contains("  1 -- 2 [color=red, penwidth=2];\n"));
+        assert!(dot.contains("  0 -- 1;\n"));
+    }
 }
@@ -0,0 +1,108 @@
+//! # Reading graphs in the book's text format
+//!
+//! The format is whitespace separated: the number of vertices `V`, the number of edges `E`,
+//! then `E` edges as `v w` pairs (or `v w weight` triples for edge-weighted graphs).
+//! Tokens after the last edge are ignored.
+
+use std::fmt;
+use std::io::{self, BufRead};
+
+/// Errors raised when parsing a graph.
+#[derive(Debug)]
+pub enum ParseGraphError {
+    /// The underlying reader failed.
+    Io(io::Error),
+    /// The input ended before all the counts and edges were read.
+    UnexpectedEof,
+    /// The number of vertices or edges is not a non-negative integer.
+    InvalidCount(String),
+    /// The number of edges is negative.
+    NegativeEdgeCount(i64),
+    /// A vertex is not a non-negative integer.
+    InvalidVertex(String),
+    /// A vertex is not between 0 and `v - 1`.
+    VertexOutOfRange { vertex: usize, v: usize },
+    /// An edge weight is not a number, or is NaN. `inf` and `-inf` are accepted,
+    /// since `Display` writes infinite weights that way.
+    InvalidWeight(String),
+}
+
+impl fmt::Display for ParseGraphError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseGraphError::Io(e) => write!(f, "io error: {}", e),
+            ParseGraphError::UnexpectedEof => write!(f, "unexpected end of input"),
+            ParseGraphError::InvalidCount(t) => write!(f, "invalid count {:?}", t),
+            ParseGraphError::NegativeEdgeCount(e) => {
+                write!(f, "number of edges must be non-negative, got {}", e)
+            }
+            ParseGraphError::InvalidVertex(t) => write!(f, "invalid vertex {:?}", t),
+            ParseGraphError::VertexOutOfRange { vertex, v } => {
+                write!(f, "vertex {} is not between 0 and {}", vertex, v)
+            }
+            ParseGraphError::InvalidWeight(t) => write!(f, "invalid weight {:?}", t),
+        }
+    }
+}
+
+impl std::error::Error for ParseGraphError {}
+
+impl From<io::Error> for ParseGraphError {
+    fn from(e: io::Error) -> Self {
+        ParseGraphError::Io(e)
+    }
+}
+
+pub type Result<T> = std::result::Result<T, ParseGraphError>;
+
+/// The tokens of a graph file, read in order.
+pub(crate) struct Tokens {
+    tokens: std::vec::IntoIter<String>,
+    v: usize, // number of vertices, once read
+}
+
+impl Tokens {
+    pub(crate) fn read(r: impl BufRead) -> Result<Self> {
+        let mut tokens = Vec::new();
+        for line in r.lines() {
+            tokens.extend(line?.split_whitespace().map(String::from));
+        }
+        Ok(Tokens {
+            tokens: tokens.into_iter(),
+            v: 0,
+        })
+    }
+
+    fn next(&mut self) -> Result<String> {
+        self.tokens.next().ok_or(ParseGraphError::UnexpectedEof)
+    }
+
+    /// Reads the number of vertices and the number of edges.
+    pub(crate) fn counts(&mut self) -> Result<(usize, usize)> {
+        let t = self.next()?;
+        self.v = t.parse().map_err(|_| ParseGraphError::InvalidCount(t))?;
+        let t = self.next()?;
+        let e: i64 = t.parse().map_err(|_| ParseGraphError::InvalidCount(t))?;
+        if e < 0 {
+            return Err(ParseGraphError::NegativeEdgeCount(e));
+        }
+        Ok((self.v, e as usize))
+    }
+
+    pub(crate) fn vertex(&mut self) -> Result<usize> {
+        let t = self.next()?;
+        let vertex = t.parse().map_err(|_| ParseGraphError::InvalidVertex(t))?;
+        if vertex >= self.v {
+            return Err(ParseGraphError::VertexOutOfRange { vertex, v: self.v });
+        }
+        Ok(vertex)
+    }
+
+    pub(crate) fn weight(&mut self) -> Result<f64> {
+        let t = self.next()?;
+        match t.parse::<f64>() {
+            Ok(weight) if !weight.is_nan() => Ok(weight),
+            _ => Err(ParseGraphError::InvalidWeight(t)),
+        }
+    }
+}
@@ -0,0 +1,195 @@
+//! # Iterative deepening depth-first search
+//!
+//! Finds a shortest path (in number of edges) from `start` to a goal vertex by running a
+//! depth-first search limited to depth 0, 1, 2, ... until it reaches a goal.
+//! Like breadth-first search it finds a shortest path, but it only keeps the current path
+//! in memory, so the successors can come from a function over a huge implicit state space.
+//!
+//! A vertex is never revisited along the current path, so cycles cannot loop forever.
+//! Each iteration takes time proportional to the number of paths of the current length,
+//! which the deeper iterations dominate in a branching search.
+
+use std::collections::HashSet;
+
+use super::digraph::Digraph;
+
+/// Returns a shortest path from `start` to a vertex satisfying `goal` with at most `max_depth`
+/// edges, `start` and the goal included; or `None` if there is no such path.
+///
+/// `neighbors(v, out)` pushes the successors of `v` onto `out`, which is empty when it is called.
+pub fn iddfs(
+    start: usize,
+    goal: impl Fn(usize) -> bool,
+    neighbors: impl Fn(usize, &mut Vec<usize>),
+    max_depth: usize,
+) -> Option<Vec<usize>> {
+    for limit in 0..=max_depth {
+        match depth_limited(start, &goal, &neighbors, limit) {
+            Search::Found(path) => return Some(path),
+            Search::Exhausted => return None,
+            Search::CutOff => {}
+        }
+    }
+    None
+}
+
+/// Returns a shortest path from `s` to `t` in `g` with at most `max_depth` edges.
+pub fn iddfs_digraph(g: &Digraph, s: usize, t: usize, max_depth: usize) -> Option<Vec<usize>> {
+    iddfs(
+        s,
+        |v| v == t,
+        |v, out| out.extend_from_slice(g.adj(v)),
+        max_depth,
+    )
+}
+
+enum Search {
+    Found(Vec<usize>),
+    CutOff,    // no path yet, but some paths were cut off at the limit
+    Exhausted, // every path from the start was searched to its end
+}
+
+fn depth_limited(
+    start: usize,
+    goal: &impl Fn(usize) -> bool,
+    neighbors: &impl Fn(usize, &mut Vec<usize>),
+    limit: usize,
+) -> Search {
+    if goal(start) {
+        return Search::Found(vec![start]);
+    }
+    if limit == 0 {
+        return Search::CutOff;
+    }
+
+    let successors = |v: usize| {
+        let mut out = Vec::new();
+        neighbors(v, &mut out);
+        out.into_iter()
+    };
+    let mut cut_off = false;
+    let mut path = vec![start];
+    let mut on_path = HashSet::from([start]);
+    // stack[i] = the successors of path[i] still to try
+    let mut stack = vec![successors(start)];
+    while let Some(next) = stack.last_mut() {
+        match next.next() {
+            Some(w) => {
+                if on_path.contains(&w) {
+                    continue;
+                }
+                path.push(w);
+                if goal(w) {
+                    return Search::Found(path);
+                }
+                if path.len() > limit {
+                    // w is at the depth limit
+                    cut_off = true;
+                    path.pop();
+                } else {
+                    on_path.insert(w);
+                    stack.push(successors(w));
+                }
+            }
+            None => {
+                stack.pop();
+                on_path.remove(&path.pop().unwrap());
+            }
+        }
+    }
+    if cut_off {
+        Search::CutOff
+    } else {
+        Search::Exhausted
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::graphs::bfs_directed_paths::BreadthFirstDirectedPaths;
+
+    fn tiny_dg() -> Digraph {
+        Digraph::from_edges(
+            13,
+            &[
+                (4, 2),
+                (2, 3),
+                (3, 2),
+                (6, 0),
+                (0, 1),
+                (2, 0),
+                (11, 12),
+                (12, 9),
+                (9, 10),
+                (9, 11),
+                (7, 9),
+                (10, 12),
+                (11, 4),
+                (4, 3),
+                (3, 5),
+                (6, 8),
+                (8, 6),
+                (5, 4),
+                (0, 5),
+                (6, 4),
+                (6, 9),
+                (7, 6),
+            ],
+        )
+    }
+
+    #[test]
+    fn same_as_bfs() {
+        let g = tiny_dg();
+        for s in 0..g.v() {
+            let bfs = BreadthFirstDirectedPaths::new(&g, s);
+            for t in 0..g.v() {
+                let path = iddfs_digraph(&g, s, t, g.v());
+                assert_eq!(path.is_some(), bfs.has_path_to(t));
+                if let Some(path) = path {
+                    assert_eq!(Some(path.len() - 1), bfs.dist_to(t));
+                    assert_eq!((path[0], path[path.len() - 1]), (s, t));
+                    for e in path.windows(2) {
+                        assert!(g.has_edge(e[0], e[1]));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn max_depth() {
+        let g = tiny_dg();
+        // 11->4->3->5
+        assert_eq!(iddfs_digraph(&g, 11, 5, 3), Some(vec![11, 4, 3, 5]));
+        assert_eq!(iddfs_digraph(&g, 11, 5, 2), None);
+        assert_eq!(iddfs_digraph(&g, 7, 7, 0), Some(vec![7]));
+        assert_eq!(iddfs_digraph(&g, 1, 0, 100), None);
+    }
+
+    #[test]
+    fn implicit_cyclic() {
+        // states 0..1_000_000 on a cycle, with steps of +1 and *2
+        let n = 1_000_000;
+        let neighbors = |v: usize, out: &mut Vec<usize>| {
+            out.push((v + 1) % n);
+            out.push(v * 2 % n);
+        };
+        // 1 -> 2 -> 3 -> 6 -> 12 -> 24 -> 25 -> 50 -> 100
+        let path = iddfs(1, |v| v == 100, neighbors, 20).unwrap();
+        assert_eq!(path.len() - 1, 8);
+        for e in path.windows(2) {
+            let mut out = Vec::new();
+            neighbors(e[0], &mut out);
+            assert!(out.contains(&e[1]));
+        }
+
+        // no path within the depth: it stops after max_depth iterations
+        assert_eq!(iddfs(1, |v| v == 100, neighbors, 7), None);
+
+        // a 3-cycle never reaches the goal, and the search terminates
+        let cycle = |v: usize, out: &mut Vec<usize>| out.push((v + 1) % 3);
+        assert_eq!(iddfs(0, |v| v == 5, cycle, usize::MAX), None);
+    }
+}
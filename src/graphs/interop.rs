@@ -0,0 +1,396 @@
+//! # Exchanging undirected graphs with other tools (e.g. networkx)
+//!
+//! Two text formats are supported:
+//!
+//! * edge list: one `u v` pair per line, as written by `networkx.write_edgelist`.
+//!   The number of vertices is the largest vertex seen plus one.
+//! * [graph6](https://users.cecs.anu.edu.au/~bdm/data/formats.txt): a compact
+//!   printable encoding of the upper triangle of the adjacency matrix of a simple graph.
+//!
+//! The graph types also write [Graphviz](https://graphviz.org/doc/info/lang.html)
+//! DOT for drawing, through their `to_dot` methods.
+
+use std::fmt;
+use std::io::{self, BufRead};
+
+use super::graph::Graph;
+
+/// Errors raised when reading a graph from text.
+#[derive(Debug)]
+pub enum InteropError {
+    /// The underlying reader failed.
+    Io(io::Error),
+    /// A malformed line in an edge list; `line` is 1-based.
+    EdgeList { line: usize, msg: String },
+    /// A malformed graph6 string; `pos` is the 0-based byte offset.
+    Graph6 { pos: usize, msg: String },
+}
+
+impl fmt::Display for InteropError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InteropError::Io(e) => write!(f, "io error: {}", e),
+            InteropError::EdgeList { line, msg } => write!(f, "line {}: {}", line, msg),
+            InteropError::Graph6 { pos, msg } => write!(f, "byte {}: {}", pos, msg),
+        }
+    }
+}
+
+impl std::error::Error for InteropError {}
+
+impl From<io::Error> for InteropError {
+    fn from(e: io::Error) -> Self {
+        InteropError::Io(e)
+    }
+}
+
+pub type Result<T> = std::result::Result<T, InteropError>;
+
+// the largest order graph6 can encode with the 4-byte header
+const GRAPH6_MAX_N: usize = 258047;
+const GRAPH6_HEADER: &str = ">>graph6<<";
+
+/// Reads an edge list with one `u v` pair per line.
+///
+/// Blank lines and `#` comments are skipped, and fields after the two endpoints
+/// (e.g. the `{}` attribute dict networkx writes by default) are ignored.
+pub fn from_edge_list_text(r: impl BufRead) -> Result<Graph> {
+    let mut edges = Vec::new();
+    let mut v = 0;
+    for (i, line) in r.lines().enumerate() {
+        let line = line?;
+        let content = match line.find('#') {
+            Some(p) => &line[..p],
+            None => &line[..],
+        };
+        let mut tokens = content.split_whitespace();
+        let first = match tokens.next() {
+            Some(t) => t,
+            None => continue,
+        };
+        let second = tokens.next().ok_or_else(|| InteropError::EdgeList {
+            line: i + 1,
+            msg: String::from("expected two vertices"),
+        })?;
+        let parse = |t: &str| {
+            t.parse::<usize>().map_err(|_| InteropError::EdgeList {
+                line: i + 1,
+                msg: format!("invalid vertex {:?}", t),
+            })
+        };
+        let (a, b) = (parse(first)?, parse(second)?);
+        v = v.max(a + 1).max(b + 1);
+        edges.push((a, b));
+    }
+
+    let mut g = Graph::new(v);
+    for (a, b) in edges {
+        g.add_edge(a, b);
+    }
+    Ok(g)
+}
+
+/// Writes every edge of `g` once as a `u v` line with `u <= v`.
+///
+/// Isolated vertices beyond the last endpoint are not representable in this format.
+pub fn to_edge_list_text(g: &Graph) -> String {
+    let mut s = String::new();
+    for (v, w) in edges(g) {
+        s.push_str(&format!("{} {}\n", v, w));
+    }
+    s
+}
+
+/// Decodes a graph6 string (an optional `>>graph6<<` header and trailing newline are accepted).
+pub fn from_graph6(s: &str) -> Result<Graph> {
+    let s = s.trim_end_matches(['\n', '\r']);
+    let offset = if s.starts_with(GRAPH6_HEADER) {
+        GRAPH6_HEADER.len()
+    } else {
+        0
+    };
+    let bytes = &s.as_bytes()[offset..];
+
+    // every byte carries 6 bits, stored as value + 63
+    let sextet = |i: usize| -> Result<usize> {
+        match bytes.get(i) {
+            Some(&b) if (63..=126).contains(&b) => Ok((b - 63) as usize),
+            Some(&b) => Err(InteropError::Graph6 {
+                pos: offset + i,
+                msg: format!("invalid byte {:#04x}", b),
+            }),
+            None => Err(InteropError::Graph6 {
+                pos: offset + i,
+                msg: String::from("unexpected end of input"),
+            }),
+        }
+    };
+
+    let (n, mut i) = match sextet(0)? {
+        63 => {
+            if bytes.get(1) == Some(&126) {
+                return Err(InteropError::Graph6 {
+                    pos: offset + 1,
+                    msg: String::from("graphs with more than 258047 vertices are not supported"),
+                });
+            }
+            ((sextet(1)? << 12) | (sextet(2)? << 6) | sextet(3)?, 4)
+        }
+        n => (n, 1),
+    };
+
+    let mut g = Graph::new(n);
+    let mut k = 0;
+    let mut bits = 0;
+    for w in 1..n {
+        for v in 0..w {
+            if k % 6 == 0 {
+                bits = sextet(i)?;
+                i += 1;
+            }
+            if bits & (1 << (5 - k % 6)) != 0 {
+                g.add_edge(v, w);
+            }
+            k += 1;
+        }
+    }
+    if i < bytes.len() {
+        return Err(InteropError::Graph6 {
+            pos: offset + i,
+            msg: String::from("trailing data"),
+        });
+    }
+    Ok(g)
+}
+
+/// Encodes `g` as graph6.
+///
+/// graph6 describes simple graphs only: self-loops are dropped and parallel edges collapse into one.
+pub fn to_graph6(g: &Graph) -> String {
+    let n = g.v();
+    assert!(
+        n <= GRAPH6_MAX_N,
+        "graph6 supports at most {} vertices",
+        GRAPH6_MAX_N
+    );
+
+    let mut out = Vec::new();
+    if n <= 62 {
+        out.push(n as u8 + 63);
+    } else {
+        out.push(126);
+        for shift in [12, 6, 0] {
+            out.push(((n >> shift) & 0x3f) as u8 + 63);
+        }
+    }
+
+    let mut adj = vec![vec![false; n]; n];
+    for (v, w) in edges(g) {
+        adj[v][w] = true;
+    }
+    let mut bits = 0u8;
+    let mut k = 0;
+    for w in 1..n {
+        for row in adj.iter().take(w) {
+            bits = (bits << 1) | row[w] as u8;
+            k += 1;
+            if k % 6 == 0 {
+                out.push(bits + 63);
+                bits = 0;
+            }
+        }
+    }
+    if k % 6 != 0 {
+        out.push((bits << (6 - k % 6)) + 63);
+    }
+    String::from_utf8(out).unwrap()
+}
+
+// writes a DOT graph listing every vertex, so that isolated ones are drawn too;
+// an undirected edge is highlighted if either orientation is in `highlighted`
+pub(crate) fn write_dot(
+    directed: bool,
+    v: usize,
+    edges: impl IntoIterator<Item = (usize, usize, Option<f64>)>,
+    highlighted: &[(usize, usize)],
+) -> String {
+    let (kind, op) = if directed {
+        ("digraph", "->")
+    } else {
+        ("graph", "--")
+    };
+    let mut s = format!("{} {{\n", kind);
+    for i in 0..v {
+        s.push_str(&format!("  {};\n", i));
+    }
+    for (a, b, weight) in edges {
+        let mut attrs = Vec::new();
+        if let Some(weight) = weight {
+            attrs.push(format!("label=\"{}\"", weight));
+        }
+        if highlighted
+            .iter()
+            .any(|&(x, y)| (x, y) == (a, b) || (!directed && (y, x) == (a, b)))
+        {
+            attrs.push(String::from("color=red"));
+            attrs.push(String::from("penwidth=2"));
+        }
+        if attrs.is_empty() {
+            s.push_str(&format!("  {} {} {};\n", a, op, b));
+        } else {
+            s.push_str(&format!("  {} {} {} [{}];\n", a, op, b, attrs.join(", ")));
+        }
+    }
+    s.push_str("}\n");
+    s
+}
+
+// each undirected edge once as (v, w) with v <= w
+pub(crate) fn edges(g: &Graph) -> Vec<(usize, usize)> {
+    (0..g.v())
+        .flat_map(|v| once_each(v, g.adj(v).iter().map(move |&w| (w, (v, w)))))
+        .collect()
+}
+
+// the items of v's adjacency list, given with their other endpoint, that make each
+// undirected edge appear once: those to a larger vertex, and every other self-loop,
+// since a self-loop appears twice in its list
+pub(crate) fn once_each<T>(
+    v: usize,
+    adj: impl IntoIterator<Item = (usize, T)>,
+) -> impl Iterator<Item = T> {
+    let mut self_loops = 0;
+    adj.into_iter().filter_map(move |(w, item)| {
+        if w == v {
+            self_loops += 1;
+        }
+        (w > v || (w == v && self_loops % 2 == 1)).then_some(item)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn sorted_edges(g: &Graph) -> Vec<(usize, usize)> {
+        let mut e = edges(g);
+        e.sort_unstable();
+        e
+    }
+
+    fn cycle(n: usize) -> Graph {
+        let mut g = Graph::new(n);
+        for v in 0..n {
+            g.add_edge(v, (v + 1) % n);
+        }
+        g
+    }
+
+    #[test]
+    fn edge_list_round_trip() {
+        let g = cycle(5);
+        let text = to_edge_list_text(&g);
+        assert_eq!(text, "0 1\n0 4\n1 2\n2 3\n3 4\n");
+
+        let h = from_edge_list_text(text.as_bytes()).unwrap();
+        assert_eq!(h.v(), 5);
+        assert_eq!(h.e(), 5);
+        assert_eq!(sorted_edges(&h), sorted_edges(&g));
+    }
+
+    #[test]
+    fn edge_list_whitespace() {
+        let text = "# written by networkx\n  0\t1 {}\n\n1    2\r\n 3 2   # comment\n";
+        let g = from_edge_list_text(text.as_bytes()).unwrap();
+        assert_eq!(g.v(), 4);
+        assert_eq!(sorted_edges(&g), vec![(0, 1), (1, 2), (2, 3)]);
+    }
+
+    #[test]
+    fn edge_list_malformed() {
+        match from_edge_list_text("0 1\n2\n".as_bytes()) {
+            Err(InteropError::EdgeList { line, .. }) => assert_eq!(line, 2),
+            _ => panic!("expected an edge list error"),
+        }
+        match from_edge_list_text("0 1\n1 -2\n".as_bytes()) {
+            Err(InteropError::EdgeList { line, .. }) => assert_eq!(line, 2),
+            _ => panic!("expected an edge list error"),
+        }
+    }
+
+    #[test]
+    fn graph6_known() {
+        assert_eq!(to_graph6(&cycle(5)), "Dhc");
+        assert_eq!(to_graph6(&Graph::new(1)), "@");
+        assert_eq!(to_graph6(&Graph::new(0)), "?");
+
+        let mut k4 = Graph::new(4);
+        for w in 1..4 {
+            for v in 0..w {
+                k4.add_edge(v, w);
+            }
+        }
+        assert_eq!(to_graph6(&k4), "C~");
+
+        let mut petersen = Graph::new(10);
+        for (v, w) in [
+            (0, 1),
+            (0, 4),
+            (0, 5),
+            (1, 2),
+            (1, 6),
+            (2, 3),
+            (2, 7),
+            (3, 4),
+            (3, 8),
+            (4, 9),
+            (5, 7),
+            (5, 8),
+            (6, 8),
+            (6, 9),
+            (7, 9),
+        ] {
+            petersen.add_edge(v, w);
+        }
+        assert_eq!(to_graph6(&petersen), "IheA@GUAo");
+
+        let g = from_graph6(">>graph6<<Dhc\n").unwrap();
+        assert_eq!(sorted_edges(&g), sorted_edges(&cycle(5)));
+    }
+
+    #[test]
+    fn graph6_round_trip() {
+        for n in [0, 1, 2, 7, 62, 63, 100] {
+            let mut g = Graph::new(n);
+            for w in 0..n {
+                for v in 0..w {
+                    if (v * 7 + w * 3) % 5 == 0 {
+                        g.add_edge(v, w);
+                    }
+                }
+            }
+            let s = to_graph6(&g);
+            let h = from_graph6(&s).unwrap();
+            assert_eq!(h.v(), n);
+            assert_eq!(sorted_edges(&h), sorted_edges(&g));
+        }
+    }
+
+    #[test]
+    fn graph6_malformed() {
+        let pos = |s: &str| match from_graph6(s) {
+            Err(InteropError::Graph6 { pos, .. }) => pos,
+            _ => panic!("expected a graph6 error for {:?}", s),
+        };
+        // empty input
+        assert_eq!(pos(""), 0);
+        // 5 vertices need 2 data bytes
+        assert_eq!(pos("Dh"), 2);
+        // byte out of the printable range
+        assert_eq!(pos("D h"), 1);
+        // one byte too many
+        assert_eq!(pos("Dhcc"), 3);
+        // the header shifts positions
+        assert_eq!(pos(">>graph6<<Dh"), 12);
+    }
+}
@@ -27,12 +27,25 @@ impl KosarajuSCC {
         scc
     }
 
-    fn dfs(&mut self, g: &Digraph, v: usize) {
-        self.marked[v] = true;
-        self.id[v] = self.count;
-        for w in g.adj(v).clone() {
-            if !self.marked[w] {
-                self.dfs(g, w);
+    // iterative, so that long paths do not overflow the call stack
+    fn dfs(&mut self, g: &Digraph, s: usize) {
+        // (v, index of the next neighbor of v to examine)
+        let mut stack = vec![(s, 0)];
+        self.marked[s] = true;
+        self.id[s] = self.count;
+        while let Some((v, i)) = stack.last_mut() {
+            match g.adj(*v).get(*i) {
+                Some(&w) => {
+                    *i += 1;
+                    if !self.marked[w] {
+                        self.marked[w] = true;
+                        self.id[w] = self.count;
+                        stack.push((w, 0));
+                    }
+                }
+                None => {
+                    stack.pop();
+                }
             }
         }
     }
@@ -86,6 +99,10 @@ mod test {
         let scc = KosarajuSCC::new(&digraph);
 
         assert_eq!(scc.count(), 5);
+        assert_eq!(
+            (0..digraph.v()).map(|v| scc.id(v)).collect::<Vec<usize>>(),
+            vec![1, 0, 1, 1, 1, 1, 3, 4, 4, 2, 2, 2, 2]
+        );
 
         assert!(scc.strongly_connected(0, 2));
         assert!(scc.strongly_connected(2, 3));
@@ -97,4 +114,21 @@ mod test {
         assert!(scc.strongly_connected(7, 8));
         assert!(!scc.strongly_connected(0, 7));
     }
+
+    #[test]
+    fn long_chain() {
+        // deep enough to overflow the stack with a recursive search
+        let n = 500_000;
+        let mut digraph = Digraph::new(n);
+        for v in 0..n - 1 {
+            digraph.add_edge(v, v + 1);
+        }
+        let scc = KosarajuSCC::new(&digraph);
+        assert_eq!(scc.count(), n);
+
+        // closing the chain makes one big strong component
+        digraph.add_edge(n - 1, 0);
+        let scc = KosarajuSCC::new(&digraph);
+        assert_eq!(scc.count(), 1);
+    }
 }
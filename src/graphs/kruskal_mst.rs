@@ -1,18 +1,21 @@
-//! # Compute a minimum spanning forest using Krusal's algorithm and the union-find data type.
+//! # Compute a minimum spanning forest using Kruskal's algorithm and the union-find data type.
 //!
 //! The time complexity is O(E log(E)).
 
 use crate::fundamentals::quick_union_uf::UF;
 
 use super::{edge::Edge, weighted_graph::EdgeWeightedGraph};
-pub struct KrusalMST {
+pub struct KruskalMST {
     mst: Vec<Edge>, // a queue
     weight: f64,
 }
 
-impl KrusalMST {
+#[deprecated(note = "renamed to `KruskalMST`")]
+pub type KrusalMST = KruskalMST;
+
+impl KruskalMST {
     pub fn new(g: &EdgeWeightedGraph) -> Self {
-        let mut k_mst = KrusalMST {
+        let mut k_mst = KruskalMST {
             mst: vec![],
             weight: 0.0,
         };
@@ -23,7 +26,8 @@ impl KrusalMST {
         let mut uf = UF::new(g.v());
 
         for edge in edges {
-            if k_mst.mst.len() < g.v() - 1 {
+            // a spanning tree has V - 1 edges
+            if k_mst.mst.len() + 1 < g.v() {
                 let v = edge.either();
                 let w = edge.other(v);
                 if !uf.connected(v, w) {
@@ -53,6 +57,15 @@ impl KrusalMST {
 mod test {
     use super::*;
 
+    fn assert_weight(mst: &KruskalMST, expected: f64) {
+        assert!(
+            (mst.weight() - expected).abs() < 1e-12,
+            "weight {} != {}",
+            mst.weight(),
+            expected
+        );
+    }
+
     #[test]
     fn tiny_ewg() {
         let mut g = EdgeWeightedGraph::new(8);
@@ -73,9 +86,42 @@ mod test {
         g.add_edge(Edge::new(6, 0, 0.58));
         g.add_edge(Edge::new(6, 4, 0.93));
 
-        let mst = KrusalMST::new(&g);
+        let mst = KruskalMST::new(&g);
         mst.edges().for_each(|e| println!("{}", e));
 
-        assert_eq!(mst.weight(), 1.81);
+        assert_eq!(mst.edges().count(), 7);
+        assert_weight(&mst, 1.81);
+    }
+
+    #[test]
+    fn forest() {
+        let mut g = EdgeWeightedGraph::new(5);
+        g.add_edge(Edge::new(0, 1, 1.0));
+        g.add_edge(Edge::new(1, 2, 2.0));
+        g.add_edge(Edge::new(0, 2, 1.5));
+        g.add_edge(Edge::new(3, 4, 4.0));
+        let mst = KruskalMST::new(&g);
+        assert_eq!(mst.edges().count(), 3);
+        assert_weight(&mst, 6.5);
+    }
+
+    #[test]
+    fn empty_and_single_vertex() {
+        let mst = KruskalMST::new(&EdgeWeightedGraph::new(0));
+        assert_eq!(mst.edges().count(), 0);
+        assert_eq!(mst.weight(), 0.0);
+
+        let mut g = EdgeWeightedGraph::new(1);
+        g.add_edge(Edge::new(0, 0, 1.0));
+        let mst = KruskalMST::new(&g);
+        assert_eq!(mst.edges().count(), 0);
+        assert_eq!(mst.weight(), 0.0);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn deprecated_alias() {
+        let mst: KrusalMST = KruskalMST::new(&EdgeWeightedGraph::new(2));
+        assert_eq!(mst.edges().count(), 0);
     }
 }
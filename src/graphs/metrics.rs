@@ -0,0 +1,121 @@
+//! # Eccentricity, diameter, radius and center of an undirected graph
+//!
+//! The eccentricity of a vertex is its distance to the vertex farthest from it;
+//! the diameter and radius are the largest and smallest eccentricities,
+//! and a center is a vertex whose eccentricity is the radius.
+//!
+//! This implementation runs breadth-first search from every vertex,
+//! so the time complexity is O(V(V + E)); it suits small graphs.
+
+use super::{bfs_paths::BreadFirstPaths, graph::Graph};
+
+pub struct GraphMetrics {
+    eccentricity: Vec<usize>, // eccentricity[v] = distance to the farthest vertex from v
+}
+
+impl GraphMetrics {
+    /// Panics if the graph has no vertices or is not connected,
+    /// as some distances would then be infinite.
+    pub fn new(g: &Graph) -> Self {
+        if g.v() == 0 {
+            panic!("graph has no vertices");
+        }
+        if !g.is_connected() {
+            panic!("graph is not connected");
+        }
+        let eccentricity = (0..g.v())
+            .map(|s| {
+                let bfs = BreadFirstPaths::new(g, s);
+                (0..g.v()).filter_map(|v| bfs.dist_to(v)).max().unwrap()
+            })
+            .collect();
+        GraphMetrics { eccentricity }
+    }
+
+    /// Returns the eccentricity of v.
+    pub fn eccentricity(&self, v: usize) -> usize {
+        if v >= self.eccentricity.len() {
+            panic!(
+                "vertex {} is not between 0 and {}",
+                v,
+                self.eccentricity.len()
+            );
+        }
+        self.eccentricity[v]
+    }
+
+    /// Returns the diameter, the greatest distance between two vertices.
+    pub fn diameter(&self) -> usize {
+        *self.eccentricity.iter().max().unwrap()
+    }
+
+    /// Returns the radius, the smallest eccentricity.
+    pub fn radius(&self) -> usize {
+        *self.eccentricity.iter().min().unwrap()
+    }
+
+    /// Returns a center, the first vertex whose eccentricity is the radius.
+    pub fn center(&self) -> usize {
+        let radius = self.radius();
+        self.eccentricity.iter().position(|&e| e == radius).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn path_graph() {
+        for n in 1..10 {
+            let mut g = Graph::new(n);
+            for v in 0..n - 1 {
+                g.add_edge(v, v + 1);
+            }
+            let metrics = GraphMetrics::new(&g);
+            assert_eq!(metrics.diameter(), n - 1);
+            assert_eq!(metrics.radius(), n / 2);
+            assert_eq!(metrics.center(), (n - 1) / 2);
+            assert_eq!(metrics.eccentricity(0), n - 1);
+        }
+    }
+
+    #[test]
+    fn complete_graph() {
+        let n = 6;
+        let mut g = Graph::new(n);
+        for v in 0..n {
+            for w in v + 1..n {
+                g.add_edge(v, w);
+            }
+        }
+        let metrics = GraphMetrics::new(&g);
+        assert_eq!(metrics.diameter(), 1);
+        assert_eq!(metrics.radius(), 1);
+        assert_eq!(metrics.center(), 0);
+    }
+
+    #[test]
+    fn star_and_cycle() {
+        // a star with a tail: 1, 2, 3 around 0, and 3-4-5
+        let g = Graph::from_edges(6, &[(0, 1), (0, 2), (0, 3), (3, 4), (4, 5)]);
+        let metrics = GraphMetrics::new(&g);
+        let eccentricity: Vec<usize> = (0..6).map(|v| metrics.eccentricity(v)).collect();
+        assert_eq!(eccentricity, vec![3, 4, 4, 2, 3, 4]);
+        assert_eq!(metrics.diameter(), 4);
+        assert_eq!(metrics.radius(), 2);
+        assert_eq!(metrics.center(), 3);
+
+        // every vertex of a cycle is a center
+        let g = Graph::from_edges(5, &[(0, 1), (1, 2), (2, 3), (3, 4), (4, 0)]);
+        let metrics = GraphMetrics::new(&g);
+        assert_eq!(metrics.diameter(), 2);
+        assert_eq!(metrics.radius(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "not connected")]
+    fn disconnected() {
+        GraphMetrics::new(&Graph::from_edges(3, &[(0, 1)]));
+    }
+}
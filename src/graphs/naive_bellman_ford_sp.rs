@@ -6,26 +6,36 @@
 use super::{directed_edge::DirectedEdge, weighted_digraph::EdgeWeightedDiagraph};
 /// Initialized `dist_to[s]` to 0 and all other values to infinity.
 /// Then, considering the digraph's edges in any order, relax all edges. Make `V` such passes.
+/// If an edge can still be relaxed after that, a negative cycle is reachable from `s`.
 pub struct NaiveBellmanFordSP {
     dist_to: Vec<f64>,
+    edge_to: Vec<Option<DirectedEdge>>, // edge_to[v] = last edge on shortest s->v path
     s: usize,
+    has_negative_cycle: bool,
 }
 
 impl NaiveBellmanFordSP {
     pub fn new(g: &EdgeWeightedDiagraph, s: usize) -> Self {
         let mut sp = NaiveBellmanFordSP {
             dist_to: vec![f64::MAX; g.v()],
+            edge_to: vec![None; g.v()],
             s,
+            has_negative_cycle: false,
         };
         sp.dist_to[s] = 0.0;
         for _ in 0..g.v() {
             // `V` pass
             for v in 0..g.v() {
-                for e in g.adj(v) {
+                for &e in g.adj_ref(v) {
                     sp.relax(&e);
                 }
             }
         }
+        // one more pass: any edge that can still be relaxed lies on or after a negative cycle
+        sp.has_negative_cycle = g.edges().any(|e| {
+            sp.dist_to[e.from()] < f64::MAX
+                && sp.dist_to[e.from()] + e.weight() < sp.dist_to[e.to()]
+        });
         sp
     }
 
@@ -34,6 +44,7 @@ impl NaiveBellmanFordSP {
         let to = e.to();
         if self.dist_to[from] + e.weight() < self.dist_to[to] {
             self.dist_to[to] = self.dist_to[from] + e.weight();
+            self.edge_to[to] = Some(*e);
         }
     }
 
@@ -41,9 +52,30 @@ impl NaiveBellmanFordSP {
         self.dist_to[v]
     }
 
+    /// Returns the edges of a shortest path from the source to v, empty if there is none.
+    /// Panics if there is a negative cycle reachable from the source, as the path may go round it.
+    pub fn path_to(&self, v: usize) -> impl Iterator<Item = DirectedEdge> {
+        if self.has_negative_cycle {
+            panic!("negative cost cycle exists");
+        }
+        let mut path = Vec::new();
+        let mut edge = self.edge_to[v];
+        while let Some(e) = edge {
+            path.push(e);
+            edge = self.edge_to[e.from()];
+        }
+        path.into_iter().rev()
+    }
+
     pub fn source(&self) -> usize {
         self.s
     }
+
+    /// Is there a negative cycle reachable from the source?
+    /// If so, the distances are meaningless.
+    pub fn has_negative_cycle(&self) -> bool {
+        self.has_negative_cycle
+    }
 }
 
 #[cfg(test)]
@@ -78,5 +110,61 @@ mod test {
 
         assert!((sp.dist_to(4) - 0.38).abs() < f64::EPSILON);
         assert!((sp.dist_to(5) - 0.73).abs() < f64::EPSILON);
+        assert!(!sp.has_negative_cycle());
+
+        assert_eq!(
+            sp.path_to(6).map(|e| e.to()).collect::<Vec<usize>>(),
+            vec![2, 7, 3, 6]
+        );
+        assert_eq!(sp.path_to(0).count(), 0);
+        for v in 1..g.v() {
+            let path = sp.path_to(v).collect::<Vec<DirectedEdge>>();
+            assert_eq!(path[0].from(), 0);
+            assert_eq!(path.last().unwrap().to(), v);
+            let length = path.iter().map(|e| e.weight()).sum::<f64>();
+            assert!((length - sp.dist_to(v)).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn negative_cycle() {
+        let mut g = EdgeWeightedDiagraph::new(5);
+        g.add_edge(DirectedEdge::new(0, 1, 1.0));
+        g.add_edge(DirectedEdge::new(1, 2, 2.0));
+        g.add_edge(DirectedEdge::new(2, 3, -4.0));
+        g.add_edge(DirectedEdge::new(3, 1, 1.0));
+        g.add_edge(DirectedEdge::new(3, 4, 1.0));
+        assert!(NaiveBellmanFordSP::new(&g, 0).has_negative_cycle());
+
+        // negative edges, but the cycle is not negative
+        let mut g = EdgeWeightedDiagraph::new(4);
+        g.add_edge(DirectedEdge::new(0, 1, 1.0));
+        g.add_edge(DirectedEdge::new(1, 2, -2.0));
+        g.add_edge(DirectedEdge::new(2, 1, 3.0));
+        g.add_edge(DirectedEdge::new(2, 3, -1.0));
+        let sp = NaiveBellmanFordSP::new(&g, 0);
+        assert!(!sp.has_negative_cycle());
+        assert_eq!(sp.dist_to(3), -2.0);
+        assert_eq!(
+            sp.path_to(3).map(|e| e.from()).collect::<Vec<usize>>(),
+            vec![0, 1, 2]
+        );
+
+        // a negative cycle that is not reachable from the source
+        let mut g = EdgeWeightedDiagraph::new(3);
+        g.add_edge(DirectedEdge::new(1, 2, -1.0));
+        g.add_edge(DirectedEdge::new(2, 1, -1.0));
+        let sp = NaiveBellmanFordSP::new(&g, 0);
+        assert!(!sp.has_negative_cycle());
+        assert_eq!(sp.path_to(1).count(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "negative cost cycle")]
+    fn path_to_with_negative_cycle() {
+        let mut g = EdgeWeightedDiagraph::new(2);
+        g.add_edge(DirectedEdge::new(0, 1, -1.0));
+        g.add_edge(DirectedEdge::new(1, 0, -1.0));
+        NaiveBellmanFordSP::new(&g, 0).path_to(1).count();
     }
 }
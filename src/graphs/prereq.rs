@@ -0,0 +1,266 @@
+//! # Course planning over a prerequisite digraph
+//!
+//! Each course is a vertex of a `SymbolDigraph`, with an edge from a course to each of its
+//! prerequisites ("requires"). A study order is then a reverse topological order, and the
+//! prerequisites can be taken in one term if none requires another.
+
+use std::fmt;
+
+use super::directed_cycle::DirectedCycle;
+use super::directed_dfs::DirectedDFS;
+use super::symbol_digraph::SymbolDigraph;
+use super::topological::Topological;
+
+// separates the names on a line of the symbol digraph; course names are single lines
+const DELIMITER: &str = "\n";
+
+/// A cycle of prerequisites, by course name: each course requires the next,
+/// and the first course is repeated at the end.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CycleByName(pub Vec<String>);
+
+impl fmt::Display for CycleByName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "prerequisite cycle: {}", self.0.join(" requires "))
+    }
+}
+
+impl std::error::Error for CycleByName {}
+
+pub struct CoursePlanner {
+    sg: SymbolDigraph, // course -> prerequisite
+}
+
+impl CoursePlanner {
+    /// Builds a planner from `(course, prerequisites)` records. A course that only
+    /// appears as a prerequisite has none of its own.
+    pub fn from_records(records: &[(&str, &[&str])]) -> Self {
+        let lines = records
+            .iter()
+            .map(|&(course, prereqs)| {
+                let mut line = vec![course];
+                line.extend_from_slice(prereqs);
+                if let Some(name) = line.iter().find(|name| name.contains(DELIMITER)) {
+                    panic!("course name {:?} contains a line break", name);
+                }
+                line.join(DELIMITER)
+            })
+            .collect::<Vec<String>>();
+        CoursePlanner {
+            sg: SymbolDigraph::new(lines, DELIMITER),
+        }
+    }
+
+    fn names(&self, courses: impl Iterator<Item = usize>) -> Vec<String> {
+        courses.map(|v| self.sg.name_of(v).to_string()).collect()
+    }
+
+    fn order(&self) -> Result<Vec<usize>, CycleByName> {
+        let g = self.sg.digraph();
+        let finder = DirectedCycle::new(g);
+        if finder.has_cycle() {
+            return Err(CycleByName(self.names(DirectedCycle::cycle(&finder))));
+        }
+        // prerequisites come after the courses requiring them in a topological order
+        Ok(Topological::new(g).order().rev().collect())
+    }
+
+    /// Returns every course in an order that takes each prerequisite before the courses requiring it.
+    pub fn plan(&self) -> Result<Vec<String>, CycleByName> {
+        Ok(self.names(self.order()?.into_iter()))
+    }
+
+    /// Groups the courses into terms of at most `max_per_term` courses, each course coming
+    /// after all its prerequisites; the courses of a term are sorted by name.
+    ///
+    /// Without a binding limit, a course is taken in the term given by its depth, the longest chain
+    /// of prerequisites below it, which gives the fewest terms. Otherwise each term takes
+    /// the available courses with the longest chains of courses depending on them first;
+    /// that is a heuristic, as the fewest terms under a limit is NP-hard in general.
+    /// Panics if `max_per_term` is 0 or the prerequisites have a cycle.
+    pub fn semesters(&self, max_per_term: usize) -> Vec<Vec<String>> {
+        if max_per_term == 0 {
+            panic!("max_per_term must be positive");
+        }
+        let order = match self.order() {
+            Ok(order) => order,
+            Err(cycle) => panic!("{}", cycle),
+        };
+        let g = self.sg.digraph();
+        let dependents = g.reverse();
+
+        // height[v] = length of the longest chain of courses that require v
+        let mut height = vec![0; g.v()];
+        for &v in order.iter().rev() {
+            for &w in dependents.adj(v) {
+                height[v] = height[v].max(height[w] + 1);
+            }
+        }
+
+        // number of prerequisites of v not yet taken
+        let mut missing = (0..g.v()).map(|v| g.out_degree(v)).collect::<Vec<usize>>();
+        let mut available = (0..g.v())
+            .filter(|&v| missing[v] == 0)
+            .collect::<Vec<usize>>();
+        let mut terms = vec![];
+        while !available.is_empty() {
+            available.sort_by(|&a, &b| {
+                height[b]
+                    .cmp(&height[a])
+                    .then_with(|| self.sg.name_of(a).cmp(self.sg.name_of(b)))
+            });
+            let rest = available.split_off(max_per_term.min(available.len()));
+            let term = std::mem::replace(&mut available, rest);
+            for &v in &term {
+                for &w in dependents.adj(v) {
+                    missing[w] -= 1;
+                    if missing[w] == 0 {
+                        available.push(w);
+                    }
+                }
+            }
+            let mut names = self.names(term.into_iter());
+            names.sort_unstable();
+            terms.push(names);
+        }
+        terms
+    }
+
+    /// Can course `a` be taken before course `b`, i.e. does `a` not require `b`, directly or not?
+    /// A course cannot be taken before itself. Returns `None` if either course is unknown.
+    pub fn can_take_before(&self, a: &str, b: &str) -> Option<bool> {
+        let a = self.sg.index_of(a)?;
+        let b = self.sg.index_of(b)?;
+        Some(!DirectedDFS::new(self.sg.digraph(), a).marked(b))
+    }
+
+    /// Returns the prerequisite digraph and course names.
+    pub fn symbol_digraph(&self) -> &SymbolDigraph {
+        &self.sg
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // the jobs of the `Topological` test, as course -> prerequisites
+    const JOBS: &[(&str, &[&str])] = &[
+        ("Algorithms", &["Introduction to CS"]),
+        ("Theoretical CS", &["Algorithms", "Linear Algebra"]),
+        ("Databases", &["Algorithms"]),
+        (
+            "Scientific Computing",
+            &["Algorithms", "Advanced Programming"],
+        ),
+        ("Advanced Programming", &["Introduction to CS"]),
+        (
+            "Computational Biology",
+            &["Scientific Computing", "Theoretical CS"],
+        ),
+        ("Artificial Intelligence", &["Theoretical CS"]),
+        ("Linear Algebra", &["Calculus"]),
+        (
+            "Neural Networks",
+            &["Artificial Intelligence", "Machine Learning"],
+        ),
+        ("Robotics", &["Artificial Intelligence"]),
+        ("Machine Learning", &["Artificial Intelligence"]),
+    ];
+
+    fn assert_prerequisites_first(planner: &CoursePlanner, terms: &[Vec<String>]) {
+        let sg = planner.symbol_digraph();
+        let mut term_of = vec![usize::MAX; sg.digraph().v()];
+        for (i, term) in terms.iter().enumerate() {
+            for name in term {
+                term_of[sg.index_of(name).unwrap()] = i;
+            }
+        }
+        assert!(term_of.iter().all(|&t| t != usize::MAX));
+        for v in 0..sg.digraph().v() {
+            for &w in sg.digraph().adj(v) {
+                assert!(term_of[w] < term_of[v]);
+            }
+        }
+    }
+
+    #[test]
+    fn jobs() {
+        let planner = CoursePlanner::from_records(JOBS);
+        let plan = planner.plan().unwrap();
+        assert_eq!(plan.len(), 13);
+        let terms = plan.into_iter().map(|c| vec![c]).collect::<Vec<_>>();
+        assert_prerequisites_first(&planner, &terms);
+
+        let terms = planner.semesters(usize::MAX);
+        assert_prerequisites_first(&planner, &terms);
+        assert_eq!(
+            terms,
+            vec![
+                vec!["Calculus", "Introduction to CS"],
+                vec!["Advanced Programming", "Algorithms", "Linear Algebra"],
+                vec!["Databases", "Scientific Computing", "Theoretical CS"],
+                vec!["Artificial Intelligence", "Computational Biology"],
+                vec!["Machine Learning", "Robotics"],
+                vec!["Neural Networks"],
+            ]
+        );
+    }
+
+    #[test]
+    fn max_per_term() {
+        let planner = CoursePlanner::from_records(JOBS);
+        let terms = planner.semesters(2);
+        assert_prerequisites_first(&planner, &terms);
+        assert!(terms.iter().all(|t| t.len() <= 2));
+        // 13 courses need at least 7 terms of 2
+        assert_eq!(terms.len(), 7);
+
+        let terms = planner.semesters(1);
+        assert_eq!(terms.len(), 13);
+        assert_prerequisites_first(&planner, &terms);
+    }
+
+    #[test]
+    fn cycle() {
+        let planner = CoursePlanner::from_records(&[
+            ("Calculus", &[]),
+            ("Analysis", &["Calculus", "Topology"]),
+            ("Topology", &["Set Theory"]),
+            ("Set Theory", &["Analysis"]),
+        ]);
+        let cycle = planner.plan().unwrap_err();
+        assert_eq!(cycle.0.len(), 4);
+        assert_eq!(cycle.0.first(), cycle.0.last());
+        let mut names = cycle.0[1..].to_vec();
+        names.sort_unstable();
+        assert_eq!(names, vec!["Analysis", "Set Theory", "Topology"]);
+        assert!(cycle.to_string().starts_with("prerequisite cycle: "));
+        assert!(cycle.to_string().contains("Analysis requires Topology"));
+    }
+
+    #[test]
+    #[should_panic(expected = "prerequisite cycle")]
+    fn semesters_with_cycle() {
+        CoursePlanner::from_records(&[("A", &["B"]), ("B", &["A"])]).semesters(3);
+    }
+
+    #[test]
+    fn can_take_before() {
+        let planner = CoursePlanner::from_records(JOBS);
+        assert_eq!(
+            planner.can_take_before("Calculus", "Neural Networks"),
+            Some(true)
+        );
+        assert_eq!(
+            planner.can_take_before("Neural Networks", "Calculus"),
+            Some(false)
+        );
+        // unrelated courses go either way
+        assert_eq!(planner.can_take_before("Databases", "Robotics"), Some(true));
+        assert_eq!(planner.can_take_before("Robotics", "Databases"), Some(true));
+        assert_eq!(planner.can_take_before("Robotics", "Robotics"), Some(false));
+        assert_eq!(planner.can_take_before("Cooking", "Robotics"), None);
+        assert_eq!(planner.can_take_before("Robotics", "Cooking"), None);
+    }
+}
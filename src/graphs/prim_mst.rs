@@ -0,0 +1,152 @@
+//! # Compute a minimum spanning forest using the eager version of Prim's algorithm.
+//!
+//! Instead of keeping every crossing edge on the priority queue, it keeps only the
+//! lightest edge that connects each non-tree vertex to the tree, indexed by that vertex.
+//! The time complexity is O(E log(V)), and the extra space is O(V).
+
+use crate::sorting::index_min_pq::IndexMinPQ;
+
+use super::{edge::Edge, weighted_graph::EdgeWeightedGraph};
+pub struct PrimMST {
+    edge_to: Vec<Option<Edge>>, // edge_to[v] = shortest edge from tree vertex to non-tree vertex
+    dist_to: Vec<f64>,          // dist_to[v] = weight of shortest such edge
+    marked: Vec<bool>,          // marked[v] = true iff v on tree
+    pq: IndexMinPQ<f64>,        // eligible crossing edges, indexed by their non-tree vertex
+}
+
+/// `PrimMST` under the name that sets it apart from `LazyPrimMST`.
+pub type EagerPrimMST = PrimMST;
+
+impl PrimMST {
+    pub fn new(g: &EdgeWeightedGraph) -> Self {
+        let mut prim_mst = PrimMST {
+            edge_to: vec![None; g.v()],
+            dist_to: vec![f64::MAX; g.v()],
+            marked: vec![false; g.v()],
+            pq: IndexMinPQ::new(g.v()),
+        };
+        // run from each vertex to find minimum spanning forest
+        for v in 0..g.v() {
+            if !prim_mst.marked[v] {
+                prim_mst.prim(g, v);
+            }
+        }
+        prim_mst
+    }
+
+    fn prim(&mut self, g: &EdgeWeightedGraph, s: usize) {
+        self.dist_to[s] = 0.0;
+        self.pq.insert(s, self.dist_to[s]);
+        while let Some(v) = self.pq.del_min() {
+            self.scan(g, v);
+        }
+    }
+
+    // add v to the tree and update the best edge to each vertex adjacent to v
+    fn scan(&mut self, g: &EdgeWeightedGraph, v: usize) {
+        self.marked[v] = true;
+        for e in g.adj(v) {
+            let w = e.other(v);
+            if self.marked[w] {
+                // v-w is obsolete edge
+                continue;
+            }
+            if e.weight() < self.dist_to[w] {
+                self.dist_to[w] = e.weight();
+                self.edge_to[w] = Some(e);
+                if self.pq.contains(w) {
+                    self.pq.decrease_key(w, self.dist_to[w]);
+                } else {
+                    self.pq.insert(w, self.dist_to[w]);
+                }
+            }
+        }
+    }
+
+    /// Returns the sum of the edge weights in a minimum spanning tree
+    /// (or forest)
+    pub fn weight(&self) -> f64 {
+        self.edges().map(|e| e.weight()).sum()
+    }
+
+    /// Returns the edges in a minimum spanning tree (or forest).
+    pub fn edges(&self) -> impl Iterator<Item = Edge> + '_ {
+        self.edge_to.iter().flatten().cloned()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    use super::*;
+    use crate::graphs::lazy_prim_mst::LazyPrimMST;
+
+    #[test]
+    fn tiny_ewg() {
+        let mut g = EdgeWeightedGraph::new(8);
+        g.add_edge(Edge::new(4, 5, 0.35));
+        g.add_edge(Edge::new(4, 7, 0.37));
+        g.add_edge(Edge::new(5, 7, 0.28));
+        g.add_edge(Edge::new(0, 7, 0.16));
+        g.add_edge(Edge::new(1, 5, 0.32));
+        g.add_edge(Edge::new(0, 4, 0.38));
+        g.add_edge(Edge::new(2, 3, 0.17));
+        g.add_edge(Edge::new(1, 7, 0.19));
+        g.add_edge(Edge::new(0, 2, 0.26));
+        g.add_edge(Edge::new(1, 2, 0.36));
+        g.add_edge(Edge::new(1, 3, 0.29));
+        g.add_edge(Edge::new(2, 7, 0.34));
+        g.add_edge(Edge::new(6, 2, 0.40));
+        g.add_edge(Edge::new(3, 6, 0.52));
+        g.add_edge(Edge::new(6, 0, 0.58));
+        g.add_edge(Edge::new(6, 4, 0.93));
+
+        let mst = PrimMST::new(&g);
+        assert!((mst.weight() - 1.81).abs() < 1e-9);
+        assert_eq!(mst.edges().count(), g.v() - 1);
+
+        let mut weights = mst.edges().map(|e| e.weight()).collect::<Vec<f64>>();
+        weights.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(weights, vec![0.16, 0.17, 0.19, 0.26, 0.28, 0.35, 0.40]);
+
+        let eager = EagerPrimMST::new(&g);
+        let lazy = LazyPrimMST::new(&g);
+        assert!((eager.weight() - lazy.weight()).abs() < 1e-9);
+        let mut eager_edges = eager.edges().collect::<Vec<Edge>>();
+        let mut lazy_edges = lazy.edges().collect::<Vec<Edge>>();
+        eager_edges.sort_unstable();
+        lazy_edges.sort_unstable();
+        assert_eq!(eager_edges, lazy_edges);
+    }
+
+    #[test]
+    fn forest() {
+        let mut g = EdgeWeightedGraph::new(5);
+        g.add_edge(Edge::new(0, 1, 1.0));
+        g.add_edge(Edge::new(1, 2, 2.0));
+        g.add_edge(Edge::new(0, 2, 1.5));
+        g.add_edge(Edge::new(3, 4, 4.0));
+        let mst = PrimMST::new(&g);
+        assert_eq!(mst.edges().count(), 3);
+        assert_eq!(mst.weight(), 6.5);
+    }
+
+    #[test]
+    fn same_as_lazy() {
+        let mut rng = StdRng::seed_from_u64(38);
+        for _ in 0..20 {
+            let n = 30;
+            let mut g = EdgeWeightedGraph::new(n);
+            for _ in 0..100 {
+                let (v, w) = (rng.gen_range(0..n), rng.gen_range(0..n));
+                g.add_edge(Edge::new(v, w, rng.gen_range(0..1000) as f64));
+            }
+            let eager = PrimMST::new(&g);
+            let lazy = LazyPrimMST::new(&g);
+            assert_eq!(eager.weight(), lazy.weight());
+            assert_eq!(eager.edges().count(), lazy.edges().count());
+        }
+    }
+}
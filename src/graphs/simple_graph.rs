@@ -0,0 +1,225 @@
+//! # An undirected graph with no self-loops and no parallel edges.
+//!
+//! `Graph` is a multigraph: `add_edge` accepts both. `SimpleGraph` rejects them at insertion,
+//! and dereferences to the underlying `Graph`, so it can be passed to any algorithm on `Graph`.
+//!
+//! What depends on simplicity:
+//!
+//! * `interop::to_graph6` encodes an adjacency matrix, so it drops self-loops and collapses
+//!   parallel edges; it round-trips only simple graphs.
+//! * `Graph::degree` counts a self-loop twice and each parallel edge separately,
+//!   so it is the number of neighbors only in a simple graph.
+//! * `Cycle` and `Bipartite` accept any graph: a self-loop or a pair of parallel edges
+//!   is reported as a cycle, and a self-loop is an odd cycle.
+
+use std::collections::HashSet;
+use std::fmt;
+use std::ops::Deref;
+
+use super::graph::Graph;
+
+/// Why an edge cannot be added to a `SimpleGraph`.
+#[derive(Debug, PartialEq, Eq)]
+pub enum SimpleGraphError {
+    /// The edge v-v.
+    SelfLoop(usize),
+    /// The graph already has the edge v-w.
+    ParallelEdge(usize, usize),
+    /// A vertex is not between 0 and `v - 1`.
+    VertexOutOfRange { vertex: usize, v: usize },
+}
+
+impl fmt::Display for SimpleGraphError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SimpleGraphError::SelfLoop(v) => write!(f, "self-loop {}-{}", v, v),
+            SimpleGraphError::ParallelEdge(v, w) => write!(f, "parallel edge {}-{}", v, w),
+            SimpleGraphError::VertexOutOfRange { vertex, v } => {
+                write!(f, "vertex {} is not between 0 and {}", vertex, v)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SimpleGraphError {}
+
+pub struct SimpleGraph {
+    graph: Graph,                   // the underlying graph
+    edges: HashSet<(usize, usize)>, // each edge v-w as (min, max)
+}
+
+impl SimpleGraph {
+    pub fn new(v: usize) -> Self {
+        SimpleGraph {
+            graph: Graph::new(v),
+            edges: HashSet::new(),
+        }
+    }
+
+    /// Adds the edge v-w, unless it is a self-loop or the graph already has it.
+    pub fn try_add_edge(&mut self, v: usize, w: usize) -> Result<(), SimpleGraphError> {
+        for vertex in [v, w] {
+            if vertex >= self.graph.v() {
+                return Err(SimpleGraphError::VertexOutOfRange {
+                    vertex,
+                    v: self.graph.v(),
+                });
+            }
+        }
+        if v == w {
+            return Err(SimpleGraphError::SelfLoop(v));
+        }
+        if !self.edges.insert((v.min(w), v.max(w))) {
+            return Err(SimpleGraphError::ParallelEdge(v, w));
+        }
+        self.graph.add_edge(v, w);
+        Ok(())
+    }
+
+    /// Is there an edge v-w? It takes constant time.
+    pub fn has_edge(&self, v: usize, w: usize) -> bool {
+        self.edges.contains(&(v.min(w), v.max(w)))
+    }
+
+    pub fn graph(&self) -> &Graph {
+        &self.graph
+    }
+
+    pub fn into_graph(self) -> Graph {
+        self.graph
+    }
+}
+
+impl Deref for SimpleGraph {
+    type Target = Graph;
+
+    fn deref(&self) -> &Self::Target {
+        &self.graph
+    }
+}
+
+/// Fails on the first self-loop or parallel edge; see `Graph::to_simple` to drop them instead.
+impl TryFrom<Graph> for SimpleGraph {
+    type Error = SimpleGraphError;
+
+    fn try_from(g: Graph) -> Result<Self, Self::Error> {
+        let mut simple = SimpleGraph::new(g.v());
+        for v in 0..g.v() {
+            for &w in g.adj(v) {
+                // each edge appears in both lists; add it from its smaller endpoint
+                if v < w {
+                    simple.try_add_edge(v, w)?;
+                } else if v == w {
+                    return Err(SimpleGraphError::SelfLoop(v));
+                }
+            }
+        }
+        Ok(simple)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::graphs::{bipartite::Bipartite, cc::CC, cycle::Cycle, interop};
+
+    #[test]
+    fn reject() {
+        let mut sg = SimpleGraph::new(3);
+        assert_eq!(sg.try_add_edge(0, 1), Ok(()));
+        assert_eq!(sg.try_add_edge(1, 1), Err(SimpleGraphError::SelfLoop(1)));
+        assert_eq!(
+            sg.try_add_edge(1, 0),
+            Err(SimpleGraphError::ParallelEdge(1, 0))
+        );
+        assert_eq!(
+            sg.try_add_edge(0, 3),
+            Err(SimpleGraphError::VertexOutOfRange { vertex: 3, v: 3 })
+        );
+        assert_eq!(sg.try_add_edge(2, 1), Ok(()));
+        // rejected edges are not added
+        assert_eq!(sg.e(), 2);
+        assert_eq!(sg.degree(1), 2);
+        assert!(sg.has_edge(1, 2));
+        assert!(!sg.has_edge(0, 2));
+        assert!(sg.is_simple());
+    }
+
+    #[test]
+    fn try_from() {
+        let mut g = Graph::new(3);
+        g.add_edge(0, 1);
+        g.add_edge(1, 2);
+        let sg = SimpleGraph::try_from(g).unwrap();
+        assert_eq!(sg.e(), 2);
+
+        let mut g = Graph::new(3);
+        g.add_edge(0, 1);
+        g.add_edge(1, 0);
+        assert_eq!(
+            SimpleGraph::try_from(g).err(),
+            Some(SimpleGraphError::ParallelEdge(0, 1))
+        );
+
+        let mut g = Graph::new(3);
+        g.add_edge(2, 2);
+        assert_eq!(
+            SimpleGraph::try_from(g).err(),
+            Some(SimpleGraphError::SelfLoop(2))
+        );
+    }
+
+    #[test]
+    fn same_results_as_graph() {
+        let edges = [(0, 5), (4, 3), (0, 1), (9, 12), (6, 4), (5, 4), (0, 2)];
+        let mut g = Graph::new(13);
+        let mut sg = SimpleGraph::new(13);
+        for (v, w) in edges {
+            g.add_edge(v, w);
+            sg.try_add_edge(v, w).unwrap();
+        }
+
+        let (cc, simple_cc) = (CC::new(&g), CC::new(&sg));
+        assert_eq!(cc.count(), simple_cc.count());
+        for v in 0..13 {
+            assert_eq!(cc.id(v), simple_cc.id(v));
+            assert_eq!(g.adj(v), sg.adj(v));
+        }
+        assert_eq!(
+            Bipartite::new(&g).is_bipartite(),
+            Bipartite::new(&sg).is_bipartite()
+        );
+        assert_eq!(Cycle::new(&g).has_cycle(), Cycle::new(&sg).has_cycle());
+        assert_eq!(interop::to_graph6(&g), interop::to_graph6(&sg));
+    }
+
+    #[test]
+    fn graph6_needs_simple() {
+        let mut sg = SimpleGraph::new(4);
+        for (v, w) in [(0, 1), (1, 2), (2, 3), (3, 0)] {
+            sg.try_add_edge(v, w).unwrap();
+        }
+        let copy = interop::from_graph6(&interop::to_graph6(&sg)).unwrap();
+        assert_eq!(copy.e(), sg.e());
+
+        // a self-loop and a parallel edge are lost
+        let mut g = sg.into_graph();
+        g.add_edge(0, 0);
+        g.add_edge(1, 0);
+        let copy = interop::from_graph6(&interop::to_graph6(&g)).unwrap();
+        assert_eq!(copy.e(), 4);
+        assert_eq!(g.e(), 6);
+    }
+
+    #[test]
+    fn cycles_in_multigraph() {
+        let mut g = Graph::new(2);
+        g.add_edge(0, 1);
+        g.add_edge(0, 1);
+        assert!(Cycle::new(&g).has_cycle());
+        assert!(Bipartite::new(&g).is_bipartite());
+
+        g.add_edge(1, 1);
+        assert!(!Bipartite::new(&g).is_bipartite());
+    }
+}
@@ -1,58 +1,90 @@
 //! # A digraph, where the vertex names are arbitrary strings.
 
-use std::collections::HashMap;
+use std::io::{self, BufRead};
 
 use super::digraph::Digraph;
-pub struct SymbolDigraph<'a> {
-    st: HashMap<&'a str, usize>, // string -> index
-    keys: Vec<&'a str>,          // index -> string
-    dg: Digraph,                 // underlying digraph
+use crate::fundamentals::interner::StringInterner;
+pub struct SymbolDigraph {
+    interner: StringInterner, // string <-> index
+    dg: Digraph,              // underlying digraph
 }
 
-impl<'a> SymbolDigraph<'a> {
-    pub fn new(data: Vec<&'a str>, delimiter: &str) -> Self {
-        let mut st = HashMap::new();
-        for &line in &data {
-            let a: Vec<&str> = line.split(delimiter).collect();
-            for item in a {
-                if !st.contains_key(item) {
-                    st.insert(item, st.len());
-                }
-            }
-        }
+impl SymbolDigraph {
+    pub fn new<S: Into<String>>(data: Vec<S>, delimiter: &str) -> Self {
+        Self::with_interner(data, delimiter, StringInterner::new())
+    }
 
-        // inverted index
-        let mut keys = vec![""; st.len()];
-        for (&k, &v) in &st {
-            keys[v] = k;
+    /// Builds the graph on top of an existing interner, so that a name has the same index
+    /// in every graph sharing it. Names already in `interner` but not in `data` are isolated vertices.
+    pub fn with_interner<S: Into<String>>(
+        data: Vec<S>,
+        delimiter: &str,
+        mut interner: StringInterner,
+    ) -> Self {
+        let data: Vec<String> = data.into_iter().map(Into::into).collect();
+        for line in &data {
+            for item in line.split(delimiter) {
+                interner.intern(item);
+            }
         }
 
         // second pass to build graph
-        let mut dg = Digraph::new(st.len());
-        for line in data {
-            let a: Vec<&str> = line.split(delimiter).collect();
-            let v = st[a[0]];
-            for &name in &a[1..] {
-                dg.add_edge(v, st[name]);
+        let mut dg = Digraph::new(interner.len());
+        for line in &data {
+            let mut names = line
+                .split(delimiter)
+                .map(|name| interner.get(name).unwrap() as usize);
+            let v = names.next().unwrap();
+            for w in names {
+                dg.add_edge(v, w);
             }
         }
 
-        SymbolDigraph { st, keys, dg }
+        SymbolDigraph { interner, dg }
+    }
+
+    /// Reads one line per vertex and its neighbors, separated by `delimiter`; blank lines are skipped.
+    pub fn from_reader(r: impl BufRead, delimiter: &str) -> io::Result<Self> {
+        let mut data = Vec::new();
+        for line in r.lines() {
+            let line = line?;
+            if !line.is_empty() {
+                data.push(line);
+            }
+        }
+        Ok(Self::new(data, delimiter))
     }
 
     /// Does the graph contain the vertex named `s`?
     pub fn contains(&self, s: &str) -> bool {
-        self.st.contains_key(s)
+        self.interner.get(s).is_some()
     }
 
     /// Returns the integer associated with the vertex named `s`.
     pub fn index_of(&self, s: &str) -> Option<usize> {
-        self.st.get(s).copied()
+        self.interner.get(s).map(|v| v as usize)
     }
 
     /// Returns the name of the vertex associated with the integer `v`
     pub fn name_of(&self, v: usize) -> &str {
-        self.keys[v]
+        self.interner.resolve(v as u32)
+    }
+
+    /// Returns the name of the vertex `v`, or `None` if there is no such vertex.
+    pub fn try_name_of(&self, v: usize) -> Option<&str> {
+        u32::try_from(v)
+            .ok()
+            .and_then(|id| self.interner.try_resolve(id))
+    }
+
+    /// Returns the outdegree of the vertex named `name`, or `None` if there is no such vertex.
+    pub fn degree_of(&self, name: &str) -> Option<usize> {
+        self.index_of(name).map(|v| self.dg.out_degree(v))
+    }
+
+    /// Returns the interner naming the vertices; clone it to build another digraph over the same names.
+    pub fn interner(&self) -> &StringInterner {
+        &self.interner
     }
 
     pub fn digraph(&self) -> &Digraph {
@@ -87,4 +119,51 @@ mod test {
             assert_eq!(result, vec!["ATL", "MCO", "ORD"]);
         }
     }
+
+    #[test]
+    fn from_reader() {
+        let input = "JFK MCO\nORD DEN\n\nJFK ORD\n";
+        let sg = SymbolDigraph::from_reader(io::Cursor::new(input.as_bytes()), " ").unwrap();
+        assert_eq!(sg.digraph().v(), 4);
+        assert_eq!(sg.digraph().e(), 3);
+
+        let v = sg.index_of("ORD").unwrap();
+        let result = sg
+            .digraph()
+            .adj(v)
+            .clone()
+            .into_iter()
+            .map(|w| sg.name_of(w))
+            .collect::<Vec<&str>>();
+        assert_eq!(result, vec!["DEN"]);
+        // edges are directed
+        assert!(sg.digraph().adj(sg.index_of("DEN").unwrap()).is_empty());
+    }
+
+    #[test]
+    fn untrusted_lookups() {
+        let sg = SymbolDigraph::new(vec!["JFK MCO", "ORD DEN", "JFK ORD", "JFK ATL"], " ");
+        assert_eq!(sg.try_name_of(0), Some("JFK"));
+        assert_eq!(sg.try_name_of(4), Some("ATL"));
+        assert_eq!(sg.try_name_of(5), None);
+        assert_eq!(sg.try_name_of(usize::MAX), None);
+
+        assert_eq!(sg.degree_of("JFK"), Some(3));
+        assert_eq!(sg.degree_of("ORD"), Some(1));
+        assert_eq!(sg.degree_of("MCO"), Some(0));
+        assert_eq!(sg.degree_of("LAX"), None);
+        assert_eq!(sg.degree_of(""), None);
+    }
+
+    #[test]
+    fn shared_interner() {
+        let sg1 = SymbolDigraph::new(vec!["JFK MCO", "ORD DEN"], " ");
+        let sg2 = SymbolDigraph::with_interner(vec!["DEN ORD LAS"], " ", sg1.interner().clone());
+        assert_eq!(sg1.index_of("DEN"), sg2.index_of("DEN"));
+        assert_eq!(sg1.index_of("ORD"), sg2.index_of("ORD"));
+        assert_eq!(sg2.name_of(4), "LAS");
+        assert_eq!(sg2.digraph().v(), 5);
+        let den = sg2.index_of("DEN").unwrap();
+        assert_eq!(sg2.digraph().adj(den).len(), 2);
+    }
 }
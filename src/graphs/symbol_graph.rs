@@ -1,65 +1,98 @@
 //! # An undirected graph, where the vertex names are arbitrary strings.
 
-use std::collections::HashMap;
+use std::io::{self, BufRead};
 
+use super::bfs_paths::BreadFirstPaths;
 use super::graph::Graph;
-pub struct SymbolGraph<'a> {
-    st: HashMap<&'a str, usize>, // string -> index
-    keys: Vec<&'a str>,          // index -> string
-    graph: Graph,                // the underlying graph
+use crate::fundamentals::interner::StringInterner;
+pub struct SymbolGraph {
+    interner: StringInterner, // string <-> index
+    graph: Graph,             // the underlying graph
 }
 
-impl<'a> SymbolGraph<'a> {
-    pub fn new(data: Vec<&'a str>, delimiter: &str) -> SymbolGraph<'a> {
-        let mut st = HashMap::new();
-        for &line in &data {
-            let a: Vec<&str> = line.split(delimiter).collect();
-            for item in a {
-                if !st.contains_key(item) {
-                    st.insert(item, st.len());
-                }
-            }
-        }
+impl SymbolGraph {
+    pub fn new<S: Into<String>>(data: Vec<S>, delimiter: &str) -> SymbolGraph {
+        Self::with_interner(data, delimiter, StringInterner::new())
+    }
 
-        // inverted index to get string keys in a vector
-        let mut keys = vec![""; st.len()];
-        for (&k, &v) in &st {
-            keys[v] = k;
+    /// Builds the graph on top of an existing interner, so that a name has the same index
+    /// in every graph sharing it. Names already in `interner` but not in `data` are isolated vertices.
+    pub fn with_interner<S: Into<String>>(
+        data: Vec<S>,
+        delimiter: &str,
+        mut interner: StringInterner,
+    ) -> SymbolGraph {
+        let data: Vec<String> = data.into_iter().map(Into::into).collect();
+        for line in &data {
+            for item in line.split(delimiter) {
+                interner.intern(item);
+            }
         }
 
         // second pass to build graph
-        let mut graph = Graph::new(st.len());
-        for line in data {
-            let a: Vec<&str> = line.split(delimiter).collect();
-            let v = st[a[0]];
-            for &name in &a[1..] {
-                graph.add_edge(v, st[name]);
+        let mut graph = Graph::new(interner.len());
+        for line in &data {
+            let mut names = line
+                .split(delimiter)
+                .map(|name| interner.get(name).unwrap() as usize);
+            let v = names.next().unwrap();
+            for w in names {
+                graph.add_edge(v, w);
             }
         }
 
-        SymbolGraph { st, keys, graph }
+        SymbolGraph { interner, graph }
+    }
+
+    /// Reads one line per vertex and its neighbors, separated by `delimiter`; blank lines are skipped.
+    pub fn from_reader(r: impl BufRead, delimiter: &str) -> io::Result<SymbolGraph> {
+        let mut data = Vec::new();
+        for line in r.lines() {
+            let line = line?;
+            if !line.is_empty() {
+                data.push(line);
+            }
+        }
+        Ok(Self::new(data, delimiter))
     }
 
     /// Does the graph contain the vertex named `s`?
     pub fn contains(&self, s: &str) -> bool {
-        self.st.contains_key(s)
+        self.interner.get(s).is_some()
     }
 
     /// Returns the integer associated with the vertex named `s`.
     pub fn index_of(&self, s: &str) -> Option<usize> {
-        self.st.get(s).copied()
+        self.interner.get(s).map(|v| v as usize)
     }
 
     /// Returns the name of the vertex associated with the integer `v`
     pub fn name_of(&self, v: usize) -> &str {
         self.validate_vertex(v);
-        self.keys[v]
+        self.interner.resolve(v as u32)
+    }
+
+    /// Returns the interner naming the vertices; clone it to build another graph over the same names.
+    pub fn interner(&self) -> &StringInterner {
+        &self.interner
     }
 
     pub fn graph(&self) -> &Graph {
         &self.graph
     }
 
+    /// Returns the names on a path with the fewest edges from `src` to `dst`,
+    /// or `None` if either name is absent or there is no path.
+    pub fn bfs_path_by_name(&self, src: &str, dst: &str) -> Option<Vec<&str>> {
+        let s = self.index_of(src)?;
+        let t = self.index_of(dst)?;
+        let bfs = BreadFirstPaths::new(&self.graph, s);
+        if !bfs.has_path_to(t) {
+            return None;
+        }
+        Some(bfs.path_to(t).map(|v| self.name_of(v)).collect())
+    }
+
     fn validate_vertex(&self, v: usize) {
         if v >= self.graph.v() {
             panic!("vertex {} is not between 0 and {}", v, self.graph.v());
@@ -102,4 +135,69 @@ mod test {
         adjs.sort_unstable();
         assert_eq!(adjs, vec!["LAS", "PHX"]);
     }
+
+    #[test]
+    fn bfs_path_by_name() {
+        let data = vec![
+            "JFK MCO", "ORD DEN", "ORD HOU", "DFW PHX", "JFK ATL", "ORD DFW", "ORD PHX", "ATL HOU",
+            "DEN PHX", "PHX LAX", "JFK ORD", "DEN LAS", "DFW HOU", "ORD ATL", "LAS LAX", "ATL MCO",
+            "HOU MCO", "LAS PHX", "SFO SEA",
+        ];
+        let sg = SymbolGraph::new(data, " ");
+        assert_eq!(
+            sg.bfs_path_by_name("JFK", "LAS"),
+            Some(vec!["JFK", "ORD", "DEN", "LAS"])
+        );
+        assert_eq!(
+            sg.bfs_path_by_name("LAX", "MCO").map(|path| path.len()),
+            Some(5)
+        );
+        assert_eq!(sg.bfs_path_by_name("DFW", "DFW"), Some(vec!["DFW"]));
+        assert_eq!(sg.bfs_path_by_name("JFK", "SEA"), None);
+        assert_eq!(sg.bfs_path_by_name("JFK", "LAB"), None);
+        assert_eq!(sg.bfs_path_by_name("LAB", "JFK"), None);
+    }
+
+    #[test]
+    fn from_reader() {
+        let input = "JFK MCO\nORD DEN\n\nJFK ORD\nJFK ATL\n";
+        let sg = SymbolGraph::from_reader(io::Cursor::new(input), " ").unwrap();
+        assert_eq!(sg.graph().v(), 5);
+        assert_eq!(sg.graph().e(), 4);
+
+        let v = sg.index_of("JFK").unwrap();
+        assert_eq!(sg.name_of(v), "JFK");
+        let mut adjs = sg
+            .graph()
+            .adj(v)
+            .clone()
+            .into_iter()
+            .map(|w| sg.name_of(w))
+            .collect::<Vec<&str>>();
+        adjs.sort_unstable();
+        assert_eq!(adjs, vec!["ATL", "MCO", "ORD"]);
+    }
+
+    #[test]
+    fn shared_interner() {
+        let sg1 = SymbolGraph::new(vec!["JFK MCO", "ORD DEN", "JFK ORD"], " ");
+        let sg2 =
+            SymbolGraph::with_interner(vec!["DEN LAS", "MCO JFK"], " ", sg1.interner().clone());
+
+        // names keep their index, and new names come after the old ones
+        for name in ["JFK", "MCO", "ORD", "DEN"] {
+            assert_eq!(sg1.index_of(name), sg2.index_of(name));
+        }
+        assert!(!sg1.contains("LAS"));
+        assert_eq!(sg2.index_of("LAS"), Some(4));
+        assert_eq!(sg2.graph().v(), 5);
+        assert_eq!(sg2.graph().e(), 2);
+        // ORD is in the interner but has no edges in the second graph
+        assert!(sg2.graph().adj(sg2.index_of("ORD").unwrap()).is_empty());
+        assert_eq!(sg2.interner().len(), 5);
+        assert_eq!(
+            sg2.interner().iter().collect::<Vec<(u32, &str)>>(),
+            vec![(0, "JFK"), (1, "MCO"), (2, "ORD"), (3, "DEN"), (4, "LAS")]
+        );
+    }
 }
@@ -0,0 +1,186 @@
+//! # Determining the strong components in a digraph.
+//!
+//! This implementation uses Tarjan's algorithm: a single depth-first search that
+//! keeps the visited vertices on a stack, and pops a strong component when it finishes
+//! a vertex v whose low-link `low[v]` (the lowest discovery time reachable from v's subtree
+//! through vertices still on the stack) equals its own discovery time `disc[v]`.
+//! The time complexity is O(V + E).
+
+use super::digraph::Digraph;
+pub struct TarjanSCC {
+    on_stack: Vec<bool>, // is v on the stack?
+    disc: Vec<usize>,    // disc[v] = discovery time of v
+    low: Vec<usize>,     // low[v] = low-link of v
+    id: Vec<usize>,      // component identifiers
+    stack: Vec<usize>,   // vertices of unfinished components
+    time: usize,         // number of vertices discovered so far
+    count: usize,        // number of strong components
+}
+
+impl TarjanSCC {
+    pub fn new(g: &Digraph) -> Self {
+        let mut scc = TarjanSCC {
+            on_stack: vec![false; g.v()],
+            disc: vec![usize::MAX; g.v()],
+            low: vec![usize::MAX; g.v()],
+            id: vec![0; g.v()],
+            stack: Vec::new(),
+            time: 0,
+            count: 0,
+        };
+        for s in 0..g.v() {
+            if scc.disc[s] == usize::MAX {
+                scc.dfs(g, s);
+            }
+        }
+        scc
+    }
+
+    fn discover(&mut self, v: usize) {
+        self.disc[v] = self.time;
+        self.low[v] = self.time;
+        self.time += 1;
+        self.stack.push(v);
+        self.on_stack[v] = true;
+    }
+
+    // iterative, so that long paths do not overflow the call stack
+    fn dfs(&mut self, g: &Digraph, s: usize) {
+        // (v, index of the next neighbor of v to examine)
+        let mut path = vec![(s, 0)];
+        self.discover(s);
+        while let Some((v, i)) = path.last_mut() {
+            let v = *v;
+            if let Some(&w) = g.adj(v).get(*i) {
+                *i += 1;
+                if self.disc[w] == usize::MAX {
+                    self.discover(w);
+                    path.push((w, 0));
+                } else if self.on_stack[w] {
+                    self.low[v] = self.low[v].min(self.disc[w]);
+                }
+                continue;
+            }
+
+            path.pop();
+            if let Some(&(parent, _)) = path.last() {
+                self.low[parent] = self.low[parent].min(self.low[v]);
+            }
+            if self.low[v] == self.disc[v] {
+                // v is the root of a strong component
+                loop {
+                    let w = self.stack.pop().unwrap();
+                    self.on_stack[w] = false;
+                    self.id[w] = self.count;
+                    if w == v {
+                        break;
+                    }
+                }
+                self.count += 1;
+            }
+        }
+    }
+
+    /// Are v and w strongly connected?
+    pub fn strongly_connected(&self, v: usize, w: usize) -> bool {
+        self.id[v] == self.id[w]
+    }
+
+    /// Returns the identifier of the strong component of v
+    pub fn id(&self, v: usize) -> usize {
+        self.id[v]
+    }
+
+    /// Returns the number of strong components
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    use super::*;
+    use crate::graphs::kosaraju_scc::KosarajuSCC;
+
+    fn assert_same(g: &Digraph) {
+        let tarjan = TarjanSCC::new(g);
+        let kosaraju = KosarajuSCC::new(g);
+        assert_eq!(tarjan.count(), kosaraju.count());
+        for v in 0..g.v() {
+            for w in 0..g.v() {
+                assert_eq!(
+                    tarjan.strongly_connected(v, w),
+                    kosaraju.strongly_connected(v, w)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn tiny_dg() {
+        let mut digraph = Digraph::new(13);
+        digraph.add_edge(4, 2);
+        digraph.add_edge(2, 3);
+        digraph.add_edge(3, 2);
+        digraph.add_edge(6, 0);
+        digraph.add_edge(0, 1);
+        digraph.add_edge(2, 0);
+        digraph.add_edge(11, 12);
+        digraph.add_edge(12, 9);
+        digraph.add_edge(9, 10);
+        digraph.add_edge(9, 11);
+        digraph.add_edge(8, 9);
+        digraph.add_edge(10, 12);
+        digraph.add_edge(11, 4);
+        digraph.add_edge(4, 3);
+        digraph.add_edge(3, 5);
+        digraph.add_edge(7, 8);
+        digraph.add_edge(8, 7);
+        digraph.add_edge(5, 4);
+        digraph.add_edge(0, 5);
+        digraph.add_edge(6, 4);
+        digraph.add_edge(6, 9);
+        digraph.add_edge(7, 6);
+
+        let scc = TarjanSCC::new(&digraph);
+        assert_eq!(scc.count(), 5);
+        assert!(scc.strongly_connected(0, 2));
+        assert!(scc.strongly_connected(4, 5));
+        assert!(!scc.strongly_connected(0, 1));
+        assert!(scc.strongly_connected(7, 8));
+        assert!(!scc.strongly_connected(0, 7));
+        // components are found in reverse topological order, so a sink comes first
+        assert_eq!(scc.id(1), 0);
+
+        assert_same(&digraph);
+    }
+
+    #[test]
+    fn random() {
+        let mut rng = StdRng::seed_from_u64(50);
+        for _ in 0..20 {
+            let n = 15;
+            let mut digraph = Digraph::new(n);
+            for _ in 0..rng.gen_range(10..30) {
+                digraph.add_edge(rng.gen_range(0..n), rng.gen_range(0..n));
+            }
+            assert_same(&digraph);
+        }
+    }
+
+    #[test]
+    fn long_chain() {
+        let n = 500_000;
+        let mut digraph = Digraph::new(n);
+        for v in 0..n - 1 {
+            digraph.add_edge(v, v + 1);
+        }
+        assert_eq!(TarjanSCC::new(&digraph).count(), n);
+
+        digraph.add_edge(n - 1, 0);
+        assert_eq!(TarjanSCC::new(&digraph).count(), 1);
+    }
+}
@@ -7,7 +7,8 @@
 
 use super::{
     dfs_order::DepthFirstOrder, digraph::Digraph, directed_cycle::DirectedCycle,
-    weighted_digraph::EdgeWeightedDiagraph, weighted_directed_cycle::EdgeWeightedDirectedCycle,
+    directed_edge::DirectedEdge, weighted_digraph::EdgeWeightedDiagraph,
+    weighted_directed_cycle::EdgeWeightedDirectedCycle,
 };
 pub struct Topological {
     order: Vec<usize>, // topological order
@@ -15,45 +16,62 @@ pub struct Topological {
 }
 
 impl Topological {
+    /// Computes a topological order, which is empty if the digraph has a cycle.
     pub fn new(g: &Digraph) -> Self {
+        Self::try_new(g).unwrap_or_else(|_| Topological::empty())
+    }
+
+    /// Computes a topological order, or returns a directed cycle as its vertices,
+    /// the first and last being the same, if the digraph is not a DAG.
+    pub fn try_new(g: &Digraph) -> Result<Self, Vec<usize>> {
         let finder = DirectedCycle::new(g);
-        let mut order = vec![];
-        let mut rank = vec![];
-        if !finder.has_cycle() {
-            let dfs = DepthFirstOrder::new(g);
-            order = dfs.rev_post().collect::<Vec<usize>>();
-            rank = vec![0; g.v()];
-            for (i, v) in order.iter().enumerate() {
-                rank[*v] = i;
-            }
+        if finder.has_cycle() {
+            return Err(DirectedCycle::cycle(&finder).collect());
         }
-        Topological { order, rank }
+        let dfs = DepthFirstOrder::new(g);
+        Ok(Topological::from_order(dfs.rev_post().collect(), g.v()))
     }
 
+    /// Computes a topological order, which is empty if the edge-weighted digraph has a cycle.
     pub fn from_weighted_diagraph(g: &EdgeWeightedDiagraph) -> Self {
+        Self::try_from_weighted_diagraph(g).unwrap_or_else(|_| Topological::empty())
+    }
+
+    /// Computes a topological order, or returns the edges of a directed cycle
+    /// if the edge-weighted digraph is not a DAG.
+    pub fn try_from_weighted_diagraph(g: &EdgeWeightedDiagraph) -> Result<Self, Vec<DirectedEdge>> {
         let finder = EdgeWeightedDirectedCycle::new(g);
-        let mut order = vec![];
-        let mut rank = vec![];
-        if !finder.has_cycle() {
-            let dfs = DepthFirstOrder::from_weighted_diagraph(g);
-            order = dfs.rev_post().collect::<Vec<usize>>();
-            rank = vec![0; g.v()];
-            for (i, v) in order.iter().enumerate() {
-                rank[*v] = i;
-            }
+        if finder.has_cycle() {
+            return Err(EdgeWeightedDirectedCycle::cycle(&finder).collect());
         }
+        let dfs = DepthFirstOrder::from_weighted_diagraph(g);
+        Ok(Topological::from_order(dfs.rev_post().collect(), g.v()))
+    }
 
+    fn from_order(order: Vec<usize>, v: usize) -> Self {
+        let mut rank = vec![0; v];
+        for (i, &v) in order.iter().enumerate() {
+            rank[v] = i;
+        }
         Topological { order, rank }
     }
 
+    fn empty() -> Self {
+        Topological {
+            order: vec![],
+            rank: vec![],
+        }
+    }
+
     /// Does the digraph have a topological order?
     pub fn has_order(&self) -> bool {
         !self.order.is_empty()
     }
 
     /// Returns a topological order if the digraph has a topologial order
-    pub fn order(&self) -> std::vec::IntoIter<usize> {
-        self.order.clone().into_iter()
+    /// The iterator borrows the order, so calling this does not allocate.
+    pub fn order(&self) -> std::iter::Copied<std::slice::Iter<'_, usize>> {
+        self.order.iter().copied()
     }
 
     /// The the rank of vertex
@@ -97,6 +115,42 @@ mod test {
             topological.order().collect::<Vec<usize>>(),
             vec![8, 7, 2, 3, 0, 5, 1, 6, 9, 10, 11, 12, 4]
         );
+
+        let topological = Topological::try_new(&dag).unwrap();
+        // both iterators borrow the same order
+        let (first, second) = (topological.order(), topological.order());
+        assert!(first.eq(second));
+        assert_eq!(
+            topological.order().rev().take(2).collect::<Vec<usize>>(),
+            vec![4, 12]
+        );
+        assert_eq!(topological.rank(8), Some(0));
+    }
+
+    #[test]
+    fn try_new_cycle() {
+        let mut g = Digraph::new(6);
+        g.add_edge(0, 1);
+        g.add_edge(1, 2);
+        g.add_edge(2, 3);
+        g.add_edge(3, 1);
+        g.add_edge(3, 4);
+        let cycle = Topological::try_new(&g).err().unwrap();
+        assert_eq!(cycle, vec![3, 1, 2, 3]);
+
+        let topological = Topological::new(&g);
+        assert!(!topological.has_order());
+        assert_eq!(topological.order().count(), 0);
+        assert_eq!(topological.rank(0), None);
+
+        let mut g = EdgeWeightedDiagraph::new(3);
+        g.add_edge(DirectedEdge::new(0, 1, 0.5));
+        g.add_edge(DirectedEdge::new(1, 2, 0.25));
+        g.add_edge(DirectedEdge::new(2, 1, 0.75));
+        let cycle = Topological::try_from_weighted_diagraph(&g).err().unwrap();
+        let hops: Vec<(usize, usize)> = cycle.iter().map(|e| (e.from(), e.to())).collect();
+        assert_eq!(hops, vec![(1, 2), (2, 1)]);
+        assert!(!Topological::from_weighted_diagraph(&g).has_order());
     }
 
     #[test]
@@ -137,4 +191,24 @@ mod test {
             ]
         );
     }
+
+    #[test]
+    fn long_chain() {
+        // deep enough to overflow the stack with a recursive search
+        let n = 500_000;
+        let mut g = Digraph::new(n);
+        for v in 0..n - 1 {
+            g.add_edge(v, v + 1);
+        }
+        let topological = Topological::new(&g);
+        assert!(topological.has_order());
+        assert!(topological.order().eq(0..n));
+
+        let mut g = EdgeWeightedDiagraph::new(n);
+        for v in 0..n - 1 {
+            g.add_edge(DirectedEdge::new(n - 1 - v, n - 2 - v, 1.0));
+        }
+        let topological = Topological::from_weighted_diagraph(&g);
+        assert!(topological.order().eq((0..n).rev()));
+    }
 }
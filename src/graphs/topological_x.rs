@@ -3,9 +3,11 @@
 //! A digraph has a topological order if and only if it is a DAG.
 //! This implementation is queue-based in a non-recursive way.
 //!
-//! Note the result relies on the order of adj.
+//! Note the result relies on the order of adj, unless ties are broken
+//! by a priority with `with_priority`.
 
-use super::digraph::Digraph;
+use super::{digraph::Digraph, symbol_digraph::SymbolDigraph};
+use crate::sorting::min_pq::MinPQ;
 use std::collections::VecDeque;
 
 pub struct TopologicalX {
@@ -44,12 +46,66 @@ impl TopologicalX {
         TopologicalX { order, ranks }
     }
 
+    /// Among the ready vertices (indegree 0), always picks the one with the
+    /// smallest `priority`, breaking equal priorities by the smaller vertex.
+    /// The resulting order is deterministic regardless of the order of adj.
+    pub fn with_priority<P, F>(g: &Digraph, priority: F) -> Self
+    where
+        P: Ord,
+        F: Fn(usize) -> P,
+    {
+        let mut in_degree: Vec<usize> = (0..g.v()).map(|v| g.in_degree(v)).collect();
+
+        let mut order = Vec::new();
+        let mut ranks = vec![0; g.v()];
+
+        // each key is computed once; the queue then holds the position of a vertex
+        // in (priority, vertex) order, so that `P` need not be `Copy`
+        let keys: Vec<P> = (0..g.v()).map(priority).collect();
+        let mut by_priority: Vec<usize> = (0..g.v()).collect();
+        by_priority.sort_by(|&v, &w| keys[v].cmp(&keys[w]).then(v.cmp(&w)));
+        let mut position = vec![0; g.v()];
+        for (i, &v) in by_priority.iter().enumerate() {
+            position[v] = i;
+        }
+
+        // the ready set is a min priority queue instead of a FIFO queue
+        let mut pq = MinPQ::empty();
+        for v in (0..g.v()).filter(|v| in_degree[*v] == 0) {
+            pq.insert(position[v]);
+        }
+
+        while let Some(i) = pq.del_min() {
+            let v = by_priority[i];
+            ranks[v] = order.len();
+            order.push(v);
+            for &w in g.adj(v) {
+                in_degree[w] -= 1;
+                if in_degree[w] == 0 {
+                    pq.insert(position[w]);
+                }
+            }
+        }
+        // there is a directed cycle
+        if order.len() != g.v() {
+            order.clear();
+            ranks.clear();
+        }
+
+        TopologicalX { order, ranks }
+    }
+
+    /// Topological order of a symbol digraph, picking ready vertices by name in lexicographic order.
+    pub fn from_symbol_digraph(sg: &SymbolDigraph) -> Self {
+        TopologicalX::with_priority(sg.digraph(), |v| sg.name_of(v))
+    }
+
     pub fn has_order(&self) -> bool {
         !self.order.is_empty()
     }
 
-    pub fn order(&self) -> std::vec::IntoIter<usize> {
-        self.order.clone().into_iter()
+    pub fn order(&self) -> std::iter::Copied<std::slice::Iter<'_, usize>> {
+        self.order.iter().copied()
     }
 
     pub fn rank(&self, v: usize) -> Option<usize> {
@@ -63,8 +119,42 @@ impl TopologicalX {
 
 #[cfg(test)]
 mod test {
+    use std::cmp::Reverse;
+
     use super::*;
 
+    fn is_topological(g: &Digraph, order: &[usize]) -> bool {
+        let mut rank = vec![usize::MAX; g.v()];
+        for (i, &v) in order.iter().enumerate() {
+            rank[v] = i;
+        }
+        order.len() == g.v() && (0..g.v()).all(|v| g.adj(v).iter().all(|&w| rank[v] < rank[w]))
+    }
+
+    fn tiny_dag() -> Digraph {
+        let mut dag = Digraph::new(13);
+        for (v, w) in [
+            (2, 3),
+            (0, 6),
+            (0, 1),
+            (2, 0),
+            (11, 12),
+            (9, 12),
+            (9, 11),
+            (9, 10),
+            (3, 5),
+            (8, 7),
+            (5, 4),
+            (0, 5),
+            (6, 4),
+            (6, 9),
+            (7, 6),
+        ] {
+            dag.add_edge(v, w);
+        }
+        dag
+    }
+
     #[test]
     fn dag() {
         let mut dag = Digraph::new(13);
@@ -121,4 +211,100 @@ mod test {
         let topological = TopologicalX::new(&digraph);
         assert!(!topological.has_order());
     }
+
+    #[test]
+    fn priority() {
+        let dag = tiny_dag();
+
+        let smallest_first = TopologicalX::with_priority(&dag, |v| v);
+        let order = smallest_first.order().collect::<Vec<usize>>();
+        assert!(is_topological(&dag, &order));
+        assert_eq!(order, vec![2, 0, 1, 3, 5, 8, 7, 6, 4, 9, 10, 11, 12]);
+
+        let largest_first = TopologicalX::with_priority(&dag, Reverse);
+        let order = largest_first.order().collect::<Vec<usize>>();
+        assert!(is_topological(&dag, &order));
+        assert_eq!(order, vec![8, 7, 2, 3, 0, 6, 9, 11, 12, 10, 5, 4, 1]);
+
+        // an owned, non-`Copy` priority; equal priorities fall back to the smaller vertex
+        let by_string = TopologicalX::with_priority(&dag, |v| Reverse((v / 2).to_string()));
+        let order = by_string.order().collect::<Vec<usize>>();
+        assert!(is_topological(&dag, &order));
+        assert_eq!(order, vec![8, 7, 2, 3, 0, 6, 9, 10, 11, 12, 5, 4, 1]);
+    }
+
+    #[test]
+    fn jobs_by_name() {
+        let data = vec![
+            "Algorithms/Theoretical CS/Databases/Scientific Computing",
+            "Introduction to CS/Advanced Programming/Algorithms",
+            "Advanced Programming/Scientific Computing",
+            "Scientific Computing/Computational Biology",
+            "Theoretical CS/Computational Biology/Artificial Intelligence",
+            "Linear Algebra/Theoretical CS",
+            "Calculus/Linear Algebra",
+            "Artificial Intelligence/Neural Networks/Robotics/Machine Learning",
+            "Machine Learning/Neural Networks",
+        ];
+
+        let sg = SymbolDigraph::new(data, "/");
+        let topological = TopologicalX::from_symbol_digraph(&sg);
+        let order = topological.order().collect::<Vec<usize>>();
+        assert!(is_topological(sg.digraph(), &order));
+        let names: Vec<&str> = order.iter().map(|&v| sg.name_of(v)).collect();
+        assert_eq!(
+            names,
+            vec![
+                "Calculus",
+                "Introduction to CS",
+                "Advanced Programming",
+                "Algorithms",
+                "Databases",
+                "Linear Algebra",
+                "Scientific Computing",
+                "Theoretical CS",
+                "Artificial Intelligence",
+                "Computational Biology",
+                "Machine Learning",
+                "Neural Networks",
+                "Robotics"
+            ]
+        );
+
+        let reversed = TopologicalX::with_priority(sg.digraph(), |v| Reverse(sg.name_of(v)));
+        let order = reversed.order().collect::<Vec<usize>>();
+        assert!(is_topological(sg.digraph(), &order));
+        let names: Vec<&str> = order.iter().map(|&v| sg.name_of(v)).collect();
+        assert_eq!(
+            names,
+            vec![
+                "Introduction to CS",
+                "Calculus",
+                "Linear Algebra",
+                "Algorithms",
+                "Theoretical CS",
+                "Databases",
+                "Artificial Intelligence",
+                "Robotics",
+                "Machine Learning",
+                "Neural Networks",
+                "Advanced Programming",
+                "Scientific Computing",
+                "Computational Biology"
+            ]
+        );
+    }
+
+    #[test]
+    fn priority_with_cycle() {
+        let mut digraph = Digraph::new(4);
+        digraph.add_edge(0, 1);
+        digraph.add_edge(1, 2);
+        digraph.add_edge(2, 1);
+        digraph.add_edge(2, 3);
+
+        let topological = TopologicalX::with_priority(&digraph, |v| v);
+        assert!(!topological.has_order());
+        assert_eq!(topological.rank(0), None);
+    }
 }
@@ -0,0 +1,98 @@
+//! # Transitive closure of a digraph
+//!
+//! Answers "is w reachable from v?" in constant time, after running a `DirectedDFS`
+//! from every vertex. This takes O(V (V + E)) time and O(V^2) space, so it suits
+//! small or dense digraphs that get many queries.
+
+use super::digraph::Digraph;
+use super::directed_dfs::DirectedDFS;
+use crate::fundamentals::bitset::BitSet;
+
+pub struct TransitiveClosure {
+    tc: Vec<BitSet>, // tc[v] = vertices reachable from v
+}
+
+impl TransitiveClosure {
+    pub fn new(g: &Digraph) -> Self {
+        TransitiveClosure {
+            tc: (0..g.v())
+                .map(|v| DirectedDFS::new(g, v).into_marked())
+                .collect(),
+        }
+    }
+
+    /// Is there a directed path from v to w? Every vertex reaches itself.
+    pub fn reachable(&self, v: usize, w: usize) -> bool {
+        self.validate_vertex(v);
+        self.validate_vertex(w);
+        self.tc[v].contains(w)
+    }
+
+    /// Returns the vertices reachable from v, in ascending order.
+    pub fn reachable_from(&self, v: usize) -> impl Iterator<Item = usize> + '_ {
+        self.validate_vertex(v);
+        self.tc[v].iter()
+    }
+
+    fn validate_vertex(&self, v: usize) {
+        if v >= self.tc.len() {
+            panic!("vertex {} is not between 0 and {}", v, self.tc.len());
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn tiny_dg() {
+        let mut digraph = Digraph::new(13);
+        digraph.add_edge(4, 2);
+        digraph.add_edge(2, 3);
+        digraph.add_edge(3, 2);
+        digraph.add_edge(6, 0);
+        digraph.add_edge(0, 1);
+        digraph.add_edge(2, 0);
+        digraph.add_edge(11, 12);
+        digraph.add_edge(12, 9);
+        digraph.add_edge(9, 10);
+        digraph.add_edge(9, 11);
+        digraph.add_edge(8, 9);
+        digraph.add_edge(10, 12);
+        digraph.add_edge(11, 4);
+        digraph.add_edge(4, 3);
+        digraph.add_edge(3, 5);
+        digraph.add_edge(7, 8);
+        digraph.add_edge(8, 7);
+        digraph.add_edge(5, 4);
+        digraph.add_edge(0, 5);
+        digraph.add_edge(6, 4);
+        digraph.add_edge(6, 9);
+        digraph.add_edge(7, 6);
+
+        let tc = TransitiveClosure::new(&digraph);
+        assert!(tc.reachable(0, 5));
+        assert!(tc.reachable(5, 0));
+        assert!(!tc.reachable(1, 0));
+        assert!(tc.reachable(1, 1));
+        assert!(tc.reachable(7, 1));
+        assert!(!tc.reachable(9, 6));
+        assert!(tc.reachable(9, 3));
+
+        // 1 is a sink reachable from everything; 7 and 8 reach everything
+        assert!((0..13).all(|v| tc.reachable(v, 1)));
+        assert!((0..13).all(|v| tc.reachable(7, v) && tc.reachable(8, v)));
+        assert_eq!((0..13).filter(|&w| tc.reachable(0, w)).count(), 6);
+        assert_eq!(
+            tc.reachable_from(0).collect::<Vec<usize>>(),
+            vec![0, 1, 2, 3, 4, 5]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn out_of_range() {
+        TransitiveClosure::new(&Digraph::new(2)).reachable(0, 2);
+    }
+}
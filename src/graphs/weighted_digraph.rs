@@ -1,6 +1,10 @@
 //! # A edge-weighted digraph of vertices named 0 to (v-1)
 
+use std::io::BufRead;
+
 use super::directed_edge::DirectedEdge;
+use super::graph_reader::{self, Tokens};
+use super::interop;
 pub struct EdgeWeightedDiagraph {
     v: usize,
     e: usize,
@@ -18,6 +22,20 @@ impl EdgeWeightedDiagraph {
         }
     }
 
+    /// Reads an edge-weighted digraph in the format `V`, `E`, then `E` triples `v w weight`.
+    pub fn from_reader(r: impl BufRead) -> graph_reader::Result<Self> {
+        let mut tokens = Tokens::read(r)?;
+        let (v, e) = tokens.counts()?;
+        let mut g = EdgeWeightedDiagraph::new(v);
+        for _ in 0..e {
+            let v = tokens.vertex()?;
+            let w = tokens.vertex()?;
+            let weight = tokens.weight()?;
+            g.add_edge(DirectedEdge::new(v, w, weight));
+        }
+        Ok(g)
+    }
+
     /// Returns the number of vertices in this edge-weighted digraph.
     pub fn v(&self) -> usize {
         self.v
@@ -28,6 +46,14 @@ impl EdgeWeightedDiagraph {
         self.e
     }
 
+    /// Adds a new vertex with no edges and returns its index.
+    pub fn add_vertex(&mut self) -> usize {
+        self.adj.push(Vec::new());
+        self.in_degree.push(0);
+        self.v += 1;
+        self.v - 1
+    }
+
     /// Adds the directed edge to this edge-weighted digraph.
     pub fn add_edge(&mut self, e: DirectedEdge) {
         let v = e.from();
@@ -42,6 +68,17 @@ impl EdgeWeightedDiagraph {
         self.adj[v].clone().into_iter()
     }
 
+    /// Returns the directed edges incident from vertex v, without copying them.
+    pub fn adj_ref(&self, v: usize) -> &[DirectedEdge] {
+        &self.adj[v]
+    }
+
+    /// Returns the weight of an edge v->w, or `None` if there is no such edge.
+    /// With parallel edges, it is the weight of the first one added.
+    pub fn has_edge(&self, v: usize, w: usize) -> Option<f64> {
+        self.adj[v].iter().find(|e| e.to() == w).map(|e| e.weight())
+    }
+
     /// Outdegree of vertex v
     pub fn out_degree(&self, v: usize) -> usize {
         self.adj[v].len()
@@ -52,6 +89,15 @@ impl EdgeWeightedDiagraph {
         self.in_degree[v]
     }
 
+    /// Returns the reverse of the edge-weighted digraph, each edge v->w becoming w->v with the same weight.
+    pub fn reverse(&self) -> EdgeWeightedDiagraph {
+        let mut r = EdgeWeightedDiagraph::new(self.v);
+        for e in self.adj.iter().flatten() {
+            r.add_edge(DirectedEdge::new(e.to(), e.from(), e.weight()));
+        }
+        r
+    }
+
     /// Returns all directed edges in this edge-weighted digraph.
     pub fn edges(&self) -> std::vec::IntoIter<DirectedEdge> {
         self.adj
@@ -61,17 +107,31 @@ impl EdgeWeightedDiagraph {
             .collect::<Vec<DirectedEdge>>()
             .into_iter()
     }
+
+    /// Returns the digraph in Graphviz DOT format, each edge labelled with its weight.
+    pub fn to_dot(&self) -> String {
+        self.to_dot_with_highlighted_edges(&[])
+    }
+
+    /// Returns the digraph in Graphviz DOT format, drawing the given edges in red.
+    pub fn to_dot_with_highlighted_edges(&self, edges: &[(usize, usize)]) -> String {
+        let list = self
+            .adj
+            .iter()
+            .flatten()
+            .map(|e| (e.from(), e.to(), Some(e.weight())));
+        interop::write_dot(true, self.v, list, edges)
+    }
 }
 
+/// Writes the digraph in the format read by `from_reader`.
 impl std::fmt::Display for EdgeWeightedDiagraph {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        writeln!(f, "{} {}", self.v, self.e)?;
-        for (i, adj) in self.adj.iter().enumerate() {
-            write!(f, "{}: ", i)?;
-            for edge in adj {
-                write!(f, "{}; ", edge)?;
-            }
-            writeln!(f)?;
+        writeln!(f, "{}", self.v)?;
+        writeln!(f, "{}", self.e)?;
+        for edge in self.adj.iter().flatten() {
+            // the shortest representation that parses back to the same weight
+            writeln!(f, "{} {} {}", edge.from(), edge.to(), edge.weight())?;
         }
         Ok(())
     }
@@ -101,5 +161,145 @@ mod test {
         g.add_edge(DirectedEdge::new(6, 4, 0.93));
 
         println!("{}", g);
+
+        assert_eq!(g.has_edge(6, 0), Some(0.58));
+        assert_eq!(g.has_edge(0, 6), None);
+        g.add_edge(DirectedEdge::new(6, 0, 0.1));
+        assert_eq!(g.has_edge(6, 0), Some(0.58));
+    }
+
+    const TINY_EWD: &str = "8
+15
+4 5 0.35
+5 4 0.35
+4 7 0.37
+5 7 0.28
+7 5 0.28
+5 1 0.32
+0 4 0.38
+0 2 0.26
+7 3 0.39
+1 3 0.29
+2 7 0.34
+6 2 0.40
+3 6 0.52
+6 0 0.58
+6 4 0.93
+";
+
+    #[test]
+    fn add_vertex() {
+        let mut g = EdgeWeightedDiagraph::new(1);
+        let v = g.add_vertex();
+        g.add_edge(DirectedEdge::new(0, v, 1.0));
+        let w = g.add_vertex();
+        g.add_edge(DirectedEdge::new(v, w, 2.0));
+        g.add_edge(DirectedEdge::new(0, w, 4.0));
+        assert_eq!(g.v(), 3);
+        assert_eq!(g.e(), 3);
+        assert_eq!(g.in_degree(w), 2);
+        assert_eq!(g.out_degree(0), 2);
+
+        let sp = crate::graphs::dijkstra_sp::DijkstraSP::new(&g, 0);
+        assert_eq!(sp.dist_to(w), 3.0);
+    }
+
+    #[test]
+    fn from_reader() {
+        let g = EdgeWeightedDiagraph::from_reader(TINY_EWD.as_bytes()).unwrap();
+        assert_eq!(g.v(), 8);
+        assert_eq!(g.e(), 15);
+        assert_eq!(g.in_degree(4), 3);
+        let adj = g
+            .adj(5)
+            .map(|e| (e.to(), e.weight()))
+            .collect::<Vec<(usize, f64)>>();
+        assert_eq!(adj, vec![(4, 0.35), (7, 0.28), (1, 0.32)]);
+
+        let text = g.to_string();
+        let copy = EdgeWeightedDiagraph::from_reader(text.as_bytes()).unwrap();
+        assert_eq!(copy.to_string(), text);
+    }
+
+    #[test]
+    fn from_reader_errors() {
+        use crate::graphs::graph_reader::ParseGraphError;
+
+        let err = |s: &str| {
+            EdgeWeightedDiagraph::from_reader(s.as_bytes())
+                .err()
+                .unwrap()
+        };
+        assert!(matches!(err("8\n15\n4 5"), ParseGraphError::UnexpectedEof));
+        assert!(matches!(
+            err("8 1 4 5 0.3.5"),
+            ParseGraphError::InvalidWeight(_)
+        ));
+        assert!(matches!(
+            err("8 -1"),
+            ParseGraphError::NegativeEdgeCount(-1)
+        ));
+        assert!(matches!(
+            err("8 1 -4 5 1.0"),
+            ParseGraphError::InvalidVertex(_)
+        ));
+    }
+
+    #[test]
+    fn adj_ref() {
+        let g = EdgeWeightedDiagraph::from_reader(
+            "4 5 0 1 0.5 0 2 0.25 0 1 0.75 2 3 1.0 3 3 2.0".as_bytes(),
+        )
+        .unwrap();
+        for v in 0..g.v() {
+            assert!(g.adj_ref(v).iter().copied().eq(g.adj(v)));
+            assert_eq!(g.adj_ref(v).len(), g.out_degree(v));
+        }
+        let to: Vec<usize> = g.adj_ref(0).iter().map(|e| e.to()).collect();
+        assert_eq!(to, vec![1, 2, 1]);
+        assert!(g.adj_ref(1).is_empty());
+    }
+
+    #[test]
+    fn reverse() {
+        let mut g = EdgeWeightedDiagraph::new(3);
+        g.add_edge(DirectedEdge::new(0, 1, 0.5));
+        g.add_edge(DirectedEdge::new(0, 2, 0.25));
+        g.add_edge(DirectedEdge::new(2, 2, 1.0));
+        let r = g.reverse();
+        assert_eq!(r.v(), 3);
+        assert_eq!(r.e(), 3);
+        assert_eq!(r.has_edge(1, 0), Some(0.5));
+        assert_eq!(r.has_edge(2, 0), Some(0.25));
+        assert_eq!(r.has_edge(0, 1), None);
+        assert_eq!(r.has_edge(2, 2), Some(1.0));
+        assert_eq!(r.in_degree(0), 2);
+        assert_eq!(r.out_degree(0), 0);
+    }
+
+    #[test]
+    fn to_dot() {
+        let mut g = EdgeWeightedDiagraph::new(3);
+        g.add_edge(DirectedEdge::new(0, 1, 0.5));
+        g.add_edge(DirectedEdge::new(1, 0, 0.25));
+        let dot = g.to_dot_with_highlighted_edges(&[(1, 0)]);
+        assert!(dot.starts_with("digraph {\n"));
+        assert!(dot.ends_with("}\n"));
+        assert!(dot.contains("  2;\n"));
+        assert!(dot.contains("  0 -> 1 [label=\"0.5\"];\n"));
+        assert!(dot.contains("  1 -> 0 [label=\"0.25\", color=red, penwidth=2];\n"));
+    }
+
+    #[test]
+    fn infinite_weights_round_trip() {
+        let mut g = EdgeWeightedDiagraph::new(2);
+        g.add_edge(DirectedEdge::new(0, 1, f64::INFINITY));
+        g.add_edge(DirectedEdge::new(1, 0, f64::NEG_INFINITY));
+
+        let text = g.to_string();
+        let copy = EdgeWeightedDiagraph::from_reader(text.as_bytes()).unwrap();
+        assert_eq!(copy.to_string(), text);
+        let weights: Vec<f64> = copy.edges().map(|e| e.weight()).collect();
+        assert_eq!(weights, vec![f64::INFINITY, f64::NEG_INFINITY]);
     }
 }
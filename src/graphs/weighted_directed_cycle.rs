@@ -25,32 +25,40 @@ impl EdgeWeightedDirectedCycle {
         dc
     }
 
-    fn dfs(&mut self, g: &EdgeWeightedDiagraph, v: usize) {
-        self.on_stack[v] = true;
-        self.marked[v] = true;
+    // iterative, so that long paths do not overflow the call stack
+    fn dfs(&mut self, g: &EdgeWeightedDiagraph, s: usize) {
+        // (v, remaining edges out of v)
+        let mut stack = vec![(s, g.adj(s))];
+        self.on_stack[s] = true;
+        self.marked[s] = true;
 
-        for e in g.adj(v) {
-            let w = e.to();
-
-            // short circuit if directed cycle found
-            if !self.cycle.is_empty() {
-                return;
-            }
-
-            if !self.marked[w] {
-                self.edge_to[w] = e;
-                self.dfs(g, w);
-            } else if self.on_stack[w] {
-                // trace back directed cycle
-                let mut f = e;
-                while f.from() != w {
-                    self.cycle.push(f);
-                    f = self.edge_to[f.from()];
+        while let Some((v, adj)) = stack.last_mut() {
+            match adj.next() {
+                Some(e) => {
+                    let w = e.to();
+                    if !self.marked[w] {
+                        self.edge_to[w] = e;
+                        self.on_stack[w] = true;
+                        self.marked[w] = true;
+                        stack.push((w, g.adj(w)));
+                    } else if self.on_stack[w] {
+                        // trace back directed cycle
+                        let mut f = e;
+                        while f.from() != w {
+                            self.cycle.push(f);
+                            f = self.edge_to[f.from()];
+                        }
+                        self.cycle.push(f);
+                        // short circuit since directed cycle found
+                        return;
+                    }
+                }
+                None => {
+                    self.on_stack[*v] = false;
+                    stack.pop();
                 }
-                self.cycle.push(f);
             }
         }
-        self.on_stack[v] = false;
     }
 
     /// Does the digraph have a directed cycle?
@@ -91,6 +99,12 @@ mod test {
         let dc = EdgeWeightedDirectedCycle::new(&g);
 
         assert!(dc.has_cycle());
+        assert_eq!(
+            EdgeWeightedDirectedCycle::cycle(&dc)
+                .map(|e| (e.from(), e.to()))
+                .collect::<Vec<_>>(),
+            vec![(4, 5), (5, 4)]
+        );
     }
 
     #[test]
@@ -2,7 +2,11 @@
 //!
 //! It is implemented using adjacency lists.
 
+use std::io::BufRead;
+
 use super::edge::Edge;
+use super::graph_reader::{self, Tokens};
+use super::interop;
 pub struct EdgeWeightedGraph {
     v: usize,
     e: usize,
@@ -18,6 +22,27 @@ impl EdgeWeightedGraph {
             adj: vec![vec![]; v],
         }
     }
+    /// Reads an edge-weighted graph in the format `V`, `E`, then `E` triples `v w weight`.
+    pub fn from_reader(r: impl BufRead) -> graph_reader::Result<Self> {
+        let mut tokens = Tokens::read(r)?;
+        let (v, e) = tokens.counts()?;
+        let mut g = EdgeWeightedGraph::new(v);
+        for _ in 0..e {
+            let v = tokens.vertex()?;
+            let w = tokens.vertex()?;
+            let weight = tokens.weight()?;
+            g.add_edge(Edge::new(v, w, weight));
+        }
+        Ok(g)
+    }
+
+    /// Adds a new isolated vertex and returns its index.
+    pub fn add_vertex(&mut self) -> usize {
+        self.adj.push(Vec::new());
+        self.v += 1;
+        self.v - 1
+    }
+
     /// Adds the undirected edge to this edge-weighted graph.
     pub fn add_edge(&mut self, e: Edge) {
         let v = e.either();
@@ -27,6 +52,15 @@ impl EdgeWeightedGraph {
         self.e += 1;
     }
 
+    /// Returns the weight of an edge v-w, or `None` if there is no such edge.
+    /// With parallel edges, it is the weight of the first one added.
+    pub fn has_edge(&self, v: usize, w: usize) -> Option<f64> {
+        self.adj[v]
+            .iter()
+            .find(|e| e.other(v) == w)
+            .map(|e| e.weight())
+    }
+
     /// Returns the number of vertices in this edge-weighted graph.
     pub fn v(&self) -> usize {
         self.v
@@ -44,38 +78,40 @@ impl EdgeWeightedGraph {
 
     /// Returns all edges in this graph.
     pub fn edges(&self) -> std::vec::IntoIter<Edge> {
-        let mut list = Vec::new();
-        for v in 0..self.v {
-            let mut self_loops = 0;
-            for e in self.adj(v) {
-                match e.other(v).cmp(&v) {
-                    std::cmp::Ordering::Less => {}
-                    std::cmp::Ordering::Equal => {
-                        // add only one copy of each self-loop
-                        if self_loops % 2 == 0 {
-                            list.push(e);
-                        }
-                        self_loops += 1;
-                    }
-                    std::cmp::Ordering::Greater => {
-                        list.push(e);
-                    }
-                }
-            }
-        }
+        let list: Vec<Edge> = (0..self.v)
+            .flat_map(|v| {
+                let adj = self.adj[v].iter().map(move |e| (e.other(v), e.clone()));
+                interop::once_each(v, adj)
+            })
+            .collect();
         list.into_iter()
     }
+
+    /// Returns the graph in Graphviz DOT format, each edge once and labelled with its weight.
+    pub fn to_dot(&self) -> String {
+        self.to_dot_with_highlighted_edges(&[])
+    }
+
+    /// Returns the graph in Graphviz DOT format, drawing the given edges
+    /// (in either orientation) in red.
+    pub fn to_dot_with_highlighted_edges(&self, edges: &[(usize, usize)]) -> String {
+        let list = self.edges().map(|e| {
+            let v = e.either();
+            (v, e.other(v), Some(e.weight()))
+        });
+        interop::write_dot(false, self.v, list, edges)
+    }
 }
 
+/// Writes the graph in the format read by `from_reader`, with each edge once.
 impl std::fmt::Display for EdgeWeightedGraph {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        writeln!(f, "{} {}", self.v, self.e)?;
-        for v in 0..self.v {
-            write!(f, "{}: ", v)?;
-            for edge in self.adj(v) {
-                write!(f, "{}  ", edge)?;
-            }
-            writeln!(f)?;
+        writeln!(f, "{}", self.v)?;
+        writeln!(f, "{}", self.e)?;
+        for edge in self.edges() {
+            let v = edge.either();
+            // the shortest representation that parses back to the same weight
+            writeln!(f, "{} {} {}", v, edge.other(v), edge.weight())?;
         }
         Ok(())
     }
@@ -107,5 +143,121 @@ mod test {
         println!("{}", g);
 
         assert_eq!(g.e(), 16);
+
+        assert_eq!(g.has_edge(4, 5), Some(0.35));
+        assert_eq!(g.has_edge(5, 4), Some(0.35));
+        assert_eq!(g.has_edge(2, 6), Some(0.40));
+        assert_eq!(g.has_edge(0, 1), None);
+    }
+
+    const TINY_EWG: &str = "8
+16
+4 5 0.35
+4 7 0.37
+5 7 0.28
+0 7 0.16
+1 5 0.32
+0 4 0.38
+2 3 0.17
+1 7 0.19
+0 2 0.26
+1 2 0.36
+1 3 0.29
+2 7 0.34
+6 2 0.40
+3 6 0.52
+6 0 0.58
+6 4 0.93
+";
+
+    #[test]
+    fn add_vertex() {
+        let mut g = EdgeWeightedGraph::new(0);
+        let a = g.add_vertex();
+        let b = g.add_vertex();
+        g.add_edge(Edge::new(a, b, 0.5));
+        let c = g.add_vertex();
+        g.add_edge(Edge::new(b, c, 0.25));
+        assert_eq!(g.v(), 3);
+        assert_eq!(g.e(), 2);
+        assert_eq!(
+            g.adj(b).map(|e| e.other(b)).collect::<Vec<usize>>(),
+            vec![0, 2]
+        );
+        assert_eq!(g.adj(c).count(), 1);
+    }
+
+    #[test]
+    fn from_reader() {
+        let g = EdgeWeightedGraph::from_reader(TINY_EWG.as_bytes()).unwrap();
+        assert_eq!(g.v(), 8);
+        assert_eq!(g.e(), 16);
+        let adj = g
+            .adj(6)
+            .map(|e| (e.other(6), e.weight()))
+            .collect::<Vec<(usize, f64)>>();
+        assert_eq!(adj, vec![(2, 0.40), (3, 0.52), (0, 0.58), (4, 0.93)]);
+
+        let text = g.to_string();
+        let copy = EdgeWeightedGraph::from_reader(text.as_bytes()).unwrap();
+        assert_eq!(copy.e(), 16);
+        assert_eq!(copy.to_string(), text);
+        let weight = |g: &EdgeWeightedGraph| g.edges().map(|e| e.weight()).sum::<f64>();
+        assert_eq!(weight(&copy), weight(&g));
+    }
+
+    #[test]
+    fn from_reader_errors() {
+        use crate::graphs::graph_reader::ParseGraphError;
+
+        let err = |s: &str| EdgeWeightedGraph::from_reader(s.as_bytes()).err().unwrap();
+        assert!(matches!(
+            err("8\n16\n4 5 0.35\n4 7"),
+            ParseGraphError::UnexpectedEof
+        ));
+        assert!(matches!(
+            err("8 1 4 5 abc"),
+            ParseGraphError::InvalidWeight(_)
+        ));
+        assert!(matches!(
+            err("8 1 4 5 NaN"),
+            ParseGraphError::InvalidWeight(_)
+        ));
+        assert!(matches!(err("8 1.5"), ParseGraphError::InvalidCount(_)));
+        assert!(matches!(
+            err("8 1 8 5 0.1"),
+            ParseGraphError::VertexOutOfRange { vertex: 8, v: 8 }
+        ));
+    }
+
+    #[test]
+    fn to_dot() {
+        let g = EdgeWeightedGraph::from_reader(TINY_EWG.as_bytes()).unwrap();
+        let dot = g.to_dot_with_highlighted_edges(&[(7, 0)]);
+        assert!(dot.starts_with("graph {\n"));
+        assert!(dot.ends_with("}\n"));
+        assert!(!dot.contains("->"));
+        assert_eq!(dot.matches(" -- ").count(), 16);
+        assert!(dot.contains("  4 -- 5 [label=\"0.35\"];\n"));
+        assert!(dot.contains("  6 -- 2 [label=\"0.4\"];\n"));
+        assert!(dot.contains("  0 -- 7 [label=\"0.16\", color=red, penwidth=2];\n"));
+        assert_eq!(dot.matches("color=red").count(), 1);
+    }
+
+    #[test]
+    fn infinite_weights_round_trip() {
+        let mut g = EdgeWeightedGraph::new(3);
+        g.add_edge(Edge::new(0, 1, f64::INFINITY));
+        g.add_edge(Edge::new(1, 2, f64::NEG_INFINITY));
+        g.add_edge(Edge::new(2, 0, 0.5));
+
+        let text = g.to_string();
+        assert!(text.contains("0 1 inf\n"));
+        assert!(text.contains("1 2 -inf\n"));
+        let copy = EdgeWeightedGraph::from_reader(text.as_bytes()).unwrap();
+        assert_eq!(copy.to_string(), text);
+        let mut weights: Vec<f64> = copy.edges().map(|e| e.weight()).collect();
+        weights.sort_by(f64::total_cmp);
+        assert_eq!(weights, vec![f64::NEG_INFINITY, 0.5, f64::INFINITY]);
     }
 }
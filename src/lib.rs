@@ -1,5 +1,8 @@
+pub mod compression;
 pub mod fundamentals;
 pub mod graphs;
+pub mod pipelines;
 pub mod searching;
 pub mod sorting;
 pub mod strings;
+pub mod test_support;
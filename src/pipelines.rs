@@ -0,0 +1,81 @@
+//! # End-to-end pipelines
+//!
+//! The book's client programs as library functions: each one parses its input, runs
+//! the algorithms, and returns the result instead of printing it.
+
+use crate::compression::{huffman::Huffman, lzw::LZW, run_length::RunLength};
+use crate::graphs::{
+    dijkstra_sp::DijkstraSP, directed_edge::DirectedEdge, graph_reader,
+    symbol_digraph::SymbolDigraph, symbol_graph::SymbolGraph, topological::Topological,
+    weighted_digraph::EdgeWeightedDiagraph,
+};
+
+/// Finds a route with the fewest hops between two airports (`DegreesOfSeparation`).
+///
+/// `airports` has one route per line, `"JFK MCO"`. Returns the airports on the route,
+/// from `from` to `to`, or `None` if either is unknown or they are not connected.
+pub fn spell_route(airports: &str, from: &str, to: &str) -> Option<Vec<String>> {
+    // reading from a &str cannot fail
+    let sg = SymbolGraph::from_reader(airports.as_bytes(), " ").unwrap();
+    let path = sg.bfs_path_by_name(from, to)?;
+    Some(path.into_iter().map(String::from).collect())
+}
+
+/// Finds a shortest path from `s` to `t` in an edge-weighted digraph given in the `tinyEWD.txt`
+/// format (`DijkstraSP`).
+///
+/// Returns its length and edges, or `None` if `t` is not reachable from `s`.
+pub fn cheapest_route(
+    ewd_text: &str,
+    s: usize,
+    t: usize,
+) -> graph_reader::Result<Option<(f64, Vec<DirectedEdge>)>> {
+    let g = EdgeWeightedDiagraph::from_reader(ewd_text.as_bytes())?;
+    let sp = DijkstraSP::new(&g, s);
+    if !sp.has_path_to(t) {
+        return Ok(None);
+    }
+    Ok(Some((sp.dist_to(t), sp.path_to(t).collect())))
+}
+
+/// Orders jobs so that each comes before the jobs that depend on it (`Topological`).
+///
+/// `jobs_text` has one job per line followed by the jobs that must come after it, separated
+/// by `/`, as in `jobs.txt`. Returns `None` if the constraints have a cycle.
+pub fn build_schedule(jobs_text: &str) -> Option<Vec<String>> {
+    // reading from a &str cannot fail
+    let sg = SymbolDigraph::from_reader(jobs_text.as_bytes(), "/").unwrap();
+    let topological = Topological::try_new(sg.digraph()).ok()?;
+    Some(
+        topological
+            .order()
+            .map(|v| sg.name_of(v).to_string())
+            .collect(),
+    )
+}
+
+/// A compression algorithm for `compress_file` and `expand_file`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Algo {
+    Huffman,
+    Lzw,
+    RunLength,
+}
+
+/// Compresses `input` with `algo` (`Huffman`, `LZW` or `RunLength`).
+pub fn compress_file(input: &[u8], algo: Algo) -> Vec<u8> {
+    match algo {
+        Algo::Huffman => Huffman::compress(input),
+        Algo::Lzw => LZW::compress(input),
+        Algo::RunLength => RunLength::compress(input),
+    }
+}
+
+/// Expands the output of `compress_file` with the same `algo`, panicking if it is malformed.
+pub fn expand_file(compressed: &[u8], algo: Algo) -> Vec<u8> {
+    match algo {
+        Algo::Huffman => Huffman::expand(compressed),
+        Algo::Lzw => LZW::expand(compressed),
+        Algo::RunLength => RunLength::expand(compressed),
+    }
+}
@@ -3,8 +3,11 @@ pub mod avl2;
 pub mod binary_search_st;
 pub mod bst;
 pub mod bst2;
+pub mod deterministic_state;
+pub mod frozen_st;
 pub mod linear_probing_hash_st;
 pub mod llrb;
 pub mod red_black_bst;
 pub mod separate_chaining_hash_st;
 pub mod sequential_search_st;
+pub mod temporal_st;
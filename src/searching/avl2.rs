@@ -74,18 +74,16 @@ impl<K, V> Node<K, V> {
 
 impl<K, V> Node<K, V> {
     fn min_key(&self) -> &K {
-        if self.left.is_none() {
-            &self.key
-        } else {
-            self.left.as_ref().unwrap().min_key()
+        match self.left {
+            Some(ref left) => left.min_key(),
+            None => &self.key,
         }
     }
 
     fn max_key(&self) -> &K {
-        if self.right.is_none() {
-            &self.key
-        } else {
-            self.right.as_ref().unwrap().max_key()
+        match self.right {
+            Some(ref right) => right.max_key(),
+            None => &self.key,
         }
     }
 }
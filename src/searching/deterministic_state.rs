@@ -0,0 +1,125 @@
+//! # Deterministic hashing
+//!
+//! `DeterministicState` builds FNV-1a hashers with the standard fixed offset basis, so a key
+//! hashes to the same value in every run and on every build. Tables using it lay out their keys,
+//! iterate and resize identically for the same sequence of operations, which keeps test and example
+//! output stable. FNV-1a is fast for short keys but offers no protection against crafted
+//! collisions, so it is not meant for untrusted input.
+
+use std::hash::{BuildHasher, Hasher};
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// A 64-bit FNV-1a hasher.
+#[derive(Clone, Copy, Debug)]
+pub struct FnvHasher {
+    hash: u64,
+}
+
+impl Default for FnvHasher {
+    fn default() -> Self {
+        FnvHasher {
+            hash: FNV_OFFSET_BASIS,
+        }
+    }
+}
+
+impl Hasher for FnvHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.hash ^= b as u64;
+            self.hash = self.hash.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.hash
+    }
+}
+
+/// A `BuildHasher` of `FnvHasher`s, all starting from the same state.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DeterministicState;
+
+impl BuildHasher for DeterministicState {
+    type Hasher = FnvHasher;
+
+    fn build_hasher(&self) -> FnvHasher {
+        FnvHasher::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::hash_map::RandomState;
+
+    use super::*;
+    use crate::searching::linear_probing_hash_st::LinearProbingHashST;
+    use crate::searching::separate_chaining_hash_st::SeparateChainingHashST;
+
+    #[test]
+    fn fnv() {
+        // reference values of 64-bit FNV-1a
+        let hash = |bytes: &[u8]| {
+            let mut h = DeterministicState.build_hasher();
+            h.write(bytes);
+            h.finish()
+        };
+        assert_eq!(hash(b""), 0xcbf29ce484222325);
+        assert_eq!(hash(b"a"), 0xaf63dc4c8601ec8c);
+        assert_eq!(hash(b"foobar"), 0x85944171f73967e8);
+    }
+
+    // the keys and the capacity after each put
+    fn linear_probing<S: BuildHasher + Clone>(hasher: S) -> (Vec<String>, Vec<usize>) {
+        let mut st = LinearProbingHashST::with_hasher(4, hasher);
+        let mut capacities = vec![];
+        for i in 0..100 {
+            st.put(format!("key{}", i), i);
+            capacities.push(st.capacity());
+        }
+        (st.keys().cloned().collect(), capacities)
+    }
+
+    fn separate_chaining<S: BuildHasher + Clone>(hasher: S) -> (Vec<String>, Vec<usize>) {
+        let mut st = SeparateChainingHashST::with_hasher(4, hasher);
+        let mut capacities = vec![];
+        for i in 0..100 {
+            st.put(format!("key{}", i), i);
+            capacities.push(st.capacity());
+        }
+        for i in 0..90 {
+            st.delete(&format!("key{}", i));
+            capacities.push(st.capacity());
+        }
+        (st.keys().cloned().collect(), capacities)
+    }
+
+    #[test]
+    fn reproducible_layout() {
+        let (keys, capacities) = linear_probing(DeterministicState);
+        assert_eq!(
+            linear_probing(DeterministicState),
+            (keys.clone(), capacities)
+        );
+        assert_eq!(keys.len(), 100);
+
+        let (keys, capacities) = separate_chaining(DeterministicState);
+        assert_eq!(
+            separate_chaining(DeterministicState),
+            (keys.clone(), capacities)
+        );
+        assert_eq!(keys.len(), 10);
+    }
+
+    #[test]
+    fn random_state() {
+        // the same pairs, though maybe in another order
+        let (mut keys, _) = linear_probing(RandomState::new());
+        let (mut expected, _) = linear_probing(DeterministicState);
+        keys.sort_unstable();
+        expected.sort_unstable();
+        assert_eq!(keys, expected);
+    }
+}
@@ -0,0 +1,187 @@
+//! # Frozen (read-only) symbol table
+//!
+//! An immutable ordered symbol table for sharing across threads.
+//! Build one with `RedBlackBST::freeze` or `LinearProbingHashST::freeze`.
+//!
+//! The pairs are stored in a single array in Eytzinger (BFS) order: the children of slot k
+//! are slots 2k + 1 and 2k + 2. A search is the same binary search as over a sorted array,
+//! but the first levels of the implicit tree sit next to each other in memory.
+//!
+//! `FrozenST<K, V>` is `Send + Sync` whenever `K` and `V` are, with no locks:
+//! nothing can be mutated after construction.
+//! (The mutable tables `RedBlackBST`, `LinearProbingHashST`, `Queue` etc. are also
+//! `Sync` for `Sync` items, since `&self` methods never mutate them, but they have
+//! to be frozen or wrapped in a lock to be written while shared.)
+
+use std::cmp::Ordering;
+
+pub struct FrozenST<K, V> {
+    pairs: Vec<(K, V)>, // in Eytzinger order
+}
+
+impl<K: Ord, V> FrozenST<K, V> {
+    /// Builds the table from pairs sorted by strictly increasing key.
+    ///
+    /// The order is not checked in release builds: with unsorted or duplicate keys,
+    /// lookups silently give wrong answers. Debug builds panic instead.
+    pub fn from_sorted(sorted: Vec<(K, V)>) -> Self {
+        debug_assert!(
+            sorted.windows(2).all(|w| w[0].0 < w[1].0),
+            "keys are not strictly increasing"
+        );
+        let n = sorted.len();
+        let mut slots: Vec<Option<(K, V)>> = (0..n).map(|_| None).collect();
+        let mut sorted = sorted.into_iter();
+        Self::fill(&mut slots, &mut sorted, 0);
+        FrozenST {
+            pairs: slots.into_iter().map(Option::unwrap).collect(),
+        }
+    }
+
+    // an in-order walk of the implicit tree visits slots in key order
+    fn fill(slots: &mut [Option<(K, V)>], sorted: &mut impl Iterator<Item = (K, V)>, k: usize) {
+        if k < slots.len() {
+            Self::fill(slots, sorted, 2 * k + 1);
+            slots[k] = sorted.next();
+            Self::fill(slots, sorted, 2 * k + 2);
+        }
+    }
+
+    /// Returns the number of key-value pairs in this symbol table.
+    pub fn size(&self) -> usize {
+        self.pairs.len()
+    }
+
+    /// Is this symbol table empty?
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+
+    /// Returns the value associated with the given key.
+    pub fn get(&self, k: &K) -> Option<&V> {
+        let mut i = 0;
+        while let Some((key, val)) = self.pairs.get(i) {
+            i = match k.cmp(key) {
+                Ordering::Equal => return Some(val),
+                Ordering::Less => 2 * i + 1,
+                Ordering::Greater => 2 * i + 2,
+            };
+        }
+        None
+    }
+
+    /// Does this symbol table contain the given key?
+    pub fn contains(&self, k: &K) -> bool {
+        self.get(k).is_some()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::Cell;
+    use std::marker::PhantomData;
+    use std::rc::Rc;
+    use std::thread;
+
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    use super::*;
+    use crate::fundamentals::deque::Deque;
+    use crate::fundamentals::queue::Queue;
+    use crate::fundamentals::resizing_stack::ResizingStack;
+    use crate::searching::linear_probing_hash_st::LinearProbingHashST;
+    use crate::searching::red_black_bst::RedBlackBST;
+
+    // `Check::<T>::IS_SYNC` resolves to the inherent constant only when `T: Sync`,
+    // and falls back to the trait's `false` otherwise
+    trait NotSync {
+        const IS_SYNC: bool = false;
+    }
+    impl<T: ?Sized> NotSync for T {}
+    struct Check<T: ?Sized>(PhantomData<T>);
+    #[allow(dead_code)]
+    impl<T: ?Sized + Sync> Check<T> {
+        const IS_SYNC: bool = true;
+    }
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn send_sync() {
+        assert_send_sync::<Queue<String>>();
+        assert_send_sync::<Deque<String>>();
+        assert_send_sync::<ResizingStack<String>>();
+        assert_send_sync::<RedBlackBST<String, i32>>();
+        assert_send_sync::<LinearProbingHashST<String, i32>>();
+        assert_send_sync::<FrozenST<String, i32>>();
+
+        // but never more than their items (checked at compile time)
+        const { assert!(Check::<Queue<i32>>::IS_SYNC) };
+        const { assert!(!Check::<Queue<Cell<i32>>>::IS_SYNC) };
+        const { assert!(!Check::<Queue<Rc<i32>>>::IS_SYNC) };
+        const { assert!(!Check::<Deque<Cell<i32>>>::IS_SYNC) };
+        const { assert!(!Check::<ResizingStack<Cell<i32>>>::IS_SYNC) };
+        const { assert!(!Check::<FrozenST<i32, Cell<i32>>>::IS_SYNC) };
+    }
+
+    #[test]
+    fn get() {
+        for n in 0..20 {
+            let st = FrozenST::from_sorted((0..n).map(|i| (2 * i as i64, i)).collect());
+            assert_eq!(st.size(), n);
+            for i in 0..n {
+                assert_eq!(st.get(&(2 * i as i64)), Some(&i));
+                assert!(!st.contains(&(2 * i as i64 + 1)));
+            }
+            assert!(!st.contains(&-1));
+        }
+    }
+
+    #[test]
+    fn concurrent_reads() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let mut rb = RedBlackBST::new();
+        let mut hash = LinearProbingHashST::default();
+        let mut reference = Vec::new();
+        for _ in 0..500 {
+            let k: u32 = rng.gen_range(0..2000);
+            let v = format!("v{}", k);
+            rb.put(k, v.clone());
+            hash.put(k, v.clone());
+            reference.push((k, v));
+        }
+        let expected: Vec<Option<String>> = (0..2000).map(|k| rb.get(&k).cloned()).collect();
+
+        let frozen_rb = rb.freeze();
+        let frozen_hash = hash.freeze();
+        thread::scope(|s| {
+            for t in 0..8 {
+                let (frozen_rb, frozen_hash, expected) = (&frozen_rb, &frozen_hash, &expected);
+                s.spawn(move || {
+                    for k in (t..2000).step_by(8) {
+                        assert_eq!(frozen_rb.get(&k), expected[k as usize].as_ref());
+                        assert_eq!(frozen_hash.get(&k), expected[k as usize].as_ref());
+                    }
+                });
+            }
+        });
+        for (k, v) in reference {
+            assert_eq!(frozen_rb.get(&k), Some(&v));
+        }
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "keys are not strictly increasing")]
+    fn from_unsorted() {
+        FrozenST::from_sorted(vec![(1, 'a'), (3, 'b'), (2, 'c')]);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "keys are not strictly increasing")]
+    fn from_duplicates() {
+        FrozenST::from_sorted(vec![(1, 'a'), (1, 'b')]);
+    }
+}
@@ -1,32 +1,52 @@
 //! # Symbol-table implementation with linear-probing hash table.
+//!
+//! Keys are hashed with `S`, by default `DefaultHasher` with its fixed keys.
+//! Use `with_hasher` to pick another `BuildHasher`, such as `RandomState`
+//! or `DeterministicState`.
 
+use std::borrow::Borrow;
 use std::collections::hash_map::DefaultHasher;
-use std::hash::{Hash, Hasher};
+use std::hash::{BuildHasher, BuildHasherDefault, Hash};
 use std::marker::PhantomData;
 
+use super::frozen_st::FrozenST;
+
 const INIT_CAPACITY: usize = 4;
 
-pub struct LinearProbingHashST<K, V> {
+#[derive(Clone)]
+pub struct LinearProbingHashST<K, V, S = BuildHasherDefault<DefaultHasher>> {
     n: usize, // number of key-value pairs
     m: usize, // size of linear probing table. m > n
     keys: Vec<Option<K>>,
     values: Vec<Option<V>>,
+    hash_builder: S,
 }
 
 impl<K: Eq + Hash + Clone, V: Clone> LinearProbingHashST<K, V> {
     pub fn new(capacity: usize) -> Self {
+        Self::with_hasher(capacity, Default::default())
+    }
+}
+
+impl<K: Eq + Hash + Clone, V: Clone, S: BuildHasher + Clone> LinearProbingHashST<K, V, S> {
+    /// Creates an empty table of the given capacity whose keys are hashed with `hash_builder`.
+    pub fn with_hasher(capacity: usize, hash_builder: S) -> Self {
         LinearProbingHashST {
             n: 0,
             m: capacity,
             keys: vec![None; capacity],
             values: vec![None; capacity],
+            hash_builder,
         }
     }
 
-    fn hash(&self, k: &K) -> usize {
-        let mut s = DefaultHasher::new();
-        k.hash(&mut s);
-        (s.finish() as usize) % self.m
+    fn hash<Q: Hash + ?Sized>(&self, k: &Q) -> usize {
+        (self.hash_builder.hash_one(k) as usize) % self.m
+    }
+
+    /// Returns the size of the underlying table, which doubles and halves as pairs are added and removed.
+    pub fn capacity(&self) -> usize {
+        self.m
     }
 
     /// Returns the number of key-value pairs in this symbol table.
@@ -40,10 +60,16 @@ impl<K: Eq + Hash + Clone, V: Clone> LinearProbingHashST<K, V> {
     }
 
     /// Returns the value associated with the specified key.
-    pub fn get(&self, k: &K) -> Option<&V> {
+    ///
+    /// The key may be any borrowed form of `K` that hashes the same way, e.g. `&str` for `String`.
+    pub fn get<Q>(&self, k: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
         let mut i = self.hash(k);
         while let Some(ref key) = self.keys[i] {
-            if key == k {
+            if key.borrow() == k {
                 return self.values[i].as_ref();
             }
             i = (i + 1) % self.m;
@@ -53,7 +79,7 @@ impl<K: Eq + Hash + Clone, V: Clone> LinearProbingHashST<K, V> {
 
     // resizes the hash table to the given capacity by re-hashing all of the keys
     fn resize(&mut self, capacity: usize) {
-        let mut temp = LinearProbingHashST::new(capacity);
+        let mut temp = LinearProbingHashST::with_hasher(capacity, self.hash_builder.clone());
         for i in 0..self.m {
             if let Some(k) = self.keys[i].take() {
                 temp.put(k, self.values[i].take().unwrap());
@@ -63,7 +89,11 @@ impl<K: Eq + Hash + Clone, V: Clone> LinearProbingHashST<K, V> {
     }
 
     /// Returns true if this symbol table contains the specified key.
-    pub fn contains(&self, k: &K) -> bool {
+    pub fn contains<Q>(&self, k: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
         self.get(k).is_some()
     }
 
@@ -130,13 +160,27 @@ impl<K: Eq + Hash + Clone, V: Clone> LinearProbingHashST<K, V> {
     }
 }
 
+impl<K: Eq + Hash + Clone + Ord, V: Clone, S> LinearProbingHashST<K, V, S> {
+    /// Converts this symbol table into an immutable `FrozenST` for concurrent reads.
+    pub fn freeze(self) -> FrozenST<K, V> {
+        let mut pairs: Vec<(K, V)> = self
+            .keys
+            .into_iter()
+            .zip(self.values)
+            .filter_map(|(k, v)| Some((k?, v?)))
+            .collect();
+        pairs.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+        FrozenST::from_sorted(pairs)
+    }
+}
+
 pub struct Iter<'a, K, V> {
     queue: Vec<&'a K>,
     _phantom: PhantomData<V>,
 }
 
 impl<'a, K: Eq + Hash + Clone, V: Clone> Iter<'a, K, V> {
-    pub fn new(hash_st: &'a LinearProbingHashST<K, V>) -> Self {
+    pub fn new<S>(hash_st: &'a LinearProbingHashST<K, V, S>) -> Self {
         let mut queue = Vec::with_capacity(hash_st.n);
         for i in 0..hash_st.m {
             if let Some(ref key) = hash_st.keys[i] {
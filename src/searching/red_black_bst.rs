@@ -6,6 +6,8 @@
 use std::cmp::Ord;
 use std::cmp::Ordering;
 
+use super::frozen_st::FrozenST;
+
 #[derive(PartialEq, Debug, Clone, Copy)]
 enum Color {
     Red,
@@ -160,6 +162,38 @@ impl<K: Ord, V> RedBlackBST<K, V> {
         Self::_get(&self.root, k)
     }
 
+    fn _get_mut<'a>(x: &'a mut Link<K, V>, k: &K) -> Option<&'a mut V> {
+        match x {
+            Some(node) => match k.cmp(&node.key) {
+                Ordering::Equal => Some(&mut node.val),
+                Ordering::Less => Self::_get_mut(&mut node.left, k),
+                Ordering::Greater => Self::_get_mut(&mut node.right, k),
+            },
+            _ => None,
+        }
+    }
+
+    /// Returns a mutable reference to the value associated with the given key.
+    pub fn get_mut(&mut self, k: &K) -> Option<&mut V> {
+        Self::_get_mut(&mut self.root, k)
+    }
+
+    fn _values_mut<'a>(x: &'a mut Link<K, V>, values: &mut Vec<&'a mut V>) {
+        if let Some(node) = x {
+            let node = &mut **node;
+            Self::_values_mut(&mut node.left, values);
+            values.push(&mut node.val);
+            Self::_values_mut(&mut node.right, values);
+        }
+    }
+
+    /// Returns mutable references to all values, in key order.
+    pub fn values_mut(&mut self) -> Vec<&mut V> {
+        let mut values = Vec::new();
+        Self::_values_mut(&mut self.root, &mut values);
+        values
+    }
+
     /// Does this symbol table contain the given key?
     pub fn contains(&self, k: &K) -> bool {
         self.get(k).is_none()
@@ -334,6 +368,24 @@ impl<K: Ord, V> RedBlackBST<K, V> {
     }
 }
 
+impl<K: Ord, V> RedBlackBST<K, V> {
+    fn _into_sorted(x: Link<K, V>, pairs: &mut Vec<(K, V)>) {
+        if let Some(node) = x {
+            let node = *node;
+            Self::_into_sorted(node.left, pairs);
+            pairs.push((node.key, node.val));
+            Self::_into_sorted(node.right, pairs);
+        }
+    }
+
+    /// Converts this symbol table into an immutable `FrozenST` for concurrent reads.
+    pub fn freeze(self) -> FrozenST<K, V> {
+        let mut pairs = Vec::with_capacity(self.size());
+        Self::_into_sorted(self.root, &mut pairs);
+        FrozenST::from_sorted(pairs)
+    }
+}
+
 impl<K: Ord, V> Default for RedBlackBST<K, V> {
     fn default() -> Self {
         Self::new()
@@ -532,4 +584,34 @@ mod tests {
         assert_eq!(st.rank(&5), 3);
         assert_eq!(st.rank(&4), 3);
     }
+
+    #[test]
+    fn get_mut() {
+        let mut st = RedBlackBST::new();
+        for (i, c) in "SEARCHEXAMPLE".chars().enumerate() {
+            st.put(c, i);
+        }
+        *st.get_mut(&'X').unwrap() += 100;
+        assert_eq!(st.get(&'X'), Some(&107));
+        assert_eq!(st.get_mut(&'Z'), None);
+
+        for v in st.values_mut() {
+            *v = 0;
+        }
+        assert_eq!(st.get(&'A'), Some(&0));
+        assert_eq!(st.values_mut().len(), 10);
+    }
+
+    #[test]
+    fn freeze() {
+        let mut st = RedBlackBST::new();
+        for (i, c) in "SEARCHEXAMPLE".chars().enumerate() {
+            st.put(c, i);
+        }
+        let frozen = st.freeze();
+        assert_eq!(frozen.size(), 10);
+        assert_eq!(frozen.get(&'E'), Some(&12));
+        assert_eq!(frozen.get(&'S'), Some(&0));
+        assert_eq!(frozen.get(&'Z'), None);
+    }
 }
@@ -1,32 +1,52 @@
 //! # A symbol table implemented with a separate-chaining hash table.
+//!
+//! Keys are hashed with `S`, by default `DefaultHasher` with its fixed keys.
+//! Use `with_hasher` to pick another `BuildHasher`, such as `RandomState`
+//! or `DeterministicState`.
 use crate::searching::sequential_search_st::SequentialSearchST;
 use std::marker::PhantomData;
 use std::{
     collections::hash_map::DefaultHasher,
-    hash::{Hash, Hasher},
+    hash::{BuildHasher, BuildHasherDefault, Hash},
 };
 
 const INIT_CAPACITY: usize = 4;
 
-pub struct SeparateChainingHashST<K, V> {
+pub struct SeparateChainingHashST<K, V, S = BuildHasherDefault<DefaultHasher>> {
     n: usize, // number of key-value pairs
     m: usize, // hash table size
     st: Vec<SequentialSearchST<K, V>>,
+    hash_builder: S,
 }
 
 impl<K: Eq + Hash, V> SeparateChainingHashST<K, V> {
     pub fn new(m: usize) -> Self {
+        Self::with_hasher(m, Default::default())
+    }
+}
+
+impl<K: Eq + Hash, V, S: BuildHasher + Clone> SeparateChainingHashST<K, V, S> {
+    /// Creates an empty table with `m` chains whose keys are hashed with `hash_builder`.
+    pub fn with_hasher(m: usize, hash_builder: S) -> Self {
         let mut data: Vec<SequentialSearchST<K, V>> = Vec::with_capacity(m);
         for _ in 0..m {
             data.push(SequentialSearchST::new());
         }
-        SeparateChainingHashST { n: 0, m, st: data }
+        SeparateChainingHashST {
+            n: 0,
+            m,
+            st: data,
+            hash_builder,
+        }
     }
 
     fn hash(&self, k: &K) -> usize {
-        let mut s = DefaultHasher::new();
-        k.hash(&mut s);
-        (s.finish() as usize) % self.m
+        (self.hash_builder.hash_one(k) as usize) % self.m
+    }
+
+    /// Returns the number of chains, which doubles and halves as pairs are added and removed.
+    pub fn capacity(&self) -> usize {
+        self.m
     }
 
     /// Returns the number of key-value pairs in this symbol table.
@@ -51,7 +71,7 @@ impl<K: Eq + Hash, V> SeparateChainingHashST<K, V> {
     }
 
     fn resize(&mut self, chains: usize) {
-        let mut tmp = SeparateChainingHashST::new(chains);
+        let mut tmp = SeparateChainingHashST::with_hasher(chains, self.hash_builder.clone());
 
         while let Some(table) = self.st.pop() {
             for (k, v) in table.into_items() {
@@ -95,7 +115,7 @@ pub struct Iter<'a, K, V> {
 }
 
 impl<'a, K: Eq + Hash, V> Iter<'a, K, V> {
-    pub fn new(hash_st: &'a SeparateChainingHashST<K, V>) -> Self {
+    pub fn new<S>(hash_st: &'a SeparateChainingHashST<K, V, S>) -> Self {
         let mut queue = Vec::with_capacity(hash_st.n);
         for table in &hash_st.st {
             for key in table.keys() {
@@ -117,7 +137,7 @@ impl<'a, K: Eq + Hash, V> Iterator for Iter<'a, K, V> {
     }
 }
 
-impl<K: Eq + Hash, V> SeparateChainingHashST<K, V> {
+impl<K: Eq + Hash, V, S> SeparateChainingHashST<K, V, S> {
     pub fn keys(&self) -> Iter<'_, K, V> {
         Iter::new(self)
     }
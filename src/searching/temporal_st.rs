@@ -0,0 +1,178 @@
+//! # Time-stamped (versioned) symbol table
+//!
+//! Every `put` records a new version of a key at a timestamp, and `get_at` answers
+//! "what was the value of this key at time t?".
+//!
+//! Each key maps (in a red-black BST) to its versions sorted by timestamp,
+//! so a temporal lookup is O(log n + log m) for n keys and m versions of the key.
+//!
+//! Timestamps need not arrive in order: a version is inserted at its sorted position,
+//! and a second `put` at an existing timestamp replaces that version.
+
+use super::red_black_bst::RedBlackBST;
+
+pub struct TemporalST<K, V> {
+    st: RedBlackBST<K, Vec<(u64, V)>>, // versions of each key, sorted by timestamp
+}
+
+impl<K: Ord, V> TemporalST<K, V> {
+    pub fn new() -> Self {
+        TemporalST {
+            st: RedBlackBST::new(),
+        }
+    }
+
+    /// Returns the number of keys in this symbol table.
+    pub fn size(&self) -> usize {
+        self.st.size()
+    }
+
+    /// Is this symbol table empty?
+    pub fn is_empty(&self) -> bool {
+        self.st.is_empty()
+    }
+
+    /// Records `v` as the value of `k` from time `t` on.
+    pub fn put(&mut self, k: K, v: V, t: u64) {
+        match self.st.get_mut(&k) {
+            Some(versions) => {
+                // number of versions at or before t
+                let i = versions.partition_point(|&(ts, _)| ts <= t);
+                if i > 0 && versions[i - 1].0 == t {
+                    versions[i - 1].1 = v;
+                } else {
+                    versions.insert(i, (t, v));
+                }
+            }
+            None => self.st.put(k, vec![(t, v)]),
+        }
+    }
+
+    /// Returns the value of `k` as of time `t`, i.e. its latest version with timestamp `<= t`.
+    pub fn get_at(&self, k: &K, t: u64) -> Option<&V> {
+        let versions = self.history(k);
+        match versions.partition_point(|&(ts, _)| ts <= t) {
+            0 => None,
+            i => Some(&versions[i - 1].1),
+        }
+    }
+
+    /// Returns the most recent value of `k`.
+    pub fn latest(&self, k: &K) -> Option<&V> {
+        self.history(k).last().map(|(_, v)| v)
+    }
+
+    /// Returns all versions of `k`, sorted by timestamp.
+    pub fn history(&self, k: &K) -> &[(u64, V)] {
+        match self.st.get(k) {
+            Some(versions) => versions,
+            None => &[],
+        }
+    }
+
+    /// Discards versions that can no longer be observed by `get_at` for any time `>= t`.
+    ///
+    /// The version visible at `t` is kept, so `get_at(k, t')` is unchanged for every `t' >= t`.
+    pub fn prune_before(&mut self, t: u64) {
+        for versions in self.st.values_mut() {
+            let i = versions.partition_point(|&(ts, _)| ts <= t);
+            if i > 1 {
+                versions.drain(..i - 1);
+            }
+        }
+    }
+}
+
+impl<K: Ord, V> Default for TemporalST<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn get_at() {
+        let mut st = TemporalST::new();
+        st.put("price", 10, 100);
+        st.put("price", 12, 200);
+        st.put("price", 11, 150); // out of order: inserted between
+        st.put("stock", 5, 120);
+
+        assert_eq!(st.size(), 2);
+        assert_eq!(st.get_at(&"price", 99), None);
+        assert_eq!(st.get_at(&"price", 100), Some(&10));
+        assert_eq!(st.get_at(&"price", 149), Some(&10));
+        assert_eq!(st.get_at(&"price", 150), Some(&11));
+        assert_eq!(st.get_at(&"price", 1000), Some(&12));
+        assert_eq!(st.get_at(&"stock", 119), None);
+        assert_eq!(st.get_at(&"bonds", 1000), None);
+
+        assert_eq!(st.latest(&"price"), Some(&12));
+        assert_eq!(st.history(&"price"), &[(100, 10), (150, 11), (200, 12)]);
+        assert!(st.history(&"bonds").is_empty());
+
+        // same timestamp replaces the version
+        st.put("price", 13, 150);
+        assert_eq!(st.get_at(&"price", 199), Some(&13));
+        assert_eq!(st.history(&"price").len(), 3);
+    }
+
+    #[test]
+    fn prune_before() {
+        let mut st = TemporalST::new();
+        st.put('a', 1, 10);
+        st.put('a', 2, 20);
+        st.put('a', 3, 30);
+        st.put('b', 1, 40);
+
+        st.prune_before(25);
+        // the version at 20 is still visible at 25
+        assert_eq!(st.history(&'a'), &[(20, 2), (30, 3)]);
+        assert_eq!(st.get_at(&'a', 25), Some(&2));
+        assert_eq!(st.get_at(&'a', 10), None);
+        assert_eq!(st.history(&'b'), &[(40, 1)]);
+
+        st.prune_before(30);
+        assert_eq!(st.history(&'a'), &[(30, 3)]);
+    }
+
+    #[test]
+    fn random() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut st = TemporalST::new();
+        // naive reference: every put in order, later puts win on equal timestamps
+        let mut puts: Vec<(u8, u64, u32)> = Vec::new();
+        let get_at = |puts: &[(u8, u64, u32)], k: u8, t: u64| {
+            puts.iter()
+                .enumerate()
+                .filter(|(_, &(pk, pt, _))| pk == k && pt <= t)
+                .max_by_key(|(i, &(_, pt, _))| (pt, *i))
+                .map(|(_, &(_, _, v))| v)
+        };
+
+        for i in 0..500 {
+            let k = rng.gen_range(0..8);
+            let t = rng.gen_range(0..100);
+            st.put(k, i, t);
+            puts.push((k, t, i));
+        }
+        for _ in 0..500 {
+            let k = rng.gen_range(0..10);
+            let t = rng.gen_range(0..110);
+            assert_eq!(st.get_at(&k, t).copied(), get_at(&puts, k, t));
+        }
+
+        st.prune_before(50);
+        for _ in 0..500 {
+            let k = rng.gen_range(0..10);
+            let t = rng.gen_range(50..110);
+            assert_eq!(st.get_at(&k, t).copied(), get_at(&puts, k, t));
+        }
+    }
+}
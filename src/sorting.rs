@@ -1,7 +1,10 @@
 pub mod heap;
 pub mod heap2;
+pub mod index_max_pq;
 pub mod index_min_pq;
 pub mod insertion;
+pub mod knapsack;
+pub mod kth;
 pub mod max_pq;
 pub mod merge;
 pub mod merge2;
@@ -12,5 +15,6 @@ pub mod min_pq;
 pub mod quick;
 pub mod quick2;
 pub mod quick_three_way;
+pub mod radix_heap;
 pub mod selection;
 pub mod shell;
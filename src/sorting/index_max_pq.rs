@@ -0,0 +1,231 @@
+//! # Index max priority queue
+//!
+//! It is to associate a unique integer `index` with each item.
+//! Given (i, t), keys[i] = t; inverse_pq[i] = j, and pq[j] = i.
+use std::cmp::PartialOrd;
+
+pub struct IndexMaxPQ<T> {
+    pq: Vec<usize>,         // binary heap using 1-based indexing
+    inverse_pq: Vec<usize>, // inverse of pq: inverse[pq[i]] = pq[inverse[i]] = i
+    n: usize,
+    max_n: usize,
+    keys: Vec<T>,
+}
+
+impl<T: Default + Copy + PartialOrd> IndexMaxPQ<T> {
+    pub fn new(max_n: usize) -> Self {
+        IndexMaxPQ {
+            pq: vec![0; max_n + 1],
+            inverse_pq: vec![0; max_n + 1],
+            n: 0,
+            max_n,
+            keys: vec![T::default(); max_n + 1],
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    pub fn contains(&self, i: usize) -> bool {
+        assert!(i < self.max_n);
+        self.inverse_pq[i] != 0
+    }
+
+    pub fn size(&self) -> usize {
+        self.n
+    }
+
+    pub fn insert(&mut self, i: usize, t: T) {
+        if self.contains(i) {
+            panic!("index is already in the priority queue");
+        }
+
+        self.n += 1;
+        self.inverse_pq[i] = self.n;
+        self.pq[self.n] = i;
+        self.keys[i] = t;
+        self.swim(self.n);
+    }
+
+    pub fn max_index(&self) -> Option<usize> {
+        if self.n == 0 {
+            return None;
+        }
+        Some(self.pq[1])
+    }
+
+    pub fn max_key(&self) -> Option<T> {
+        if self.n == 0 {
+            return None;
+        }
+        Some(self.keys[self.pq[1]])
+    }
+
+    pub fn del_max(&mut self) -> Option<usize> {
+        if self.n == 0 {
+            return None;
+        }
+
+        let max = self.pq[1];
+        self.exch(1, self.n);
+        self.n -= 1;
+        self.sink(1);
+        assert_eq!(max, self.pq[self.n + 1]);
+        self.inverse_pq[max] = 0;
+        self.pq[self.n + 1] = 0;
+        Some(max)
+    }
+
+    pub fn key_of(&self, i: usize) -> Option<T> {
+        if !self.contains(i) {
+            return None;
+        }
+        Some(self.keys[i])
+    }
+
+    /// Change the key associated with index `i` to the specified value
+    pub fn change_key(&mut self, i: usize, t: T) {
+        if !self.contains(i) {
+            panic!("no such element");
+        }
+        self.keys[i] = t;
+        // `swim` and `sink` can be exchanged
+        self.swim(self.inverse_pq[i]);
+        self.sink(self.inverse_pq[i]);
+    }
+
+    /// Decrease the key associated with index i to the specified value
+    pub fn decrease_key(&mut self, i: usize, key: T) {
+        if !self.contains(i) {
+            panic!("no such element");
+        }
+        if self.keys[i] <= key {
+            panic!("Calling decrease_key() with a key >= current key");
+        }
+        self.keys[i] = key;
+        self.sink(self.inverse_pq[i]);
+    }
+
+    /// Increase the key associated with index i to the specified value
+    pub fn increase_key(&mut self, i: usize, key: T) {
+        if !self.contains(i) {
+            panic!("no such element");
+        }
+        if self.keys[i] >= key {
+            panic!("Calling increase() with a key <= current key");
+        }
+        self.keys[i] = key;
+        self.swim(self.inverse_pq[i]);
+    }
+
+    fn less(&self, i: usize, j: usize) -> bool {
+        self.keys[self.pq[i]] < self.keys[self.pq[j]]
+    }
+
+    fn exch(&mut self, i: usize, j: usize) {
+        self.pq.swap(i, j);
+        self.inverse_pq[self.pq[i]] = i;
+        self.inverse_pq[self.pq[j]] = j;
+    }
+
+    fn swim(&mut self, k: usize) {
+        let mut index = k;
+        while index > 1 && self.less(index / 2, index) {
+            self.exch(index, index / 2);
+            index /= 2;
+        }
+    }
+
+    fn sink(&mut self, k: usize) {
+        let mut index = k;
+        while 2 * index <= self.n {
+            let mut j = 2 * index;
+            if j < self.n && self.less(j, j + 1) {
+                j += 1;
+            }
+            if !self.less(index, j) {
+                break;
+            }
+            self.exch(index, j);
+            index = j;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert_del() {
+        let v = [
+            "it", "was", "the", "best", "of", "times", "it", "was", "the", "worst",
+        ];
+
+        let mut pq = IndexMaxPQ::new(v.len());
+
+        for (i, &item) in v.iter().enumerate() {
+            pq.insert(i, item);
+        }
+
+        let mut index = vec![];
+        while !pq.is_empty() {
+            index.push(pq.del_max().unwrap());
+        }
+        // worst was was times the the of it it best
+        assert_eq!(index, vec![9, 1, 7, 5, 8, 2, 4, 6, 0, 3]);
+    }
+
+    #[test]
+    fn key_of() {
+        let v = ["it", "was", "the", "best"];
+
+        let mut pq = IndexMaxPQ::new(10);
+
+        for (i, &item) in v.iter().enumerate() {
+            pq.insert(i, item);
+        }
+
+        assert_eq!(pq.key_of(0), Some("it"));
+        assert_eq!(pq.key_of(1), Some("was"));
+        assert_eq!(pq.key_of(2), Some("the"));
+        assert_eq!(pq.key_of(3), Some("best"));
+        assert_eq!(pq.key_of(4), None);
+    }
+
+    #[test]
+    fn change_key() {
+        let v = ["it", "was", "the", "best"];
+
+        let mut pq = IndexMaxPQ::new(v.len());
+
+        for (i, &item) in v.iter().enumerate() {
+            pq.insert(i, item);
+        }
+        assert_eq!(pq.max_index(), Some(1));
+        pq.change_key(0, "zoo");
+
+        assert_eq!(pq.key_of(0), Some("zoo"));
+        assert_eq!(pq.max_index(), Some(0));
+    }
+
+    #[test]
+    fn increase_decrease_key() {
+        let mut pq = IndexMaxPQ::new(4);
+        pq.insert(0, 3);
+        pq.insert(1, 5);
+        pq.insert(2, 4);
+        assert_eq!(pq.max_key(), Some(5));
+
+        pq.increase_key(0, 6);
+        assert_eq!(pq.max_index(), Some(0));
+
+        pq.decrease_key(0, 1);
+        assert_eq!(pq.max_index(), Some(1));
+        assert_eq!(pq.del_max(), Some(1));
+        assert_eq!(pq.del_max(), Some(2));
+        assert_eq!(pq.del_max(), Some(0));
+        assert_eq!(pq.del_max(), None);
+    }
+}
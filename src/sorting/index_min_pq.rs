@@ -4,6 +4,7 @@
 //! Given (i, t), keys[i] = t; inverse_pq[i] = j, and pq[j] = i.
 use std::cmp::PartialOrd;
 
+#[derive(Clone)]
 pub struct IndexMinPQ<T> {
     pq: Vec<usize>,         // binary heap using 1-based indexing
     inverse_pq: Vec<usize>, // inverse of pq: inverse[pq[i]] = pq[inverse[i]] = i
@@ -119,6 +120,21 @@ impl<T: Default + Copy + PartialOrd> IndexMinPQ<T> {
         self.sink(self.inverse_pq[i]);
     }
 
+    /// Returns the (index, key) pairs in increasing order of index.
+    /// The queue is not modified.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, T)> + '_ {
+        (0..self.max_n)
+            .filter(|&i| self.inverse_pq[i] != 0)
+            .map(|i| (i, self.keys[i]))
+    }
+
+    /// Returns the (index, key) pairs in increasing order of key,
+    /// i.e., the order `del_min` would produce.
+    /// It drains a copy of the queue, so each step takes logarithmic time.
+    pub fn sorted_iter(&self) -> SortedIter<T> {
+        SortedIter { pq: self.clone() }
+    }
+
     fn greater(&self, i: usize, j: usize) -> bool {
         self.keys[self.pq[i]] > self.keys[self.pq[j]]
     }
@@ -153,6 +169,19 @@ impl<T: Default + Copy + PartialOrd> IndexMinPQ<T> {
     }
 }
 
+pub struct SortedIter<T> {
+    pq: IndexMinPQ<T>,
+}
+
+impl<T: Default + Copy + PartialOrd> Iterator for SortedIter<T> {
+    type Item = (usize, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let key = self.pq.min_key()?;
+        self.pq.del_min().map(|i| (i, key))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -209,4 +238,25 @@ mod tests {
         assert_eq!(pq.key_of(0), Some("apple"));
         assert_eq!(pq.min_index(), Some(0));
     }
+
+    #[test]
+    fn iter() {
+        let v = ["it", "was", "the", "best", "of"];
+
+        let mut pq = IndexMinPQ::new(10);
+        for (i, &item) in v.iter().enumerate() {
+            pq.insert(i * 2, item);
+        }
+        pq.del_min();
+
+        let pairs = pq.iter().collect::<Vec<(usize, &str)>>();
+        assert_eq!(pairs, vec![(0, "it"), (2, "was"), (4, "the"), (8, "of")]);
+
+        let pairs = pq.sorted_iter().collect::<Vec<(usize, &str)>>();
+        assert_eq!(pairs, vec![(0, "it"), (8, "of"), (4, "the"), (2, "was")]);
+
+        // both iterators leave the queue untouched
+        assert_eq!(pq.size(), 4);
+        assert_eq!(pq.min_index(), Some(0));
+    }
 }
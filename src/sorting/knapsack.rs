@@ -0,0 +1,253 @@
+//! # Subset sum and 0/1 knapsack
+//!
+//! Exact pseudo-polynomial dynamic programs.
+//!
+//! `subset_sum_exists` and `partition_min_diff` keep the set of reachable sums as a bitset
+//! packed into `u64` blocks, so adding an item is one shift-or over (target / 64) words:
+//! O(n * target / 64) time.
+//!
+//! `zero_one_knapsack` fills the classic (n + 1) x (capacity + 1) table in O(n * capacity)
+//! time and space, which is what allows the chosen items to be reconstructed.
+
+// reachable subset sums in 0..=limit
+struct Bitset {
+    words: Vec<u64>,
+    limit: usize,
+}
+
+impl Bitset {
+    fn new(limit: usize) -> Self {
+        let mut words = vec![0u64; limit / 64 + 1];
+        words[0] = 1; // the empty subset sums to 0
+        Bitset { words, limit }
+    }
+
+    fn get(&self, i: usize) -> bool {
+        self.words[i / 64] >> (i % 64) & 1 == 1
+    }
+
+    // bits |= bits << k, dropping sums above the limit
+    fn shift_or(&mut self, k: usize) {
+        if k > self.limit {
+            return;
+        }
+        let (ws, bs) = (k / 64, k % 64);
+        // from high to low, so every word read is still the old value
+        for i in (ws..self.words.len()).rev() {
+            let mut shifted = self.words[i - ws] << bs;
+            if bs > 0 && i > ws {
+                shifted |= self.words[i - ws - 1] >> (64 - bs);
+            }
+            self.words[i] |= shifted;
+        }
+        let last = self.words.len() - 1;
+        self.words[last] &= u64::MAX >> (63 - self.limit % 64);
+    }
+}
+
+// the sum of the items that are at most `limit`, saturating; no larger sum is reachable
+fn reachable_max(a: &[u64], limit: u64) -> u64 {
+    a.iter()
+        .filter(|&&x| x <= limit)
+        .fold(0, |sum, &x| sum.saturating_add(x))
+}
+
+fn reachable(a: &[u64], limit: u64) -> Bitset {
+    let mut bits = Bitset::new(limit as usize);
+    for &x in a {
+        if x <= limit {
+            bits.shift_or(x as usize);
+        }
+    }
+    bits
+}
+
+/// Is there a subset of `a` that sums to exactly `target`?
+pub fn subset_sum_exists(a: &[u64], target: u64) -> bool {
+    // so that a huge target does not size a huge bitset
+    if target > reachable_max(a, target) {
+        return false;
+    }
+    reachable(a, target).get(target as usize)
+}
+
+/// Returns the maximum total value of items whose total weight is at most `capacity`,
+/// and the indices of one such set of items in increasing order.
+pub fn zero_one_knapsack(weights: &[u64], values: &[u64], capacity: u64) -> (u64, Vec<usize>) {
+    assert_eq!(
+        weights.len(),
+        values.len(),
+        "weights and values must have the same length"
+    );
+    let n = weights.len();
+    // capacity beyond what the items can fill does not change the answer,
+    // and would only make the table huge
+    let cap = capacity.min(reachable_max(weights, capacity)) as usize;
+    // best[i][c] = best value using the first i items with capacity c
+    let mut best = vec![vec![0u64; cap + 1]; n + 1];
+    for i in 1..=n {
+        let (w, v) = (weights[i - 1], values[i - 1]);
+        for c in 0..=cap {
+            best[i][c] = best[i - 1][c];
+            if w <= c as u64 {
+                best[i][c] = best[i][c].max(best[i - 1][c - w as usize] + v);
+            }
+        }
+    }
+
+    // walk back: item i - 1 was taken iff it changed the optimum
+    let mut items = Vec::new();
+    let mut c = cap;
+    for i in (1..=n).rev() {
+        if best[i][c] != best[i - 1][c] {
+            items.push(i - 1);
+            c -= weights[i - 1] as usize;
+        }
+    }
+    items.reverse();
+    (best[n][cap], items)
+}
+
+/// Splits `a` into two subsets and returns the smallest possible difference of their sums.
+pub fn partition_min_diff(a: &[u64]) -> u64 {
+    let total: u64 = a.iter().sum();
+    let half = total / 2;
+    let bits = reachable(a, half);
+    // the subset sum closest to half from below
+    let s = (0..=half).rev().find(|&s| bits.get(s as usize)).unwrap();
+    total - 2 * s
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    use super::*;
+
+    // all (weight, value) sums over the 2^n subsets
+    fn subsets(weights: &[u64], values: &[u64]) -> Vec<(u64, u64)> {
+        (0..1u32 << weights.len())
+            .map(|mask| {
+                (0..weights.len())
+                    .filter(|i| mask >> i & 1 == 1)
+                    .fold((0, 0), |(w, v), i| (w + weights[i], v + values[i]))
+            })
+            .collect()
+    }
+
+    fn check_items(
+        weights: &[u64],
+        values: &[u64],
+        capacity: u64,
+        (best, items): (u64, Vec<usize>),
+    ) {
+        let w: u64 = items.iter().map(|&i| weights[i]).sum();
+        let v: u64 = items.iter().map(|&i| values[i]).sum();
+        assert!(w <= capacity);
+        assert_eq!(v, best);
+    }
+
+    #[test]
+    fn subset_sum() {
+        // a target above the sum of the items is not even allocated for
+        assert!(!subset_sum_exists(&[1, 2, 3], u64::MAX));
+        assert!(!subset_sum_exists(&[1, 2, 3], 7));
+        assert!(subset_sum_exists(&[1, 2, 3], 6));
+
+        let a = [3, 34, 4, 12, 5, 2];
+        assert!(subset_sum_exists(&a, 9));
+        assert!(!subset_sum_exists(&a, 30));
+        assert!(subset_sum_exists(&a, 0));
+        assert!(subset_sum_exists(&a, 60));
+        assert!(!subset_sum_exists(&a, 61));
+        assert!(!subset_sum_exists(&[], 1));
+
+        // sums that cross u64 block boundaries
+        assert!(subset_sum_exists(&[63, 1, 64, 129], 257));
+        assert!(!subset_sum_exists(&[63, 64, 129], 128));
+    }
+
+    #[test]
+    fn knapsack() {
+        let weights = [10, 20, 30];
+        let values = [60, 100, 120];
+        let result = zero_one_knapsack(&weights, &values, 50);
+        assert_eq!(result, (220, vec![1, 2]));
+
+        let weights = [1, 3, 4, 5];
+        let values = [1, 4, 5, 7];
+        let result = zero_one_knapsack(&weights, &values, 7);
+        assert_eq!(result.0, 9);
+        check_items(&weights, &values, 7, result);
+    }
+
+    #[test]
+    fn knapsack_edge_cases() {
+        assert_eq!(zero_one_knapsack(&[1, 2], &[5, 6], 0), (0, vec![]));
+        // a huge capacity is clamped to what the items weigh
+        assert_eq!(
+            zero_one_knapsack(&[2, 3, u64::MAX], &[3, 4, 1], u64::MAX - 1),
+            (7, vec![0, 1])
+        );
+        assert_eq!(zero_one_knapsack(&[10, 20], &[5, 6], 9), (0, vec![]));
+        assert_eq!(zero_one_knapsack(&[], &[], 10), (0, vec![]));
+    }
+
+    #[test]
+    fn partition() {
+        assert_eq!(partition_min_diff(&[1, 6, 11, 5]), 1);
+        assert_eq!(partition_min_diff(&[3, 1, 4, 2, 2]), 0);
+        assert_eq!(partition_min_diff(&[7]), 7);
+        assert_eq!(partition_min_diff(&[]), 0);
+    }
+
+    #[test]
+    fn brute_force() {
+        let mut rng = StdRng::seed_from_u64(11);
+        for _ in 0..50 {
+            let n = rng.gen_range(0..=12);
+            let weights: Vec<u64> = (0..n).map(|_| rng.gen_range(1..100)).collect();
+            let values: Vec<u64> = (0..n).map(|_| rng.gen_range(0..100)).collect();
+            let all = subsets(&weights, &values);
+            let total: u64 = weights.iter().sum();
+
+            let capacity = rng.gen_range(0..=total + 1);
+            let expected = all
+                .iter()
+                .filter(|&&(w, _)| w <= capacity)
+                .map(|&(_, v)| v)
+                .max()
+                .unwrap();
+            let result = zero_one_knapsack(&weights, &values, capacity);
+            assert_eq!(result.0, expected);
+            check_items(&weights, &values, capacity, result);
+
+            let target = rng.gen_range(0..=total + 1);
+            assert_eq!(
+                subset_sum_exists(&weights, target),
+                all.iter().any(|&(w, _)| w == target)
+            );
+
+            let diff = all
+                .iter()
+                .map(|&(w, _)| (total - w).abs_diff(w))
+                .min()
+                .unwrap();
+            assert_eq!(partition_min_diff(&weights), diff);
+        }
+
+        // n = 20 for subset sum and partition
+        let a: Vec<u64> = (0..20).map(|_| rng.gen_range(1..1000)).collect();
+        let all = subsets(&a, &a);
+        let total: u64 = a.iter().sum();
+        for target in (0..=total).step_by(97) {
+            assert_eq!(
+                subset_sum_exists(&a, target),
+                all.iter().any(|&(w, _)| w == target)
+            );
+        }
+        let diff = all.iter().map(|&(w, _)| (total - w).abs_diff(w)).min();
+        assert_eq!(Some(partition_min_diff(&a)), diff);
+    }
+}
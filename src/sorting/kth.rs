@@ -0,0 +1,166 @@
+//! # The k smallest or largest items of an iterator
+//!
+//! `k_smallest` and `k_largest` keep a bounded heap of the best k items seen so far, replacing
+//! its root when a better item arrives, so they take O(n log k) time and O(k) space, and read
+//! the iterator once. `kth_smallest` buffers the items and uses quickselect, in expected O(n) time.
+//! Items need not be `Copy` or `Clone`.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use rand::seq::SliceRandom;
+
+/// Returns the k smallest items in ascending order (all of them if there are fewer than k).
+pub fn k_smallest<T: Ord>(iter: impl IntoIterator<Item = T>, k: usize) -> Vec<T> {
+    if k == 0 {
+        return Vec::new();
+    }
+    let iter = iter.into_iter();
+    // a max-heap: its root is the largest of the k smallest so far;
+    // k may be far more than the number of items, so it is not preallocated from k alone
+    let mut heap = BinaryHeap::with_capacity(k.min(iter.size_hint().0));
+    for item in iter {
+        if heap.len() < k {
+            heap.push(item);
+        } else if let Some(mut top) = heap.peek_mut() {
+            if item < *top {
+                *top = item;
+            }
+        }
+    }
+    heap.into_sorted_vec()
+}
+
+/// Returns the k largest items in descending order (all of them if there are fewer than k).
+pub fn k_largest<T: Ord>(iter: impl IntoIterator<Item = T>, k: usize) -> Vec<T> {
+    k_smallest(iter.into_iter().map(Reverse), k)
+        .into_iter()
+        .map(|Reverse(item)| item)
+        .collect()
+}
+
+/// Returns the item of rank k (0-based) in ascending order, or `None` if there are at most k items.
+pub fn kth_smallest<T: Ord>(iter: impl IntoIterator<Item = T>, k: usize) -> Option<T> {
+    let mut a = iter.into_iter().collect::<Vec<T>>();
+    if k >= a.len() {
+        return None;
+    }
+    // shuffle to make the running time independent of the input order
+    a.shuffle(&mut rand::thread_rng());
+    let (mut lo, mut hi) = (0, a.len() - 1);
+    while lo < hi {
+        let j = partition(&mut a, lo, hi);
+        match j.cmp(&k) {
+            std::cmp::Ordering::Less => lo = j + 1,
+            std::cmp::Ordering::Greater => hi = j - 1,
+            std::cmp::Ordering::Equal => break,
+        }
+    }
+    Some(a.swap_remove(k))
+}
+
+// partitions a[lo..=hi] around a[lo], returning its final index j:
+// a[lo..j] <= a[j] <= a[j + 1..=hi]
+fn partition<T: Ord>(a: &mut [T], lo: usize, hi: usize) -> usize {
+    let (mut i, mut j) = (lo + 1, hi);
+    loop {
+        while i < hi && a[i] < a[lo] {
+            i += 1;
+        }
+        while j > lo && a[lo] < a[j] {
+            j -= 1;
+        }
+        if i >= j {
+            break;
+        }
+        a.swap(i, j);
+        i += 1;
+        j -= 1;
+    }
+    a.swap(lo, j);
+    j
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn same_as_sort() {
+        let mut rng = StdRng::seed_from_u64(62);
+        for _ in 0..50 {
+            let n = rng.gen_range(0..200);
+            let a = (0..n).map(|_| rng.gen_range(0..50)).collect::<Vec<i32>>();
+            let mut sorted = a.clone();
+            sorted.sort_unstable();
+            let k = rng.gen_range(0..=n);
+
+            assert_eq!(k_smallest(a.iter().copied(), k), sorted[..k]);
+            let mut largest = sorted[n - k..].to_vec();
+            largest.reverse();
+            assert_eq!(k_largest(a.iter().copied(), k), largest);
+            assert_eq!(kth_smallest(a.iter().copied(), k), sorted.get(k).copied());
+        }
+    }
+
+    #[test]
+    fn small_k() {
+        let a = [5, 3, 9, 1];
+        assert_eq!(k_smallest(a, 0), Vec::<i32>::new());
+        assert_eq!(k_largest(a, 0), Vec::<i32>::new());
+        assert_eq!(k_smallest(a, 10), vec![1, 3, 5, 9]);
+        assert_eq!(k_largest(a, 10), vec![9, 5, 3, 1]);
+        assert_eq!(kth_smallest(a, 4), None);
+        assert_eq!(kth_smallest(Vec::<i32>::new(), 0), None);
+
+        // no capacity overflow for a huge k
+        assert_eq!(k_smallest([3, 1, 2], usize::MAX), vec![1, 2, 3]);
+        assert_eq!(k_largest([3, 1, 2], usize::MAX), vec![3, 2, 1]);
+        // an iterator that does not know its length
+        let odd = (1..=5).rev().filter(|x| x % 2 == 1);
+        assert_eq!(k_smallest(odd, usize::MAX), vec![1, 3, 5]);
+    }
+
+    #[test]
+    fn duplicates() {
+        let a = [2, 3, 2, 1, 2, 3];
+        assert_eq!(k_smallest(a, 2), vec![1, 2]);
+        assert_eq!(k_smallest(a, 3), vec![1, 2, 2]);
+        assert_eq!(k_largest(a, 3), vec![3, 3, 2]);
+        assert_eq!(kth_smallest(a, 1), Some(2));
+        assert_eq!(kth_smallest(a, 3), Some(2));
+        assert_eq!(kth_smallest(a, 4), Some(3));
+        assert_eq!(kth_smallest([7; 100], 50), Some(7));
+    }
+
+    #[test]
+    fn not_copy() {
+        let words = ["pear", "fig", "apple", "kiwi", "banana"].map(String::from);
+        assert_eq!(k_smallest(words.clone(), 2), vec!["apple", "banana"]);
+        assert_eq!(k_largest(words.clone(), 2), vec!["pear", "kiwi"]);
+        assert_eq!(kth_smallest(words, 2), Some(String::from("fig")));
+    }
+
+    #[test]
+    fn consumed_once() {
+        let calls = Cell::new(0);
+        let iter = (0..1000).rev().inspect(|_| calls.set(calls.get() + 1));
+        assert_eq!(k_smallest(iter, 3), vec![0, 1, 2]);
+        assert_eq!(calls.get(), 1000);
+
+        calls.set(0);
+        let iter = (0..1000).inspect(|_| calls.set(calls.get() + 1));
+        assert_eq!(k_largest(iter, 3), vec![999, 998, 997]);
+        assert_eq!(calls.get(), 1000);
+
+        calls.set(0);
+        let iter = (0..1000).inspect(|_| calls.set(calls.get() + 1));
+        assert_eq!(kth_smallest(iter, 500), Some(500));
+        assert_eq!(calls.get(), 1000);
+    }
+}
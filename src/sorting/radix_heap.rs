@@ -0,0 +1,141 @@
+//! # Radix heap
+//!
+//! A monotone priority queue on `u64` keys: a key may never be smaller than the last key removed,
+//! which is exactly how Dijkstra's algorithm uses its queue.
+//!
+//! Bucket i > 0 holds the keys whose highest bit differing from the last removed key is bit i - 1;
+//! bucket 0 holds keys equal to it. When bucket 0 runs empty, the first non-empty bucket is
+//! redistributed around its minimum, and every item lands in a strictly lower bucket.
+//! So an item moves at most 64 times, and `pop` is O(64) amortized.
+
+const BUCKETS: usize = 65;
+
+pub struct RadixHeap<V> {
+    buckets: Vec<Vec<(u64, V)>>,
+    last: u64,    // last key removed
+    n: usize,     // number of items
+    moves: usize, // number of times an item was redistributed
+}
+
+impl<V> RadixHeap<V> {
+    pub fn new() -> Self {
+        RadixHeap {
+            buckets: (0..BUCKETS).map(|_| Vec::new()).collect(),
+            last: 0,
+            n: 0,
+            moves: 0,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    fn bucket(&self, key: u64) -> usize {
+        (64 - (key ^ self.last).leading_zeros()) as usize
+    }
+
+    /// Adds a value with the given key.
+    ///
+    /// # Panics
+    /// if `key` is less than the last key removed by `pop`.
+    pub fn push(&mut self, key: u64, value: V) {
+        if key < self.last {
+            panic!("key {} is less than the last popped key {}", key, self.last);
+        }
+        let i = self.bucket(key);
+        self.buckets[i].push((key, value));
+        self.n += 1;
+    }
+
+    /// Removes and returns an item with the smallest key.
+    pub fn pop(&mut self) -> Option<(u64, V)> {
+        if self.buckets[0].is_empty() {
+            let i = self.buckets.iter().position(|b| !b.is_empty())?;
+            let items = std::mem::take(&mut self.buckets[i]);
+            self.last = items.iter().map(|&(k, _)| k).min().unwrap();
+            for (k, v) in items {
+                let j = self.bucket(k);
+                self.buckets[j].push((k, v));
+                self.moves += 1;
+            }
+        }
+        self.n -= 1;
+        self.buckets[0].pop()
+    }
+}
+
+impl<V> Default for RadixHeap<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn push_pop() {
+        let mut heap = RadixHeap::new();
+        for (k, v) in [(5, 'a'), (1, 'b'), (9, 'c'), (1, 'd'), (0, 'e')] {
+            heap.push(k, v);
+        }
+        assert_eq!(heap.len(), 5);
+        assert_eq!(heap.pop(), Some((0, 'e')));
+        assert_eq!(heap.pop().map(|(k, _)| k), Some(1));
+        heap.push(3, 'f');
+        assert_eq!(heap.pop().map(|(k, _)| k), Some(1));
+        assert_eq!(heap.pop(), Some((3, 'f')));
+        assert_eq!(heap.pop(), Some((5, 'a')));
+        assert_eq!(heap.pop(), Some((9, 'c')));
+        assert_eq!(heap.pop(), None);
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    fn monotone_workload() {
+        let mut rng = StdRng::seed_from_u64(5);
+        let mut heap = RadixHeap::new();
+        let mut pushes = 0;
+        let mut last = 0;
+        for _ in 0..2000 {
+            // pushes are never below the last popped key
+            for _ in 0..rng.gen_range(0..4) {
+                let bits = rng.gen_range(0..40);
+                let key = last + rng.gen_range(0..1u64 << bits);
+                heap.push(key, ());
+                pushes += 1;
+            }
+            if let Some((key, _)) = heap.pop() {
+                assert!(key >= last);
+                last = key;
+            }
+        }
+        let mut keys = Vec::new();
+        while let Some((key, _)) = heap.pop() {
+            keys.push(key);
+        }
+        assert!(keys.windows(2).all(|w| w[0] <= w[1]));
+        assert!(keys.first().is_none_or(|&k| k >= last));
+
+        // each item only ever moves to a lower bucket
+        assert!(heap.moves <= pushes * (BUCKETS - 1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn not_monotone() {
+        let mut heap = RadixHeap::new();
+        heap.push(10, ());
+        heap.pop();
+        heap.push(9, ());
+    }
+}
@@ -1,4 +1,13 @@
 pub mod alphabet;
+pub mod boyer_moore;
+pub mod fm_index;
 pub mod key_idx_cnt;
+pub mod kmp;
 pub mod lsd;
 pub mod msd;
+pub mod nfa;
+pub mod quick3_string;
+pub mod rabin_karp;
+pub mod trie_st;
+pub mod tst;
+pub mod wavelet;
@@ -0,0 +1,260 @@
+//! # Boyer-Moore substring search
+//!
+//! Scans the pattern from right to left and, on a mismatch, skips ahead by the larger of
+//! the shifts given by the bad-character (mismatched character) and good-suffix heuristics.
+//! The pattern and text are matched byte by byte, so the returned index is a byte offset,
+//! and either may be a `&str` or binary data (`&[u8]`).
+//! `AlphabetBoyerMoore` matches characters of an `Alphabet` instead, and returns character offsets.
+//!
+//! Typically about n / m character compares; O(n * m) in the worst case.
+
+use super::alphabet::Alphabet;
+
+const R: usize = 256; // radix
+
+pub struct BoyerMoore {
+    right: [i32; R], // right[c] = rightmost position of byte c in the pattern, or -1
+    good_suffix: Vec<usize>, // good_suffix[j] = shift when pat[j..] matched and pat[j - 1] did not
+    pat: Vec<u8>,
+}
+
+impl BoyerMoore {
+    pub fn new(pat: impl AsRef<[u8]>) -> Self {
+        let pat = pat.as_ref();
+        let mut right = [-1; R];
+        for (j, &c) in pat.iter().enumerate() {
+            right[c as usize] = j as i32;
+        }
+        BoyerMoore {
+            right,
+            good_suffix: good_suffix(pat),
+            pat: pat.to_vec(),
+        }
+    }
+
+    /// Returns the index of the first occurrence of the pattern in `txt`.
+    pub fn search(&self, txt: impl AsRef<[u8]>) -> Option<usize> {
+        self.search_from(txt.as_ref(), 0)
+    }
+
+    /// Returns the index of every occurrence of the pattern in `txt`, overlapping ones included.
+    pub fn search_all(&self, txt: impl AsRef<[u8]>) -> Vec<usize> {
+        let txt = txt.as_ref();
+        let mut found = Vec::new();
+        let mut start = 0;
+        while let Some(i) = self.search_from(txt, start) {
+            found.push(i);
+            start = i + 1;
+        }
+        found
+    }
+
+    // the first occurrence at or after index `start`
+    fn search_from(&self, txt: &[u8], start: usize) -> Option<usize> {
+        let right = |c: &u8| self.right[*c as usize];
+        search_from(&self.pat, txt, right, &self.good_suffix, start)
+    }
+}
+
+/// Boyer-Moore over the characters of an `Alphabet`, returning character (not byte) offsets.
+pub struct AlphabetBoyerMoore<'a> {
+    alphabet: &'a Alphabet,
+    right: Vec<i32>, // right[c] for the alphabet index c
+    good_suffix: Vec<usize>,
+    pat: Vec<Option<u16>>, // alphabet indices; `None` in the text is a character outside the alphabet
+}
+
+impl<'a> AlphabetBoyerMoore<'a> {
+    /// Panics if the pattern has a character outside `alphabet`.
+    pub fn new(pat: &str, alphabet: &'a Alphabet) -> Self {
+        let pat: Vec<Option<u16>> = alphabet.to_indices(pat).into_iter().map(Some).collect();
+        let mut right = vec![-1; alphabet.radix() as usize];
+        for (j, c) in pat.iter().enumerate() {
+            right[c.unwrap() as usize] = j as i32;
+        }
+        AlphabetBoyerMoore {
+            alphabet,
+            right,
+            good_suffix: good_suffix(&pat),
+            pat,
+        }
+    }
+
+    /// Returns the character index of the first occurrence of the pattern in `txt`.
+    pub fn search(&self, txt: &str) -> Option<usize> {
+        let txt: Vec<Option<u16>> = txt
+            .chars()
+            .map(|c| self.alphabet.contains(c).then(|| self.alphabet.to_index(c)))
+            .collect();
+        let right = |c: &Option<u16>| c.map_or(-1, |c| self.right[c as usize]);
+        search_from(&self.pat, &txt, right, &self.good_suffix, 0)
+    }
+}
+
+// the first occurrence of pat in txt at or after index `start`,
+// where right(c) is the rightmost position of c in pat, or -1
+fn search_from<T: PartialEq>(
+    pat: &[T],
+    txt: &[T],
+    right: impl Fn(&T) -> i32,
+    good_suffix: &[usize],
+    start: usize,
+) -> Option<usize> {
+    let m = pat.len();
+    if m > txt.len() {
+        return None;
+    }
+    let mut i = start;
+    while i <= txt.len() - m {
+        let mut skip = 0;
+        for j in (0..m).rev() {
+            if pat[j] != txt[i + j] {
+                let bad_character = j as i32 - right(&txt[i + j]);
+                skip = (bad_character.max(1) as usize).max(good_suffix[j + 1]);
+                break;
+            }
+        }
+        if skip == 0 {
+            return Some(i);
+        }
+        i += skip;
+    }
+    None
+}
+
+// shift[j] for 0 <= j <= m: how far the pattern can move when pat[j..] matched the text
+// but pat[j - 1] did not (for j = 0, after a full match), so that the matched suffix lines
+// up with another occurrence of it in the pattern, or a prefix with a suffix of it
+fn good_suffix<T: PartialEq>(pat: &[T]) -> Vec<usize> {
+    let m = pat.len();
+    let mut shift = vec![0; m + 1];
+    // border[i] = start of the widest border of pat[i..]
+    let mut border = vec![0; m + 1];
+    let (mut i, mut j) = (m, m + 1);
+    border[i] = j;
+    while i > 0 {
+        while j <= m && pat[i - 1] != pat[j - 1] {
+            if shift[j] == 0 {
+                shift[j] = j - i;
+            }
+            j = border[j];
+        }
+        i -= 1;
+        j -= 1;
+        border[i] = j;
+    }
+    // the rest line a prefix of the pattern up with a suffix of the matched part
+    j = border[0];
+    for (i, s) in shift.iter_mut().enumerate() {
+        if *s == 0 {
+            *s = j;
+        }
+        if i == j {
+            j = border[j];
+        }
+    }
+    shift
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn search() {
+        let bm = BoyerMoore::new("abracadabra");
+        assert_eq!(bm.search("abracadabra is a magic word"), Some(0));
+        assert_eq!(bm.search("abacadabrabracabracadabrabrabracad"), Some(14));
+        assert_eq!(bm.search("say abracadabra"), Some(4));
+        assert_eq!(bm.search("abacadabrabracabracadabr"), None);
+    }
+
+    #[test]
+    fn repeated_characters() {
+        let bm = BoyerMoore::new("NEEDLE");
+        assert_eq!(bm.right[b'E' as usize], 5);
+        assert_eq!(bm.right[b'N' as usize], 0);
+        assert_eq!(bm.right[b'X' as usize], -1);
+        assert_eq!(bm.search("FINDINAHAYSTACKNEEDLEINA"), Some(15));
+
+        let bm = BoyerMoore::new("aab");
+        assert_eq!(bm.search("aaaaaab"), Some(4));
+        assert_eq!(bm.search("aaaaaaa"), None);
+    }
+
+    #[test]
+    fn edge_cases() {
+        assert_eq!(BoyerMoore::new("long pattern").search("short"), None);
+        assert_eq!(BoyerMoore::new("x").search(""), None);
+        assert_eq!(BoyerMoore::new("").search("abc"), Some(0));
+        assert_eq!(BoyerMoore::new("é").search("café"), Some(3));
+    }
+
+    #[test]
+    fn long_text() {
+        let mut txt = "abcdefghij".repeat(100_000);
+        txt.push_str("needle");
+        txt.push_str(&"xyz".repeat(10));
+        let bm = BoyerMoore::new("needle");
+        assert_eq!(bm.search(&txt), Some(1_000_000));
+    }
+
+    #[test]
+    fn search_all() {
+        let bm = BoyerMoore::new("AACAA");
+        assert_eq!(bm.search_all("AACAACAAACAA"), vec![0, 3, 7]);
+        assert_eq!(BoyerMoore::new("aa").search_all("aaaa"), vec![0, 1, 2]);
+        assert_eq!(BoyerMoore::new("ab").search_all("ba"), vec![]);
+        assert_eq!(BoyerMoore::new("").search_all("ab"), vec![0, 1, 2]);
+        assert_eq!(BoyerMoore::new("abc").search_all("ab"), vec![]);
+        assert_eq!(
+            BoyerMoore::new(b"\xff\x00").search_all(b"\x00\xff\x00\xff\x00"),
+            vec![1, 3]
+        );
+    }
+
+    #[test]
+    fn good_suffix_shift() {
+        // after matching "ABC" with a mismatch at X, align the leading "ABC"
+        let shift = good_suffix(b"ABCXXXABC");
+        assert_eq!(shift[6], 6);
+        assert_eq!(shift[9], 1);
+        // after a full match, the next possible one starts at the border "ABC"
+        assert_eq!(shift[0], 6);
+        // no border: move past the match
+        assert_eq!(good_suffix(b"abcd")[0], 4);
+        assert_eq!(good_suffix(b"")[0], 1);
+
+        let bm = BoyerMoore::new("ABCXXXABC");
+        assert_eq!(bm.search("ABCYXXABCXXXABC"), Some(6));
+    }
+
+    #[test]
+    fn end_of_text() {
+        for pat in ["a", "ab", "needle", "aab"] {
+            let txt = format!("{}{}", "xy".repeat(50), pat);
+            assert_eq!(BoyerMoore::new(pat).search(&txt), Some(100));
+            assert_eq!(BoyerMoore::new(pat).search(&txt[..txt.len() - 1]), None);
+        }
+        assert_eq!(BoyerMoore::new("b").search("ab"), Some(1));
+        assert_eq!(BoyerMoore::new("ab").search("ab"), Some(0));
+    }
+
+    #[test]
+    fn alphabet() {
+        let dna = Alphabet::new("ACGT");
+        let bm = AlphabetBoyerMoore::new("GCAGAGAG", &dna);
+        let txt = "GCATCGCAGAGAGTATACAGTACG";
+        assert_eq!(bm.search(txt), Some(5));
+        assert_eq!(bm.search(txt), BoyerMoore::new("GCAGAGAG").search(txt));
+        assert_eq!(bm.search("GCAGAGA"), None);
+        // a character outside the alphabet never matches
+        assert_eq!(bm.search("GCAGAGAXGCAGAGAG"), Some(8));
+
+        // character offsets, not byte offsets
+        let greek = Alphabet::new("αβγ");
+        let bm = AlphabetBoyerMoore::new("βγ", &greek);
+        assert_eq!(bm.search("ααβγ"), Some(2));
+        assert_eq!(AlphabetBoyerMoore::new("", &greek).search("αβ"), Some(0));
+    }
+}
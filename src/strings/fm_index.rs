@@ -0,0 +1,210 @@
+//! # FM-index
+//!
+//! A compressed full-text index: counts and locates the occurrences of a pattern
+//! without scanning the text.
+//!
+//! The text, ended by a sentinel `'\0'` smaller than every other byte, is sorted into its suffix array,
+//! and the Burrows-Wheeler transform (BWT) is the byte before each sorted suffix.
+//! The BWT is kept in a wavelet tree, so rank queries on it take O(log σ).
+//!
+//! Backward search matches the pattern from its last byte: the suffixes starting with a pattern suffix
+//! form a range of rows, and the LF mapping `c_count[c] + rank(c, i)` narrows the range one byte at a time.
+//! `count` takes O(m log σ) for a pattern of length m.
+//!
+//! Only every `sample_rate`-th suffix array entry (by text position) is kept. `locate` walks the LF
+//! mapping from each row to a sampled row, so it takes O(sample_rate * log σ) per occurrence.
+
+use crate::fundamentals::bitvector::RankSelectBitVec;
+
+use super::wavelet::WaveletTree;
+
+const R: usize = 256; // radix
+const SAMPLE_RATE: usize = 32;
+
+pub struct FMIndex {
+    bwt: WaveletTree,          // bwt[i] = byte before the i-th smallest suffix
+    c_count: [usize; R],       // c_count[c] = number of bytes less than c
+    sampled: RankSelectBitVec, // sampled[i] = is the i-th suffix's position kept?
+    samples: Vec<usize>,       // positions of the sampled suffixes, in row order
+}
+
+impl FMIndex {
+    /// # Panics
+    /// if `text` contains `'\0'`, which is reserved for the sentinel.
+    pub fn new(text: &str) -> Self {
+        Self::with_sample_rate(text, SAMPLE_RATE)
+    }
+
+    /// Keeps the suffix array entry of every `sample_rate`-th text position;
+    /// a smaller rate makes `locate` faster and the index larger.
+    pub fn with_sample_rate(text: &str, sample_rate: usize) -> Self {
+        if sample_rate == 0 {
+            panic!("sample rate must be positive");
+        }
+        if text.contains('\0') {
+            panic!("text cannot contain the sentinel '\\0'");
+        }
+        let mut s = text.as_bytes().to_vec();
+        s.push(0);
+        let n = s.len();
+
+        // sorting the suffixes directly takes O(n^2 log n) in the worst case
+        let mut sa = (0..n).collect::<Vec<usize>>();
+        sa.sort_unstable_by_key(|&i| &s[i..]);
+
+        let bwt = sa.iter().map(|&i| s[(i + n - 1) % n]).collect::<Vec<u8>>();
+
+        let mut c_count = [0; R];
+        for &c in &s {
+            c_count[c as usize] += 1;
+        }
+        let mut total = 0;
+        for count in c_count.iter_mut() {
+            (*count, total) = (total, total + *count);
+        }
+
+        let sampled = sa
+            .iter()
+            .map(|&i| i.is_multiple_of(sample_rate))
+            .collect::<RankSelectBitVec>();
+        let samples = sa
+            .into_iter()
+            .filter(|&i| i.is_multiple_of(sample_rate))
+            .collect();
+
+        FMIndex {
+            bwt: WaveletTree::new(&bwt),
+            c_count,
+            sampled,
+            samples,
+        }
+    }
+
+    // the range of rows whose suffixes start with pat
+    fn backward_search(&self, pat: &str) -> (usize, usize) {
+        let (mut lo, mut hi) = (0, self.bwt.len());
+        for &c in pat.as_bytes().iter().rev() {
+            if c == 0 {
+                return (0, 0);
+            }
+            lo = self.lf(c, lo);
+            hi = self.lf(c, hi);
+            if lo >= hi {
+                return (0, 0);
+            }
+        }
+        (lo, hi)
+    }
+
+    // the row of the suffix that is c followed by the suffix of row i
+    fn lf(&self, c: u8, i: usize) -> usize {
+        self.c_count[c as usize] + self.bwt.rank(c, i)
+    }
+
+    /// Returns the number of occurrences of `pat` in the text.
+    pub fn count(&self, pat: &str) -> usize {
+        let (lo, hi) = self.backward_search(pat);
+        hi - lo
+    }
+
+    /// Returns the index of every occurrence of `pat` in the text, in increasing order.
+    pub fn locate(&self, pat: &str) -> Vec<usize> {
+        let (lo, hi) = self.backward_search(pat);
+        let mut found = (lo..hi)
+            .map(|mut row| {
+                // each step moves one position left in the text
+                let mut steps = 0;
+                while !self.sampled.get(row) {
+                    row = self.lf(self.bwt.access(row), row);
+                    steps += 1;
+                }
+                self.samples[self.sampled.rank1(row)] + steps
+            })
+            .collect::<Vec<usize>>();
+        found.sort_unstable();
+        found
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    use super::*;
+    use crate::strings::kmp::KMP;
+
+    fn check(fm: &FMIndex, text: &str, pat: &str) {
+        let expected = KMP::new(pat).search_all(text);
+        assert_eq!(fm.count(pat), expected.len());
+        assert_eq!(fm.locate(pat), expected);
+    }
+
+    #[test]
+    fn abracadabra() {
+        let text = "ABRACADABRA";
+        let fm = FMIndex::new(text);
+        assert_eq!(fm.count("ABRA"), 2);
+        assert_eq!(fm.locate("ABRA"), vec![0, 7]);
+        assert_eq!(fm.locate("A"), vec![0, 3, 5, 7, 10]);
+        assert_eq!(fm.locate("CAD"), vec![4]);
+        assert_eq!(fm.count("ABRACADABRA"), 1);
+        assert_eq!(fm.count("ABRACADABRAA"), 0);
+        assert_eq!(fm.count("X"), 0);
+        assert_eq!(fm.count("\0"), 0);
+        for pat in ["", "A", "BRA", "RAC", "DABRA", "AA", "ABRACADABRA"] {
+            check(&fm, text, pat);
+        }
+    }
+
+    #[test]
+    fn random() {
+        let mut rng = StdRng::seed_from_u64(35);
+        for sigma in [2, 4, 26] {
+            let text = (0..500)
+                .map(|_| (b'a' + rng.gen_range(0..sigma)) as char)
+                .collect::<String>();
+            let fm = FMIndex::new(&text);
+            for _ in 0..50 {
+                let m = rng.gen_range(1..8);
+                // half the patterns are taken from the text, so they occur at least once
+                let pat = if rng.gen_bool(0.5) {
+                    let i = rng.gen_range(0..text.len() - m);
+                    text[i..i + m].to_string()
+                } else {
+                    (0..m)
+                        .map(|_| (b'a' + rng.gen_range(0..sigma)) as char)
+                        .collect()
+                };
+                check(&fm, &text, &pat);
+            }
+            check(&fm, &text, &text);
+        }
+    }
+
+    #[test]
+    fn sample_rates() {
+        let text = "she sells sea shells by the sea shore";
+        let expected = FMIndex::with_sample_rate(text, 1);
+        for rate in [2, 3, 7, 64] {
+            let fm = FMIndex::with_sample_rate(text, rate);
+            for pat in ["s", "se", "sea", "shells", "e", " ", "xyz"] {
+                assert_eq!(fm.locate(pat), expected.locate(pat));
+                check(&fm, text, pat);
+            }
+        }
+    }
+
+    #[test]
+    fn empty_text() {
+        let fm = FMIndex::new("");
+        assert_eq!(fm.count("a"), 0);
+        assert_eq!(fm.locate(""), vec![0]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn sentinel_in_text() {
+        FMIndex::new("a\0b");
+    }
+}
@@ -0,0 +1,220 @@
+//! # Knuth-Morris-Pratt substring search
+//!
+//! Builds a DFA from the pattern, then scans the text without ever backing up.
+//! The pattern and text are matched byte by byte, so the returned index is a byte offset,
+//! and either may be a `&str` or binary data (`&[u8]`).
+//!
+//! Preprocessing takes O(R * m) time and space for radix R = 256; search takes O(n).
+//!
+//! `AlphabetKMP` matches characters of an `Alphabet` instead, for non-ASCII text
+//! with a DFA of `radix` rows; its indices are character offsets.
+
+use super::alphabet::Alphabet;
+
+const R: usize = 256; // radix
+
+pub struct KMP {
+    dfa: Vec<Vec<usize>>, // dfa[c][j] = next state after reading byte c in state j
+    pat: Vec<u8>,
+    restart: usize, // state after reading pat[1..m], where a search resumes after a match
+}
+
+impl KMP {
+    pub fn new(pat: impl AsRef<[u8]>) -> Self {
+        let p = pat.as_ref();
+        let symbols: Vec<usize> = p.iter().map(|&c| c as usize).collect();
+        let (dfa, x) = build_dfa(&symbols, R);
+        KMP {
+            dfa,
+            pat: p.to_vec(),
+            restart: x,
+        }
+    }
+
+    /// Returns the index of the first occurrence of the pattern in `txt`.
+    pub fn search(&self, txt: impl AsRef<[u8]>) -> Option<usize> {
+        let txt = txt.as_ref();
+        let m = self.pat.len();
+        let mut j = 0;
+        for (i, &c) in txt.iter().enumerate() {
+            if j == m {
+                return Some(i - m);
+            }
+            j = self.dfa[c as usize][j];
+        }
+        if j == m {
+            Some(txt.len() - m)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the index of every occurrence of the pattern in `txt`, overlapping ones included.
+    pub fn search_all(&self, txt: impl AsRef<[u8]>) -> Vec<usize> {
+        let txt = txt.as_ref();
+        let m = self.pat.len();
+        if m == 0 {
+            return (0..=txt.len()).collect();
+        }
+        let mut found = Vec::new();
+        let mut j = 0;
+        for (i, &c) in txt.iter().enumerate() {
+            j = self.dfa[c as usize][j];
+            if j == m {
+                found.push(i + 1 - m);
+                j = self.restart;
+            }
+        }
+        found
+    }
+}
+
+/// KMP over the characters of an `Alphabet`, returning character (not byte) offsets.
+pub struct AlphabetKMP<'a> {
+    alphabet: &'a Alphabet,
+    dfa: Vec<Vec<usize>>, // dfa[c][j] for the alphabet index c
+    m: usize,
+    restart: usize,
+}
+
+impl<'a> AlphabetKMP<'a> {
+    /// Panics if the pattern has a character outside `alphabet`.
+    pub fn new(pat: &str, alphabet: &'a Alphabet) -> Self {
+        let p: Vec<usize> = alphabet
+            .to_indices(pat)
+            .into_iter()
+            .map(usize::from)
+            .collect();
+        let (dfa, restart) = build_dfa(&p, alphabet.radix() as usize);
+        AlphabetKMP {
+            alphabet,
+            dfa,
+            m: p.len(),
+            restart,
+        }
+    }
+
+    /// Returns the character index of the first occurrence of the pattern in `txt`.
+    pub fn search(&self, txt: &str) -> Option<usize> {
+        self.search_all(txt).into_iter().next()
+    }
+
+    /// Returns the character index of every occurrence of the pattern in `txt`, overlapping ones included.
+    /// A character outside the alphabet matches nothing, so the search restarts after it.
+    pub fn search_all(&self, txt: &str) -> Vec<usize> {
+        if self.m == 0 {
+            return (0..=txt.chars().count()).collect();
+        }
+        let mut found = Vec::new();
+        let mut j = 0;
+        for (i, c) in txt.chars().enumerate() {
+            j = if self.alphabet.contains(c) {
+                self.dfa[self.alphabet.to_index(c) as usize][j]
+            } else {
+                0
+            };
+            if j == self.m {
+                found.push(i + 1 - self.m);
+                j = self.restart;
+            }
+        }
+        found
+    }
+}
+
+// the DFA of a pattern of symbols below r, and its restart state (after reading p[1..m])
+fn build_dfa(p: &[usize], r: usize) -> (Vec<Vec<usize>>, usize) {
+    let m = p.len();
+    let mut dfa = vec![vec![0; m]; r];
+    // x is the state after reading pat[1..j]
+    let mut x = 0;
+    if m > 0 {
+        dfa[p[0]][0] = 1;
+        for j in 1..m {
+            for row in dfa.iter_mut() {
+                row[j] = row[x]; // copy mismatch cases
+            }
+            dfa[p[j]][j] = j + 1; // set match case
+            x = dfa[p[j]][x]; // update restart state
+        }
+    }
+    (dfa, x)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn search() {
+        let kmp = KMP::new("abracadabra");
+        assert_eq!(kmp.search("abracadabra is a magic word"), Some(0));
+        assert_eq!(kmp.search("abacadabrabracabracadabrabrabracad"), Some(14));
+        assert_eq!(kmp.search("say abracadabra"), Some(4));
+        assert_eq!(kmp.search("abracadabr"), None);
+        assert_eq!(kmp.search("abacadabrabracabracadabr"), None);
+    }
+
+    #[test]
+    fn overlapping() {
+        let kmp = KMP::new("AACAA");
+        assert_eq!(kmp.search("AABRAACADABRAACAADABRA"), Some(12));
+        let kmp = KMP::new("aab");
+        assert_eq!(kmp.search("aaab"), Some(1));
+    }
+
+    #[test]
+    fn search_all() {
+        let kmp = KMP::new("AACAA");
+        assert_eq!(kmp.search_all("AACAACAAACAA"), vec![0, 3, 7]);
+        assert_eq!(KMP::new("aa").search_all("aaaa"), vec![0, 1, 2]);
+        assert_eq!(KMP::new("ab").search_all("ba"), vec![]);
+        assert_eq!(KMP::new("").search_all("ab"), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn edge_cases() {
+        assert_eq!(KMP::new("long pattern").search("short"), None);
+        assert_eq!(KMP::new("x").search(""), None);
+        assert_eq!(KMP::new("").search("abc"), Some(0));
+        assert_eq!(KMP::new("").search(""), Some(0));
+        // byte offsets
+        assert_eq!(KMP::new("é").search("café"), Some(3));
+    }
+
+    #[test]
+    fn binary() {
+        let txt = [0u8, 255, 0, 0, 255, 0, 7];
+        let kmp = KMP::new([0u8, 255, 0]);
+        assert_eq!(kmp.search(txt), Some(0));
+        assert_eq!(kmp.search_all(&txt[..]), vec![0, 3]);
+        assert_eq!(kmp.search(&txt[1..]), Some(2));
+    }
+
+    #[test]
+    fn alphabet() {
+        let greek = Alphabet::new("αβγδ");
+        let kmp = AlphabetKMP::new("αβα", &greek);
+        // character offsets, not byte offsets
+        assert_eq!(kmp.search("γαβαβαδ"), Some(1));
+        assert_eq!(kmp.search_all("γαβαβαδ"), vec![1, 3]);
+        assert_eq!(kmp.search("αβγαβδ"), None);
+        // a character outside the alphabet breaks a match
+        assert_eq!(kmp.search_all("αβxα αβα"), vec![5]);
+        assert_eq!(AlphabetKMP::new("", &greek).search_all("αβ"), vec![0, 1, 2]);
+
+        let dna = Alphabet::new("ACGT");
+        let kmp = AlphabetKMP::new("AACAA", &dna);
+        assert_eq!(
+            kmp.search_all("AACAACAAACAA"),
+            KMP::new("AACAA").search_all("AACAACAAACAA")
+        );
+        assert_eq!(kmp.search("GGGGTTTT"), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn pattern_outside_alphabet() {
+        AlphabetKMP::new("ACGU", &Alphabet::new("ACGT"));
+    }
+}
@@ -35,6 +35,43 @@ impl LSD {
             a[..].clone_from_slice(&aux[..]);
         }
     }
+
+    /// Sorts 32-bit integers with 4 passes of key-indexed counting, one per byte.
+    /// The sign bit is flipped in the last pass, so that negative numbers come first.
+    pub fn sort_ints(a: &mut [i32]) {
+        const BITS_PER_BYTE: usize = 8;
+        const W: usize = 32 / BITS_PER_BYTE; // each int is 4 bytes
+        const R: usize = 1 << BITS_PER_BYTE; // each byte is between 0 and 255
+        const MASK: u32 = R as u32 - 1;
+
+        let n = a.len();
+        let mut aux = vec![0; n];
+        for d in 0..W {
+            let shift = BITS_PER_BYTE * d;
+            let key = |x: i32| {
+                let c = ((x as u32 >> shift) & MASK) as usize;
+                // for the most significant byte, 0x80..0xFF are the negatives
+                if d == W - 1 {
+                    c ^ (R >> 1)
+                } else {
+                    c
+                }
+            };
+
+            let mut count = vec![0; R + 1];
+            for &x in a.iter() {
+                count[key(x) + 1] += 1;
+            }
+            for r in 0..R {
+                count[r + 1] += count[r];
+            }
+            for &x in a.iter() {
+                aux[count[key(x)]] = x;
+                count[key(x)] += 1;
+            }
+            a.copy_from_slice(&aux);
+        }
+    }
 }
 
 #[cfg(test)]
@@ -58,4 +95,46 @@ mod test {
             ]
         );
     }
+
+    #[test]
+    fn same_as_msd() {
+        use crate::strings::msd::MSD;
+        use rand::rngs::StdRng;
+        use rand::{Rng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(92);
+        let owned: Vec<String> = (0..1000)
+            .map(|_| (0..5).map(|_| rng.gen_range(b'0'..=b'z') as char).collect())
+            .collect();
+        let mut data: Vec<&str> = owned.iter().map(|s| s.as_str()).collect();
+        let mut expected = data.clone();
+
+        LSD::sort(&mut data, 5);
+        MSD::sort(&mut expected);
+        assert_eq!(data, expected);
+    }
+
+    #[test]
+    fn sort_ints() {
+        let mut data = vec![5, -1, 0, i32::MAX, i32::MIN, 256, -256, 65536, -65537, 1, 5];
+        LSD::sort_ints(&mut data);
+        assert_eq!(
+            data,
+            vec![i32::MIN, -65537, -256, -1, 0, 1, 5, 5, 256, 65536, i32::MAX]
+        );
+
+        use rand::rngs::StdRng;
+        use rand::{Rng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(92);
+        let mut data: Vec<i32> = (0..10_000).map(|_| rng.gen()).collect();
+        let mut expected = data.clone();
+        expected.sort_unstable();
+        LSD::sort_ints(&mut data);
+        assert_eq!(data, expected);
+
+        let mut empty: Vec<i32> = vec![];
+        LSD::sort_ints(&mut empty);
+        assert!(empty.is_empty());
+    }
 }
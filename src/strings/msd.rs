@@ -1,6 +1,9 @@
 //!# MSD String sort
 //!
 //! Most-significant digit first string sort.
+//!
+//! `sort` works on bytes with radix 256, so strings come out in byte-wise
+//! (UTF-8) order; `sort_chars` works on Unicode scalar values.
 const R: usize = 256; // radix
 const M: usize = 3; // cutoff for small sub-arrays
 pub struct MSD;
@@ -14,7 +17,13 @@ impl MSD {
         }
     }
 
+    /// Sorts in byte-wise order. Non-ASCII strings are fine, and for UTF-8
+    /// this agrees with `sort_chars`, but it is not a locale-aware collation.
     pub fn sort(a: &mut Vec<&str>) {
+        // nothing to sort, and `a.len() - 1` would underflow
+        if a.len() <= 1 {
+            return;
+        }
         let mut aux = vec![""; a.len()];
         MSD::_sort(a, &mut aux, 0, a.len() - 1, 0);
     }
@@ -51,10 +60,92 @@ impl MSD {
     }
 }
 
-// sort from a[lo] to a[hi], starting at the dth character
+impl MSD {
+    fn char_at_unicode(s: &str, d: usize) -> Option<char> {
+        s.chars().nth(d)
+    }
+
+    /// Sorts by Unicode scalar values (`char`) rather than by raw bytes.
+    ///
+    /// A radix of 0x110000 is far too large for a count array, so every
+    /// sub-array is counted over the distinct characters that actually appear
+    /// at position `d`. Together with the O(d) cost of `chars().nth(d)`, this
+    /// makes it noticeably slower than `sort`; prefer `sort` for ASCII input.
+    pub fn sort_chars(a: &mut Vec<&str>) {
+        if a.len() <= 1 {
+            return;
+        }
+        let mut aux = vec![""; a.len()];
+        MSD::_sort_chars(a, &mut aux, 0, a.len() - 1, 0);
+    }
+
+    fn _sort_chars<'a>(
+        a: &mut Vec<&'a str>,
+        aux: &mut Vec<&'a str>,
+        lo: usize,
+        hi: usize,
+        d: usize,
+    ) {
+        if hi <= lo + M {
+            insert_sort_chars(a, lo, hi, d);
+            return;
+        }
+        // the alphabet of this sub-array, in scalar order
+        let mut alphabet: Vec<char> = a[lo..=hi]
+            .iter()
+            .filter_map(|s| MSD::char_at_unicode(s, d))
+            .collect();
+        alphabet.sort_unstable();
+        alphabet.dedup();
+        let r = alphabet.len();
+        // `None` (end of string) maps to 0 so that shorter strings come first
+        let index = |s: &str| match MSD::char_at_unicode(s, d) {
+            Some(c) => alphabet.binary_search(&c).unwrap() + 1,
+            None => 0,
+        };
+
+        let mut count = vec![0; r + 2];
+        for s in &a[lo..=hi] {
+            count[index(s) + 1] += 1;
+        }
+        for c in 0..r + 1 {
+            count[c + 1] += count[c];
+        }
+        for &s in &a[lo..=hi] {
+            let c = index(s);
+            aux[count[c]] = s;
+            count[c] += 1;
+        }
+        a[lo..=hi].copy_from_slice(&aux[0..=hi - lo]);
+        // count[c] is now the end of bucket c; recursively sort for each character,
+        // skipping the end-of-string bucket 0
+        for c in 1..=r {
+            if count[c] > count[c - 1] + 1 {
+                MSD::_sort_chars(a, aux, lo + count[c - 1], lo + count[c] - 1, d + 1);
+            }
+        }
+    }
+}
+
+// sort from a[lo] to a[hi], starting at the dth byte
 fn insert_sort(a: &mut [&str], lo: usize, hi: usize, d: usize) {
+    // compare bytes, since `d` may fall inside a multibyte character
     fn less(v: &str, w: &str, d: usize) -> bool {
-        v[d..].cmp(&w[d..]).is_le()
+        v.as_bytes()[d..].cmp(&w.as_bytes()[d..]).is_le()
+    }
+
+    for i in lo..=hi {
+        let mut j = i;
+        while j > lo && less(a[j], a[j - 1], d) {
+            a.swap(j, j - 1);
+            j -= 1;
+        }
+    }
+}
+
+fn insert_sort_chars(a: &mut [&str], lo: usize, hi: usize, d: usize) {
+    fn less(v: &str, w: &str, d: usize) -> bool {
+        v.chars().skip(d).le(w.chars().skip(d))
     }
 
     for i in lo..=hi {
@@ -110,4 +201,92 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn empty() {
+        let mut data: Vec<&str> = Vec::new();
+        MSD::sort(&mut data);
+        assert!(data.is_empty());
+    }
+
+    #[test]
+    fn single() {
+        let mut data = vec!["she"];
+        MSD::sort(&mut data);
+        assert_eq!(data, vec!["she"]);
+    }
+
+    #[test]
+    fn sort_chars() {
+        let mut data = vec![
+            "été", "eta", "éa", "zoo", "e", "ébène", "apple", "étage", "école", "ça", "car", "été",
+            "", "über", "uber", "Zürich",
+        ];
+        let mut expected = data.clone();
+        expected.sort_by(|v, w| v.chars().cmp(w.chars()));
+
+        MSD::sort_chars(&mut data);
+        assert_eq!(data, expected);
+        assert_eq!(&data[..6], &["", "Zürich", "apple", "car", "e", "eta"]);
+    }
+
+    #[test]
+    fn multibyte() {
+        // "é" is two bytes and CJK characters are three, so small
+        // sub-arrays start comparing in the middle of a character
+        let mut data = vec![
+            "été", "étage", "étoile", "école", "eta", "東京", "東北", "東", "北京", "日本", "é",
+            "ébène", "zoo", "",
+        ];
+        let mut expected = data.clone();
+        expected.sort_unstable_by(|v, w| v.as_bytes().cmp(w.as_bytes()));
+
+        MSD::sort(&mut data);
+        assert_eq!(data, expected);
+        assert_eq!(&data[..4], &["", "eta", "zoo", "é"]);
+    }
+
+    #[test]
+    fn long_common_prefix() {
+        let prefix = "ab".repeat(100);
+        let owned: Vec<String> = ["c", "", "b", "ba", "a", "aa", "bb", "a"]
+            .iter()
+            .map(|s| format!("{}{}", prefix, s))
+            .collect();
+        let mut data: Vec<&str> = owned.iter().map(|s| s.as_str()).collect();
+        let mut expected = data.clone();
+        expected.sort_unstable();
+
+        MSD::sort(&mut data);
+        assert_eq!(data, expected);
+    }
+
+    #[test]
+    fn sort_chars_random() {
+        use rand::rngs::StdRng;
+        use rand::{Rng, SeedableRng};
+
+        let mut data = vec!["b", "ab", "a", "b", "aa"];
+        MSD::sort_chars(&mut data);
+        assert_eq!(data, vec!["a", "aa", "ab", "b", "b"]);
+
+        let mut rng = StdRng::seed_from_u64(71);
+        for _ in 0..50 {
+            let owned: Vec<String> = (0..rng.gen_range(0..200))
+                .map(|_| {
+                    let len = rng.gen_range(0..6);
+                    (0..len)
+                        .map(|_| ['a', 'b', 'c', 'é', '東'][rng.gen_range(0..5)])
+                        .collect()
+                })
+                .collect();
+            let mut data: Vec<&str> = owned.iter().map(|s| s.as_str()).collect();
+            // for UTF-8, scalar order is byte order
+            let mut expected = data.clone();
+            expected.sort_unstable();
+
+            MSD::sort_chars(&mut data);
+            assert_eq!(data, expected);
+        }
+    }
 }
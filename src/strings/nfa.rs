@@ -0,0 +1,257 @@
+//! # Regular expression pattern matching with an NFA
+//!
+//! The regular expression is compiled into a nondeterministic finite automaton:
+//! state `i` is the i-th character of the regular expression, plus an accept state `m`.
+//! Matching a character follows the implicit edge `i -> i + 1`, while the
+//! epsilon-transitions (for parentheses, `|`, `*` and `+`) are the edges of a `Digraph`,
+//! so the states reachable without reading input are found with `DirectedDFS`.
+//!
+//! Supported are concatenation, `|` (any number of alternatives within parentheses or
+//! at the top level), `*`, `+`, parentheses and the wildcard `.`.
+//! There are no escapes, so the metacharacters `(`, `)`, `|`, `*` and `+` cannot be matched.
+//! `recognizes` takes O(m n) time for a text of length n, however the regexp is nested.
+
+use std::fmt;
+
+use crate::graphs::digraph::Digraph;
+use crate::graphs::directed_dfs::DirectedDFS;
+
+/// Why a regular expression cannot be compiled; positions are 0-based character offsets.
+#[derive(Debug, PartialEq, Eq)]
+pub enum RegexError {
+    /// A `)` with no `(` before it.
+    UnmatchedClose(usize),
+    /// A `(` that is never closed.
+    UnclosedOpen(usize),
+    /// A `*` or `+` with nothing to repeat.
+    DanglingOperator { op: char, pos: usize },
+}
+
+impl fmt::Display for RegexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RegexError::UnmatchedClose(pos) => write!(f, "unmatched ')' at {}", pos),
+            RegexError::UnclosedOpen(pos) => write!(f, "unclosed '(' at {}", pos),
+            RegexError::DanglingOperator { op, pos } => {
+                write!(f, "'{}' at {} has nothing to repeat", op, pos)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RegexError {}
+
+pub struct NFA {
+    re: Vec<char>, // match transitions
+    g: Digraph,    // epsilon transitions
+    m: usize,      // number of characters in regular expression
+}
+
+impl NFA {
+    /// Compiles the regular expression, panicking if it is malformed.
+    pub fn new(regexp: &str) -> NFA {
+        match NFA::try_new(regexp) {
+            Ok(nfa) => nfa,
+            Err(e) => panic!("invalid regular expression {:?}: {}", regexp, e),
+        }
+    }
+
+    /// Compiles the regular expression, or returns why it is malformed.
+    pub fn try_new(regexp: &str) -> Result<NFA, RegexError> {
+        validate(regexp)?;
+        // wrapped in parentheses, so that `|` also works at the top level
+        let re: Vec<char> = format!("({})", regexp).chars().collect();
+        let m = re.len();
+
+        let mut g = Digraph::new(m + 1);
+        let mut ops = Vec::new();
+        for i in 0..m {
+            let mut lp = i;
+            if re[i] == '(' || re[i] == '|' {
+                ops.push(i);
+            } else if re[i] == ')' {
+                // the parentheses are balanced, so there is a '(' below any '|'
+                let mut ors = Vec::new();
+                lp = ops.pop().unwrap();
+                while re[lp] == '|' {
+                    ors.push(lp);
+                    lp = ops.pop().unwrap();
+                }
+                for or in ors {
+                    g.add_edge(lp, or + 1);
+                    g.add_edge(or, i);
+                }
+            }
+
+            // closure operator (uses 1-character lookahead)
+            if i + 1 < m && re[i + 1] == '*' {
+                g.add_edge(lp, i + 1);
+                g.add_edge(i + 1, lp);
+            }
+            // one or more: only the way back
+            if i + 1 < m && re[i + 1] == '+' {
+                g.add_edge(i + 1, lp);
+            }
+            if matches!(re[i], '(' | '*' | '+' | ')') {
+                g.add_edge(i, i + 1);
+            }
+        }
+        Ok(NFA { re, g, m })
+    }
+
+    /// Does the regular expression match the whole text?
+    pub fn recognizes(&self, txt: &str) -> bool {
+        let mut dfs = DirectedDFS::new(&self.g, 0);
+        for c in txt.chars() {
+            let matched: Vec<usize> = (0..self.m)
+                .filter(|&v| dfs.marked(v) && self.matches(v, c))
+                .map(|v| v + 1)
+                .collect();
+            if matched.is_empty() {
+                return false;
+            }
+            dfs = DirectedDFS::from_sources(&self.g, matched);
+        }
+        dfs.marked(self.m)
+    }
+
+    // does state v read the character c?
+    fn matches(&self, v: usize, c: char) -> bool {
+        match self.re[v] {
+            '(' | ')' | '|' | '*' | '+' => false,
+            '.' => true,
+            r => r == c,
+        }
+    }
+}
+
+// checks the parentheses and that every `*` and `+` follows something to repeat
+fn validate(regexp: &str) -> Result<(), RegexError> {
+    let mut open = Vec::new();
+    let mut prev = None;
+    for (i, c) in regexp.chars().enumerate() {
+        match c {
+            '(' => open.push(i),
+            ')' => {
+                open.pop().ok_or(RegexError::UnmatchedClose(i))?;
+            }
+            '*' | '+' if matches!(prev, None | Some('(') | Some('|')) => {
+                return Err(RegexError::DanglingOperator { op: c, pos: i });
+            }
+            _ => {}
+        }
+        prev = Some(c);
+    }
+    match open.pop() {
+        Some(i) => Err(RegexError::UnclosedOpen(i)),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn textbook() {
+        let nfa = NFA::new("((A*B|AC)D)");
+        assert!(nfa.recognizes("AABD"));
+        assert!(nfa.recognizes("BD"));
+        assert!(nfa.recognizes("ACD"));
+        assert!(nfa.recognizes("AAAAAAAABD"));
+        assert!(!nfa.recognizes("AABBD"));
+        assert!(!nfa.recognizes("ACBD"));
+        assert!(!nfa.recognizes("AAD"));
+        assert!(!nfa.recognizes("AABDD"));
+        assert!(!nfa.recognizes(""));
+
+        let nfa = NFA::new("(A|B)(C|D)");
+        assert!(nfa.recognizes("AC"));
+        assert!(nfa.recognizes("BD"));
+        assert!(!nfa.recognizes("AB"));
+    }
+
+    #[test]
+    fn alternation() {
+        let nfa = NFA::new("(a|(b|c)d|e)*f");
+        assert!(nfa.recognizes("f"));
+        assert!(nfa.recognizes("abdcdef"));
+        assert!(nfa.recognizes("eeaf"));
+        assert!(!nfa.recognizes("bf"));
+        assert!(!nfa.recognizes("abdcd"));
+
+        // at the top level, and with more than two alternatives
+        let nfa = NFA::new("cat|dog|bird");
+        assert!(nfa.recognizes("cat"));
+        assert!(nfa.recognizes("dog"));
+        assert!(nfa.recognizes("bird"));
+        assert!(!nfa.recognizes("catdog"));
+
+        // an empty alternative
+        let nfa = NFA::new("a(|b)c");
+        assert!(nfa.recognizes("ac"));
+        assert!(nfa.recognizes("abc"));
+        assert!(!nfa.recognizes("abbc"));
+    }
+
+    #[test]
+    fn plus_and_wildcard() {
+        let nfa = NFA::new("(ab)+c");
+        assert!(nfa.recognizes("abc"));
+        assert!(nfa.recognizes("ababc"));
+        assert!(!nfa.recognizes("c"));
+        assert!(!nfa.recognizes("abac"));
+
+        let nfa = NFA::new("a+b*");
+        assert!(nfa.recognizes("a"));
+        assert!(nfa.recognizes("aaabb"));
+        assert!(!nfa.recognizes("b"));
+
+        let nfa = NFA::new(".*é.");
+        assert!(nfa.recognizes("caféx"));
+        assert!(nfa.recognizes("éé"));
+        assert!(!nfa.recognizes("café"));
+
+        assert!(NFA::new("").recognizes(""));
+        assert!(!NFA::new("").recognizes("a"));
+        // metacharacters in the text are not matched by metacharacters in the regexp
+        assert!(!NFA::new("(a)").recognizes("(a)"));
+    }
+
+    #[test]
+    fn pathological() {
+        // a backtracking matcher takes exponential time on this
+        let nfa = NFA::new("(a|aa)*b");
+        let txt = "a".repeat(10_000);
+        assert!(!nfa.recognizes(&txt));
+        assert!(nfa.recognizes(&(txt + "b")));
+
+        let nfa = NFA::new("(a*)*(a*)*c");
+        assert!(!nfa.recognizes(&"a".repeat(5_000)));
+    }
+
+    #[test]
+    fn malformed() {
+        let err = |s: &str| NFA::try_new(s).err().unwrap();
+        assert_eq!(err("(ab"), RegexError::UnclosedOpen(0));
+        assert_eq!(err("a(b|c"), RegexError::UnclosedOpen(1));
+        assert_eq!(err("ab)"), RegexError::UnmatchedClose(2));
+        assert_eq!(err("a)(b"), RegexError::UnmatchedClose(1));
+        assert_eq!(err("*a"), RegexError::DanglingOperator { op: '*', pos: 0 });
+        assert_eq!(
+            err("(+a)"),
+            RegexError::DanglingOperator { op: '+', pos: 1 }
+        );
+        assert_eq!(
+            err("a|*b"),
+            RegexError::DanglingOperator { op: '*', pos: 2 }
+        );
+        assert_eq!(err("ab)").to_string(), "unmatched ')' at 2");
+    }
+
+    #[test]
+    #[should_panic(expected = "unclosed '(' at 0")]
+    fn new_panics() {
+        NFA::new("(a");
+    }
+}
@@ -0,0 +1,219 @@
+//! # 3-way string quicksort
+//!
+//! Partitions on the d-th byte into less, equal and greater sub-arrays, and
+//! moves on to byte d + 1 only within the equal one. It sorts in place,
+//! without the aux array and count arrays that `MSD` needs, and like `sort`
+//! in `MSD` it orders strings byte-wise.
+const M: usize = 3; // cutoff for small sub-arrays, as in `MSD`
+pub struct Quick3String;
+
+impl Quick3String {
+    // the d-th byte of s, or -1 past its end
+    fn char_at(s: &str, d: usize) -> i32 {
+        match s.as_bytes().get(d) {
+            Some(&b) => b as i32,
+            None => -1,
+        }
+    }
+
+    /// Sorts `a` in byte-wise order; works for `&str`, `String` or anything else that is `AsRef<str>`.
+    pub fn sort<S: AsRef<str>>(a: &mut [S]) {
+        let n = a.len();
+        Quick3String::_sort(a, 0, n, 0);
+    }
+
+    // sort a[lo..hi], whose strings all share their first d bytes
+    fn _sort<S: AsRef<str>>(a: &mut [S], mut lo: usize, mut hi: usize, mut d: usize) {
+        loop {
+            if hi - lo <= M {
+                insert_sort(a, lo, hi, d);
+                return;
+            }
+            let v = Quick3String::char_at(a[lo].as_ref(), d);
+            let (mut lt, mut gt, mut i) = (lo, hi, lo + 1);
+            while i < gt {
+                let t = Quick3String::char_at(a[i].as_ref(), d);
+                if t < v {
+                    a.swap(lt, i);
+                    lt += 1;
+                    i += 1;
+                } else if t > v {
+                    gt -= 1;
+                    a.swap(i, gt);
+                } else {
+                    i += 1;
+                }
+            }
+
+            // a[lo..lt] < v = a[lt..gt] < a[gt..hi]; the equal strings are
+            // done if they all ended at d, so their part is then left empty
+            let eq = if v >= 0 {
+                (lt, gt, d + 1)
+            } else {
+                (lt, lt, d + 1)
+            };
+            let mut parts = [(lo, lt, d), (gt, hi, d), eq];
+            // recurse on the smaller parts and loop on the largest,
+            // so that the depth stays logarithmic
+            let largest = (0..3).max_by_key(|&k| parts[k].1 - parts[k].0).unwrap();
+            parts.swap(largest, 2);
+            let (l, h, e) = parts[2];
+            for (pl, ph, pd) in &parts[..2] {
+                if ph - pl > 1 {
+                    Quick3String::_sort(a, *pl, *ph, *pd);
+                }
+            }
+            lo = l;
+            hi = h;
+            d = e;
+        }
+    }
+}
+
+// sort a[lo..hi], starting at the dth byte
+fn insert_sort<S: AsRef<str>>(a: &mut [S], lo: usize, hi: usize, d: usize) {
+    fn less(v: &str, w: &str, d: usize) -> bool {
+        v.as_bytes()[d..] < w.as_bytes()[d..]
+    }
+
+    for i in lo..hi {
+        let mut j = i;
+        while j > lo && less(a[j].as_ref(), a[j - 1].as_ref(), d) {
+            a.swap(j, j - 1);
+            j -= 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seashells() {
+        let mut data = vec![
+            "she",
+            "sells",
+            "seashells",
+            "by",
+            "the",
+            "sea",
+            "shore",
+            "the",
+            "shells",
+            "she",
+            "sells",
+            "are",
+            "surely",
+            "seashells",
+        ];
+        Quick3String::sort(&mut data);
+
+        assert_eq!(
+            data,
+            vec![
+                "are",
+                "by",
+                "sea",
+                "seashells",
+                "seashells",
+                "sells",
+                "sells",
+                "she",
+                "she",
+                "shells",
+                "shore",
+                "surely",
+                "the",
+                "the"
+            ]
+        );
+    }
+
+    #[test]
+    fn same_as_msd() {
+        use crate::strings::msd::MSD;
+
+        let mut data = vec![
+            "she",
+            "sells",
+            "seashells",
+            "by",
+            "the",
+            "sea",
+            "shore",
+            "the",
+            "shells",
+            "she",
+            "sells",
+            "are",
+            "surely",
+            "seashells",
+            "",
+            "séance",
+            "shé",
+        ];
+        let mut expected = data.clone();
+        MSD::sort(&mut expected);
+
+        Quick3String::sort(&mut data);
+        assert_eq!(data, expected);
+    }
+
+    #[test]
+    fn random() {
+        use rand::rngs::StdRng;
+        use rand::{Rng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(90);
+        let mut data: Vec<String> = (0..10_000)
+            .map(|_| {
+                let len = rng.gen_range(0..12);
+                (0..len)
+                    .map(|_| ['a', 'b', 'c', 'é', '東'][rng.gen_range(0..5)])
+                    .collect()
+            })
+            .collect();
+        let mut expected = data.clone();
+        expected.sort_unstable();
+
+        Quick3String::sort(&mut data);
+        assert_eq!(data, expected);
+    }
+
+    #[test]
+    fn long_common_prefix() {
+        let prefix = "ab".repeat(100);
+        let mut data: Vec<String> = (0..200)
+            .map(|i| format!("{}{}", prefix, (i * 7919) % 200))
+            .collect();
+        data.push(prefix.clone());
+        let mut expected = data.clone();
+        expected.sort_unstable();
+
+        Quick3String::sort(&mut data);
+        assert_eq!(data, expected);
+
+        // every string is equal, so each pass only moves on a byte
+        let mut same = vec!["x".repeat(2000); 1000];
+        Quick3String::sort(&mut same);
+        assert!(same.iter().all(|s| s.len() == 2000));
+    }
+
+    #[test]
+    fn empty_strings() {
+        let mut data = vec![
+            "b", "", "a", "", "ab", "", "ba", "a", "", "", "b", "", "aa", "", "", "ab", "", "b",
+        ];
+        let mut expected = data.clone();
+        expected.sort_unstable();
+
+        Quick3String::sort(&mut data);
+        assert_eq!(data, expected);
+        assert_eq!(&data[..9], &[""; 9]);
+
+        let mut empty: Vec<&str> = Vec::new();
+        Quick3String::sort(&mut empty);
+        assert!(empty.is_empty());
+    }
+}
@@ -0,0 +1,195 @@
+//! # Rabin-Karp substring search
+//!
+//! Compares a rolling hash (fingerprint) of each m-byte window of the text with the hash of the pattern.
+//! A hash match is then verified byte by byte (the Las Vegas version), so a collision can cost
+//! time but never produces a wrong answer.
+//! The pattern and text are matched byte by byte, so the returned index is a byte offset,
+//! and either may be a `&str` or binary data (`&[u8]`).
+
+const R: u64 = 256; // radix
+const Q: u64 = 4_294_967_291; // a large prime: the largest below 2^32, so products fit in u64
+
+pub struct RabinKarp {
+    pat: Vec<u8>,
+    pat_hash: u64, // pattern hash value
+    q: u64,        // modulus
+    rm: u64,       // R^(m-1) % q
+}
+
+impl RabinKarp {
+    pub fn new(pat: impl AsRef<[u8]>) -> Self {
+        Self::with_modulus(pat.as_ref(), Q)
+    }
+
+    // a small modulus forces collisions in tests
+    fn with_modulus(pat: impl AsRef<[u8]>, q: u64) -> Self {
+        let pat = pat.as_ref();
+        let m = pat.len();
+        // precompute R^(m-1) % q for use in removing leading digit
+        let mut rm = 1;
+        for _ in 1..m {
+            rm = (R * rm) % q;
+        }
+        RabinKarp {
+            pat: pat.to_vec(),
+            pat_hash: hash(pat, q),
+            q,
+            rm,
+        }
+    }
+
+    // Las Vegas version: does pat match txt[i..i + m]?
+    fn check(&self, txt: &[u8], i: usize) -> bool {
+        self.pat[..] == txt[i..i + self.pat.len()]
+    }
+
+    /// Returns the index of the first occurrence of the pattern in `txt`.
+    pub fn search(&self, txt: impl AsRef<[u8]>) -> Option<usize> {
+        self.scan(txt.as_ref(), true).first().copied()
+    }
+
+    /// Returns the index of every occurrence of the pattern in `txt`, overlapping ones included.
+    pub fn search_all(&self, txt: impl AsRef<[u8]>) -> Vec<usize> {
+        self.scan(txt.as_ref(), false)
+    }
+
+    // the verified matches in order, stopping at the first one if `first_only`
+    fn scan(&self, txt: &[u8], first_only: bool) -> Vec<usize> {
+        let (m, q) = (self.pat.len(), self.q);
+        let mut found = Vec::new();
+        if m > txt.len() {
+            return found;
+        }
+        if m == 0 {
+            // there is no window to roll; the empty pattern matches everywhere
+            let n = if first_only { 0 } else { txt.len() };
+            return (0..=n).collect();
+        }
+        let mut txt_hash = hash(&txt[..m], q);
+        if txt_hash == self.pat_hash && self.check(txt, 0) {
+            found.push(0);
+            if first_only {
+                return found;
+            }
+        }
+        for i in m..txt.len() {
+            // remove leading digit, add trailing digit, check for match
+            txt_hash = (txt_hash + q - self.rm * txt[i - m] as u64 % q) % q;
+            txt_hash = (txt_hash * R + txt[i] as u64) % q;
+            let offset = i - m + 1;
+            if txt_hash == self.pat_hash && self.check(txt, offset) {
+                found.push(offset);
+                if first_only {
+                    break;
+                }
+            }
+        }
+        found
+    }
+}
+
+// Horner's method
+fn hash(key: &[u8], q: u64) -> u64 {
+    key.iter().fold(0, |h, &c| (R * h + c as u64) % q)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn search() {
+        let rk = RabinKarp::new("abracadabra");
+        assert_eq!(rk.search("abracadabra is a magic word"), Some(0));
+        assert_eq!(rk.search("abacadabrabracabracadabrabrabracad"), Some(14));
+        assert_eq!(rk.search("say abracadabra"), Some(4));
+        assert_eq!(rk.search("abacadabrabracabracadabr"), None);
+        assert_eq!(RabinKarp::new("long pattern").search("short"), None);
+        assert_eq!(RabinKarp::new("").search("abc"), Some(0));
+    }
+
+    #[test]
+    fn single_byte() {
+        let rk = RabinKarp::new("d");
+        assert_eq!(rk.search("abcd"), Some(3));
+        assert_eq!(rk.search("d"), Some(0));
+        assert_eq!(rk.search("abc"), None);
+        assert_eq!(rk.search(""), None);
+    }
+
+    #[test]
+    fn collision() {
+        // with modulus 3, "ab" (hash 0) and "ba" (hash 0) collide
+        let rk = RabinKarp::with_modulus("ab", 3);
+        assert_eq!(hash(b"ab", 3), hash(b"ba", 3));
+        assert_eq!(rk.search("bab"), Some(1));
+        assert_eq!(rk.search("bacbacb"), None);
+
+        // every window collides with modulus 1
+        let rk = RabinKarp::with_modulus("needle", 1);
+        assert_eq!(rk.search("haystack with a needle"), Some(16));
+        assert_eq!(rk.search("haystack without"), None);
+    }
+
+    #[test]
+    fn search_all() {
+        let rk = RabinKarp::new("AACAA");
+        assert_eq!(rk.search_all("AACAACAAACAA"), vec![0, 3, 7]);
+        assert_eq!(RabinKarp::new("aa").search_all("aaaa"), vec![0, 1, 2]);
+        assert_eq!(RabinKarp::new("").search_all("ab"), vec![0, 1, 2]);
+        assert_eq!(RabinKarp::new("abc").search_all("ab"), vec![]);
+        assert_eq!(
+            RabinKarp::with_modulus("ab", 3).search_all("babab"),
+            vec![1, 3]
+        );
+    }
+
+    #[test]
+    fn cross_check() {
+        use crate::strings::boyer_moore::BoyerMoore;
+        use crate::strings::kmp::KMP;
+        use rand::rngs::StdRng;
+        use rand::{Rng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(95);
+        for _ in 0..500 {
+            // a small alphabet, so that matches and near-matches are common
+            let txt: String = (0..rng.gen_range(0..60))
+                .map(|_| ['a', 'b', 'c'][rng.gen_range(0..3)])
+                .collect();
+            let pat: String = (0..rng.gen_range(0..5))
+                .map(|_| ['a', 'b', 'c'][rng.gen_range(0..3)])
+                .collect();
+
+            let expected: Vec<usize> = (0..=txt.len())
+                .filter(|&i| txt[i..].starts_with(&pat))
+                .collect();
+            let (kmp, bm, rk) = (KMP::new(&pat), BoyerMoore::new(&pat), RabinKarp::new(&pat));
+            assert_eq!(kmp.search_all(&txt), expected);
+            assert_eq!(bm.search_all(&txt), expected);
+            assert_eq!(rk.search_all(&txt), expected);
+            assert_eq!(kmp.search(&txt), txt.find(&pat));
+            assert_eq!(bm.search(&txt), txt.find(&pat));
+            assert_eq!(rk.search(&txt), txt.find(&pat));
+        }
+
+        // binary data, with a modulus small enough to collide often
+        for _ in 0..200 {
+            let txt: Vec<u8> = (0..rng.gen_range(0..60))
+                .map(|_| [0, 1, 255][rng.gen_range(0..3)])
+                .collect();
+            let pat: Vec<u8> = (1..rng.gen_range(2..5))
+                .map(|_| [0, 1, 255][rng.gen_range(0..3)])
+                .collect();
+            let expected: Vec<usize> = txt
+                .windows(pat.len())
+                .enumerate()
+                .filter(|(_, w)| *w == &pat[..])
+                .map(|(i, _)| i)
+                .collect();
+            assert_eq!(KMP::new(&pat).search_all(&txt), expected);
+            assert_eq!(BoyerMoore::new(&pat).search_all(&txt), expected);
+            assert_eq!(RabinKarp::with_modulus(&pat, 7).search_all(&txt), expected);
+        }
+    }
+}
@@ -0,0 +1,348 @@
+//! # R-way trie symbol table
+//!
+//! A string symbol table implemented with a 256-way trie.
+//! Keys are processed byte by byte, so any `&str` is a valid key.
+
+const R: usize = 256; // radix
+
+type Link<V> = Option<Box<Node<V>>>;
+
+struct Node<V> {
+    val: Option<V>,
+    next: Vec<Link<V>>,
+}
+
+impl<V> Node<V> {
+    fn new() -> Self {
+        Node {
+            val: None,
+            next: (0..R).map(|_| None).collect(),
+        }
+    }
+}
+
+pub struct TrieST<V> {
+    root: Link<V>,
+    n: usize, // number of keys in trie
+}
+
+impl<V> TrieST<V> {
+    pub fn new() -> Self {
+        TrieST { root: None, n: 0 }
+    }
+
+    /// Returns the number of key-value pairs in this symbol table.
+    pub fn size(&self) -> usize {
+        self.n
+    }
+
+    /// Is this symbol table empty?
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    // returns the node at the end of `key`, if any
+    fn _get(&self, key: &[u8]) -> Option<&Node<V>> {
+        let mut x = self.root.as_deref()?;
+        for &c in key {
+            x = x.next[c as usize].as_deref()?;
+        }
+        Some(x)
+    }
+
+    /// Returns the value associated with the given key.
+    pub fn get(&self, key: &str) -> Option<&V> {
+        self._get(key.as_bytes()).and_then(|x| x.val.as_ref())
+    }
+
+    /// Does this symbol table contain the given key?
+    pub fn contains(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Inserts the key-value pair into the symbol table,
+    /// overwriting the old value with the new value if the key is already in the symbol table.
+    pub fn put(&mut self, key: &str, val: V) {
+        let mut x = self.root.get_or_insert_with(|| Box::new(Node::new()));
+        for &c in key.as_bytes() {
+            x = x.next[c as usize].get_or_insert_with(|| Box::new(Node::new()));
+        }
+        if x.val.is_none() {
+            self.n += 1;
+        }
+        x.val = Some(val);
+    }
+
+    /// Removes the key from the symbol table if the key is present.
+    /// Nodes left without a value or children are pruned.
+    pub fn delete(&mut self, key: &str) {
+        if Self::_delete(&mut self.root, key.as_bytes(), 0) {
+            self.n -= 1;
+        }
+    }
+
+    // returns true if a value was removed
+    fn _delete(x: &mut Link<V>, key: &[u8], d: usize) -> bool {
+        let removed = match x {
+            Some(node) => {
+                if d == key.len() {
+                    node.val.take().is_some()
+                } else {
+                    Self::_delete(&mut node.next[key[d] as usize], key, d + 1)
+                }
+            }
+            None => return false,
+        };
+        // remove subtrie rooted at x if it is completely empty
+        if let Some(node) = x {
+            if node.val.is_none() && node.next.iter().all(|l| l.is_none()) {
+                *x = None;
+            }
+        }
+        removed
+    }
+
+    /// Returns the string in the symbol table that is the longest prefix of `query`.
+    pub fn longest_prefix_of<'a>(&self, query: &'a str) -> Option<&'a str> {
+        let bytes = query.as_bytes();
+        let mut x = self.root.as_deref();
+        let mut length = None;
+        let mut d = 0;
+        while let Some(node) = x {
+            if node.val.is_some() {
+                length = Some(d);
+            }
+            if d == bytes.len() {
+                break;
+            }
+            x = node.next[bytes[d] as usize].as_deref();
+            d += 1;
+        }
+        // a key always ends on a char boundary, so the slice is valid UTF-8
+        length.map(|len| &query[..len])
+    }
+
+    /// Returns all of the keys in the symbol table, in sorted order.
+    pub fn keys(&self) -> Vec<String> {
+        self.keys_with_prefix("")
+    }
+
+    /// Returns all of the keys in the symbol table that start with `prefix`, in sorted order.
+    pub fn keys_with_prefix(&self, prefix: &str) -> Vec<String> {
+        let mut results = Vec::new();
+        if let Some(x) = self._get(prefix.as_bytes()) {
+            let mut pre = prefix.as_bytes().to_vec();
+            Self::collect(x, &mut pre, &mut results);
+        }
+        results
+    }
+
+    // all keys in the subtrie rooted at x with given prefix
+    fn collect(x: &Node<V>, pre: &mut Vec<u8>, results: &mut Vec<String>) {
+        if x.val.is_some() {
+            results.push(String::from_utf8(pre.clone()).unwrap());
+        }
+        for (c, next) in x.next.iter().enumerate() {
+            if let Some(next) = next {
+                pre.push(c as u8);
+                Self::collect(next, pre, results);
+                pre.pop();
+            }
+        }
+    }
+
+    /// Returns all of the keys in the symbol table that match `pattern`, in sorted order,
+    /// where the character `.` is a wildcard for any single character.
+    pub fn keys_that_match(&self, pattern: &str) -> Vec<String> {
+        let mut results = Vec::new();
+        if let Some(x) = self.root.as_deref() {
+            Self::collect_match(x, &mut Vec::new(), pattern.as_bytes(), &mut results);
+        }
+        results
+    }
+
+    fn collect_match(x: &Node<V>, pre: &mut Vec<u8>, pattern: &[u8], results: &mut Vec<String>) {
+        let c = match pattern.first() {
+            Some(&c) => c,
+            None => {
+                if x.val.is_some() {
+                    results.push(String::from_utf8(pre.clone()).unwrap());
+                }
+                return;
+            }
+        };
+        if c == b'.' {
+            // a character is a lead byte followed by its continuation bytes
+            for (lead, next) in x.next.iter().enumerate() {
+                let Some(width) = utf8_width(lead as u8) else {
+                    continue;
+                };
+                if let Some(next) = next {
+                    pre.push(lead as u8);
+                    Self::collect_char(next, pre, width - 1, &pattern[1..], results);
+                    pre.pop();
+                }
+            }
+        } else if let Some(next) = x.next[c as usize].as_deref() {
+            pre.push(c);
+            Self::collect_match(next, pre, &pattern[1..], results);
+            pre.pop();
+        }
+    }
+
+    // consumes the remaining continuation bytes of a wildcard character
+    fn collect_char(
+        x: &Node<V>,
+        pre: &mut Vec<u8>,
+        remaining: usize,
+        pattern: &[u8],
+        results: &mut Vec<String>,
+    ) {
+        if remaining == 0 {
+            Self::collect_match(x, pre, pattern, results);
+            return;
+        }
+        for (c, next) in x.next.iter().enumerate() {
+            if let Some(next) = next {
+                pre.push(c as u8);
+                Self::collect_char(next, pre, remaining - 1, pattern, results);
+                pre.pop();
+            }
+        }
+    }
+}
+
+// the number of bytes in a UTF-8 character starting with `lead`,
+// or `None` if it is a continuation byte
+pub(crate) fn utf8_width(lead: u8) -> Option<usize> {
+    match lead {
+        0x00..=0x7f => Some(1),
+        0xc0..=0xdf => Some(2),
+        0xe0..=0xef => Some(3),
+        0xf0..=0xf7 => Some(4),
+        _ => None,
+    }
+}
+
+impl<V> Default for TrieST<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn shells() -> TrieST<usize> {
+        let mut st = TrieST::new();
+        for (i, key) in "she sells sea shells by the sea shore"
+            .split(' ')
+            .enumerate()
+        {
+            st.put(key, i);
+        }
+        st
+    }
+
+    #[test]
+    fn put_get() {
+        let st = shells();
+        assert_eq!(st.size(), 7);
+        assert_eq!(st.get("she"), Some(&0));
+        assert_eq!(st.get("sells"), Some(&1));
+        assert_eq!(st.get("sea"), Some(&6));
+        assert_eq!(st.get("shells"), Some(&3));
+        assert_eq!(st.get("shore"), Some(&7));
+        assert_eq!(st.get("sh"), None);
+        assert_eq!(st.get("shell"), None);
+        assert!(st.contains("by"));
+        assert!(!st.contains("s"));
+    }
+
+    #[test]
+    fn longest_prefix_of() {
+        let st = shells();
+        assert_eq!(st.longest_prefix_of("shellsort"), Some("shells"));
+        assert_eq!(st.longest_prefix_of("shell"), Some("she"));
+        assert_eq!(st.longest_prefix_of("she"), Some("she"));
+        assert_eq!(st.longest_prefix_of("quicksort"), None);
+        assert_eq!(st.longest_prefix_of(""), None);
+    }
+
+    #[test]
+    fn delete() {
+        let mut st = shells();
+        st.delete("she");
+        assert_eq!(st.size(), 6);
+        assert!(!st.contains("she"));
+        assert!(st.contains("shells"));
+        assert_eq!(st.longest_prefix_of("shell"), None);
+
+        // deleting an absent key is a no-op
+        st.delete("she");
+        st.delete("xyz");
+        assert_eq!(st.size(), 6);
+
+        for key in ["sells", "sea", "shells", "by", "the", "shore"] {
+            st.delete(key);
+        }
+        assert!(st.is_empty());
+        assert!(st.root.is_none());
+    }
+
+    #[test]
+    fn keys() {
+        let mut st = shells();
+        assert_eq!(
+            st.keys(),
+            vec!["by", "sea", "sells", "she", "shells", "shore", "the"]
+        );
+        // keys that are prefixes of each other
+        st.put("s", 10);
+        st.put("shell", 11);
+        assert_eq!(st.longest_prefix_of("shellfish"), Some("shell"));
+        assert_eq!(st.longest_prefix_of("sh"), Some("s"));
+        st.delete("shells");
+        assert_eq!(st.size(), 8);
+        assert_eq!(st.keys_with_prefix("shel"), vec!["shell"]);
+        assert_eq!(TrieST::<usize>::new().keys(), Vec::<String>::new());
+    }
+
+    #[test]
+    fn keys_with_prefix() {
+        let st = shells();
+        assert_eq!(st.keys_with_prefix("sh"), vec!["she", "shells", "shore"]);
+        assert_eq!(st.keys_with_prefix("shells"), vec!["shells"]);
+        assert_eq!(st.keys_with_prefix("x"), Vec::<String>::new());
+        assert_eq!(
+            st.keys_with_prefix(""),
+            vec!["by", "sea", "sells", "she", "shells", "shore", "the"]
+        );
+    }
+
+    #[test]
+    fn keys_that_match() {
+        let mut st = shells();
+        assert_eq!(st.keys_that_match(".he"), vec!["she", "the"]);
+        assert_eq!(st.keys_that_match("s..."), Vec::<String>::new());
+        assert_eq!(st.keys_that_match("s...."), vec!["sells", "shore"]);
+        assert_eq!(st.keys_that_match("..."), vec!["sea", "she", "the"]);
+
+        // a wildcard matches a whole multi-byte character
+        st.put("café", 8);
+        st.put("cafés", 9);
+        assert_eq!(st.keys_that_match("caf."), vec!["café"]);
+        assert_eq!(st.keys_that_match("caf.."), vec!["cafés"]);
+    }
+
+    #[test]
+    fn empty_key() {
+        let mut st = TrieST::new();
+        st.put("", 1);
+        assert_eq!(st.get(""), Some(&1));
+        assert_eq!(st.longest_prefix_of("abc"), Some(""));
+        st.delete("");
+        assert!(st.is_empty());
+    }
+}
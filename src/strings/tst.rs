@@ -0,0 +1,413 @@
+//! # Ternary search trie
+//!
+//! A string symbol table implemented with a ternary search trie.
+//! Each node holds one byte and three links, so space depends on the keys
+//! rather than on the size of the alphabet. Like `TrieST`, keys are processed
+//! byte by byte, so non-ASCII keys share the nodes of common UTF-8 bytes and
+//! come out in byte-wise order, which for UTF-8 is also `char` order.
+//!
+//! The empty string is a valid key; having no node to end on, its value is kept
+//! apart from the trie.
+
+use std::cmp::Ordering;
+
+use super::trie_st::utf8_width;
+
+type Link<V> = Option<Box<Node<V>>>;
+
+struct Node<V> {
+    c: u8,          // byte
+    val: Option<V>, // value associated with the key ending here
+    left: Link<V>,  // keys whose d-th byte is less than c
+    mid: Link<V>,   // keys whose d-th byte is c
+    right: Link<V>, // keys whose d-th byte is greater than c
+}
+
+impl<V> Node<V> {
+    fn new(c: u8) -> Self {
+        Node {
+            c,
+            val: None,
+            left: None,
+            mid: None,
+            right: None,
+        }
+    }
+}
+
+pub struct TST<V> {
+    root: Link<V>,
+    empty: Option<V>, // value associated with the empty key
+    n: usize,         // size
+}
+
+impl<V> TST<V> {
+    pub fn new() -> Self {
+        TST {
+            root: None,
+            empty: None,
+            n: 0,
+        }
+    }
+
+    /// Returns the number of key-value pairs in this symbol table.
+    pub fn size(&self) -> usize {
+        self.n
+    }
+
+    /// Is this symbol table empty?
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    // returns the node whose mid-path spells `key`, if any
+    fn _get(&self, key: &str) -> Option<&Node<V>> {
+        let mut bytes = key.bytes();
+        let mut c = bytes.next()?;
+        let mut x = self.root.as_deref();
+        while let Some(node) = x {
+            x = match c.cmp(&node.c) {
+                Ordering::Less => node.left.as_deref(),
+                Ordering::Greater => node.right.as_deref(),
+                Ordering::Equal => match bytes.next() {
+                    Some(next) => {
+                        c = next;
+                        node.mid.as_deref()
+                    }
+                    None => return Some(node),
+                },
+            };
+        }
+        None
+    }
+
+    /// Returns the value associated with the given key.
+    pub fn get(&self, key: &str) -> Option<&V> {
+        if key.is_empty() {
+            return self.empty.as_ref();
+        }
+        self._get(key).and_then(|x| x.val.as_ref())
+    }
+
+    /// Does this symbol table contain the given key?
+    pub fn contains(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Inserts the key-value pair into the symbol table,
+    /// overwriting the old value with the new value if the key is already in the symbol table.
+    pub fn put(&mut self, key: &str, val: V) {
+        if key.is_empty() {
+            if self.empty.is_none() {
+                self.n += 1;
+            }
+            self.empty = Some(val);
+            return;
+        }
+        let key = key.as_bytes();
+        let mut x = &mut self.root;
+        let mut d = 0;
+        loop {
+            let node = x.get_or_insert_with(|| Box::new(Node::new(key[d])));
+            x = match key[d].cmp(&node.c) {
+                Ordering::Less => &mut node.left,
+                Ordering::Greater => &mut node.right,
+                Ordering::Equal if d + 1 < key.len() => {
+                    d += 1;
+                    &mut node.mid
+                }
+                Ordering::Equal => {
+                    if node.val.is_none() {
+                        self.n += 1;
+                    }
+                    node.val = Some(val);
+                    return;
+                }
+            };
+        }
+    }
+
+    /// Returns the string in the symbol table that is the longest prefix of `query`.
+    pub fn longest_prefix_of<'a>(&self, query: &'a str) -> Option<&'a str> {
+        let mut length = self.empty.as_ref().map(|_| 0);
+        let mut x = self.root.as_deref();
+        let bytes = query.as_bytes();
+        let mut d = 0;
+        while let (Some(node), Some(&c)) = (x, bytes.get(d)) {
+            x = match c.cmp(&node.c) {
+                Ordering::Less => node.left.as_deref(),
+                Ordering::Greater => node.right.as_deref(),
+                Ordering::Equal => {
+                    d += 1;
+                    if node.val.is_some() {
+                        length = Some(d);
+                    }
+                    node.mid.as_deref()
+                }
+            };
+        }
+        // a key always ends on a char boundary, so the slice is valid UTF-8
+        length.map(|len| &query[..len])
+    }
+
+    /// Returns all of the keys in the symbol table, in sorted order.
+    pub fn keys(&self) -> Vec<String> {
+        self.keys_with_prefix("")
+    }
+
+    /// Returns all of the keys in the symbol table that start with `prefix`, in sorted order.
+    pub fn keys_with_prefix(&self, prefix: &str) -> Vec<String> {
+        let mut results = Vec::new();
+        if prefix.is_empty() {
+            if self.empty.is_some() {
+                results.push(String::new());
+            }
+            Self::collect(&self.root, &mut Vec::new(), &mut results);
+        } else if let Some(x) = self._get(prefix) {
+            if x.val.is_some() {
+                results.push(prefix.to_string());
+            }
+            Self::collect(&x.mid, &mut prefix.as_bytes().to_vec(), &mut results);
+        }
+        results
+    }
+
+    // all keys in the subtrie rooted at x with given prefix
+    fn collect(x: &Link<V>, prefix: &mut Vec<u8>, results: &mut Vec<String>) {
+        if let Some(node) = x {
+            Self::collect(&node.left, prefix, results);
+            prefix.push(node.c);
+            if node.val.is_some() {
+                results.push(String::from_utf8(prefix.clone()).unwrap());
+            }
+            Self::collect(&node.mid, prefix, results);
+            prefix.pop();
+            Self::collect(&node.right, prefix, results);
+        }
+    }
+
+    /// Returns all of the keys in the symbol table that match `pattern`, in sorted order,
+    /// where the character `.` is a wildcard for any single character.
+    pub fn keys_that_match(&self, pattern: &str) -> Vec<String> {
+        let mut results = Vec::new();
+        if pattern.is_empty() {
+            if self.empty.is_some() {
+                results.push(String::new());
+            }
+        } else {
+            let pattern = pattern.as_bytes();
+            Self::collect_match(&self.root, &mut Vec::new(), pattern, 0, &mut results);
+        }
+        results
+    }
+
+    // keys in the subtrie rooted at x, after `prefix`, matching `pattern`; `pending` is the
+    // number of continuation bytes a wildcard still has to match before `pattern` resumes,
+    // and the two together are never empty
+    fn collect_match(
+        x: &Link<V>,
+        prefix: &mut Vec<u8>,
+        pattern: &[u8],
+        pending: usize,
+        results: &mut Vec<String>,
+    ) {
+        if let Some(node) = x {
+            let any = pending > 0 || pattern[0] == b'.';
+            if any || pattern[0] < node.c {
+                Self::collect_match(&node.left, prefix, pattern, pending, results);
+            }
+            // what is left to match after node.c
+            let rest = if pending > 0 {
+                Some((pattern, pending - 1))
+            } else if pattern[0] == b'.' {
+                // a wildcard is a lead byte followed by its continuation bytes
+                utf8_width(node.c).map(|width| (&pattern[1..], width - 1))
+            } else if pattern[0] == node.c {
+                Some((&pattern[1..], 0))
+            } else {
+                None
+            };
+            if let Some((pattern, pending)) = rest {
+                prefix.push(node.c);
+                if pattern.is_empty() && pending == 0 {
+                    if node.val.is_some() {
+                        results.push(String::from_utf8(prefix.clone()).unwrap());
+                    }
+                } else {
+                    Self::collect_match(&node.mid, prefix, pattern, pending, results);
+                }
+                prefix.pop();
+            }
+            if any || pattern[0] > node.c {
+                Self::collect_match(&node.right, prefix, pattern, pending, results);
+            }
+        }
+    }
+}
+
+impl<V> Default for TST<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn shells() -> TST<usize> {
+        let mut st = TST::new();
+        for (i, key) in "she sells sea shells by the sea shore"
+            .split(' ')
+            .enumerate()
+        {
+            st.put(key, i);
+        }
+        st
+    }
+
+    #[test]
+    fn put_get() {
+        let st = shells();
+        assert_eq!(st.size(), 7);
+        assert_eq!(st.get("she"), Some(&0));
+        assert_eq!(st.get("sells"), Some(&1));
+        assert_eq!(st.get("sea"), Some(&6));
+        assert_eq!(st.get("shells"), Some(&3));
+        assert_eq!(st.get("shore"), Some(&7));
+        assert_eq!(st.get("sh"), None);
+        assert_eq!(st.get("shell"), None);
+        assert_eq!(st.get(""), None);
+        assert!(st.contains("by"));
+        assert!(!st.contains("s"));
+    }
+
+    #[test]
+    fn longest_prefix_of() {
+        let st = shells();
+        assert_eq!(st.longest_prefix_of("shellsort"), Some("shells"));
+        assert_eq!(st.longest_prefix_of("shell"), Some("she"));
+        assert_eq!(st.longest_prefix_of("she"), Some("she"));
+        assert_eq!(st.longest_prefix_of("quicksort"), None);
+        assert_eq!(st.longest_prefix_of(""), None);
+    }
+
+    #[test]
+    fn keys_with_prefix() {
+        let st = shells();
+        assert_eq!(st.keys_with_prefix("sh"), vec!["she", "shells", "shore"]);
+        assert_eq!(st.keys_with_prefix("shells"), vec!["shells"]);
+        assert_eq!(st.keys_with_prefix("x"), Vec::<String>::new());
+        assert_eq!(
+            st.keys_with_prefix(""),
+            vec!["by", "sea", "sells", "she", "shells", "shore", "the"]
+        );
+    }
+
+    #[test]
+    fn non_ascii() {
+        // 'é' (U+00E9) and 'è' (U+00E8) share their first UTF-8 byte 0xC3
+        let mut st = TST::new();
+        st.put("café", 1);
+        st.put("cafè", 2);
+        st.put("caf", 3);
+        st.put("日本語", 4);
+        st.put("日本", 5);
+
+        assert_eq!(st.size(), 5);
+        assert_eq!(st.get("café"), Some(&1));
+        assert_eq!(st.get("cafè"), Some(&2));
+        assert_eq!(st.keys_with_prefix("caf"), vec!["caf", "cafè", "café"]);
+        assert_eq!(st.keys_with_prefix("日"), vec!["日本", "日本語"]);
+        assert_eq!(st.longest_prefix_of("cafés"), Some("café"));
+        assert_eq!(st.longest_prefix_of("日本人"), Some("日本"));
+    }
+
+    #[test]
+    fn keys_that_match() {
+        let mut st = shells();
+        assert_eq!(st.keys_that_match(".he"), vec!["she", "the"]);
+        assert_eq!(st.keys_that_match("s..."), Vec::<String>::new());
+        assert_eq!(st.keys_that_match("s...."), vec!["sells", "shore"]);
+        assert_eq!(st.keys_that_match("..."), vec!["sea", "she", "the"]);
+        assert_eq!(st.keys_that_match("sh.lls"), vec!["shells"]);
+        assert_eq!(st.keys_that_match(""), Vec::<String>::new());
+
+        st.put("café", 8);
+        st.put("cafés", 9);
+        assert_eq!(st.keys_that_match("caf."), vec!["café"]);
+        assert_eq!(st.keys_that_match("...é."), vec!["cafés"]);
+        // a wildcard is a whole character, however many bytes it takes
+        st.put("日本", 10);
+        st.put("日", 11);
+        assert_eq!(st.keys_that_match(".."), vec!["by", "日本"]);
+        assert_eq!(st.keys_that_match("."), vec!["日"]);
+        assert_eq!(st.keys_that_match("日."), vec!["日本"]);
+    }
+
+    #[test]
+    fn empty_key() {
+        let mut st = shells();
+        st.put("", 10);
+        assert_eq!(st.size(), 8);
+        st.put("", 11);
+        assert_eq!(st.size(), 8);
+        assert_eq!(st.get(""), Some(&11));
+        assert_eq!(st.longest_prefix_of("quicksort"), Some(""));
+        assert_eq!(st.longest_prefix_of("shell"), Some("she"));
+        assert_eq!(st.keys()[..2], ["", "by"]);
+        assert_eq!(st.keys_with_prefix("")[0], "");
+        assert_eq!(st.keys_that_match(""), vec![""]);
+        // and existing keys are overwritten without growing the size
+        st.put("sea", 12);
+        assert_eq!(st.size(), 8);
+        assert_eq!(st.get("sea"), Some(&12));
+    }
+
+    #[test]
+    fn same_as_trie_st() {
+        use crate::strings::trie_st::TrieST;
+        use rand::rngs::StdRng;
+        use rand::{Rng, SeedableRng};
+
+        let alphabet = ['a', 'b', 'c', 'd', 'é'];
+        let mut rng = StdRng::seed_from_u64(84);
+        let word = |rng: &mut StdRng, max: usize| -> String {
+            let len = rng.gen_range(0..=max);
+            (0..len)
+                .map(|_| alphabet[rng.gen_range(0..alphabet.len())])
+                .collect()
+        };
+
+        let mut tst = TST::new();
+        let mut trie = TrieST::new();
+        for i in 0..3000 {
+            let key = word(&mut rng, 7);
+            tst.put(&key, i);
+            trie.put(&key, i);
+        }
+        assert_eq!(tst.size(), trie.size());
+        assert_eq!(tst.keys(), trie.keys());
+        for _ in 0..500 {
+            let query = word(&mut rng, 9);
+            assert_eq!(tst.get(&query), trie.get(&query));
+            assert_eq!(
+                tst.longest_prefix_of(&query),
+                trie.longest_prefix_of(&query)
+            );
+            let prefix = word(&mut rng, 3);
+            assert_eq!(
+                tst.keys_with_prefix(&prefix),
+                trie.keys_with_prefix(&prefix)
+            );
+            let pattern: String = word(&mut rng, 6)
+                .chars()
+                .map(|c| if rng.gen_bool(0.4) { '.' } else { c })
+                .collect();
+            assert_eq!(
+                tst.keys_that_match(&pattern),
+                trie.keys_that_match(&pattern)
+            );
+        }
+    }
+}
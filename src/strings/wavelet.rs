@@ -0,0 +1,256 @@
+//! # Wavelet tree
+//!
+//! A static sequence of bytes that answers rank, select, access and range quantile queries.
+//!
+//! Each node covers a range of symbols `[lo, hi]` and stores one bit per symbol of its
+//! subsequence: 0 if it belongs to the lower half `[lo, mid]`, 1 if to the upper half.
+//! The subsequences of the halves (in their original order) are stored by the children.
+//! A query walks one root-to-leaf path with rank (down) or select (up) on each bit vector,
+//! so it takes O(log σ), where σ is the size of the range of symbols.
+
+use crate::fundamentals::bitvector::RankSelectBitVec;
+
+struct Node {
+    lo: u8,                                   // smallest symbol in this node's range
+    hi: u8,                                   // largest symbol in this node's range
+    n: usize,                                 // length of this node's subsequence
+    bits: RankSelectBitVec,                   // bits[i] = is the i-th symbol in the upper half?
+    children: Option<(Box<Node>, Box<Node>)>, // none for a leaf, where lo == hi
+}
+
+impl Node {
+    fn build(data: &[u8], lo: u8, hi: u8) -> Node {
+        if lo == hi {
+            return Node {
+                lo,
+                hi,
+                n: data.len(),
+                bits: RankSelectBitVec::new(Vec::new(), 0),
+                children: None,
+            };
+        }
+        let mid = lo + (hi - lo) / 2;
+        let bits = data.iter().map(|&c| c > mid).collect();
+        let (lower, upper): (Vec<u8>, Vec<u8>) = data.iter().partition(|&&c| c <= mid);
+        Node {
+            lo,
+            hi,
+            n: data.len(),
+            bits,
+            children: Some((
+                Box::new(Node::build(&lower, lo, mid)),
+                Box::new(Node::build(&upper, mid + 1, hi)),
+            )),
+        }
+    }
+
+    fn mid(&self) -> u8 {
+        self.lo + (self.hi - self.lo) / 2
+    }
+
+    fn select(&self, c: u8, k: usize) -> Option<usize> {
+        match &self.children {
+            None => (k < self.n).then_some(k),
+            Some((left, right)) => {
+                if c <= self.mid() {
+                    self.bits.select0(left.select(c, k)?)
+                } else {
+                    self.bits.select1(right.select(c, k)?)
+                }
+            }
+        }
+    }
+}
+
+pub struct WaveletTree {
+    root: Node,
+}
+
+impl WaveletTree {
+    pub fn new(data: &[u8]) -> Self {
+        let lo = data.iter().copied().min().unwrap_or(0);
+        let hi = data.iter().copied().max().unwrap_or(0);
+        WaveletTree {
+            root: Node::build(data, lo, hi),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.root.n
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.n == 0
+    }
+
+    /// Returns the symbol at position `i`.
+    pub fn access(&self, i: usize) -> u8 {
+        if i >= self.len() {
+            panic!("index {} is not between 0 and {}", i, self.len());
+        }
+        let mut node = &self.root;
+        let mut i = i;
+        while let Some((left, right)) = &node.children {
+            if node.bits.get(i) {
+                i = node.bits.rank1(i);
+                node = right;
+            } else {
+                i = node.bits.rank0(i);
+                node = left;
+            }
+        }
+        node.lo
+    }
+
+    /// Returns the number of occurrences of `c` in positions `[0, i)`.
+    pub fn rank(&self, c: u8, i: usize) -> usize {
+        self.validate_index(i, self.len());
+        let mut node = &self.root;
+        if c < node.lo || c > node.hi {
+            return 0;
+        }
+        let mut i = i;
+        while let Some((left, right)) = &node.children {
+            if c <= node.mid() {
+                i = node.bits.rank0(i);
+                node = left;
+            } else {
+                i = node.bits.rank1(i);
+                node = right;
+            }
+        }
+        i
+    }
+
+    /// Returns the position of the `k`-th occurrence (starting from 0) of `c`, if there are more than `k`.
+    pub fn select(&self, c: u8, k: usize) -> Option<usize> {
+        if c < self.root.lo || c > self.root.hi {
+            return None;
+        }
+        self.root.select(c, k)
+    }
+
+    /// Returns the `k`-th smallest symbol (starting from 0) in positions `[lo, hi)`.
+    pub fn quantile(&self, lo: usize, hi: usize, k: usize) -> u8 {
+        self.validate_index(hi, self.len());
+        if lo >= hi || k >= hi - lo {
+            panic!("{} is not between 0 and {}", k, hi.saturating_sub(lo));
+        }
+        let mut node = &self.root;
+        let (mut lo, mut hi, mut k) = (lo, hi, k);
+        while let Some((left, right)) = &node.children {
+            let (lo0, hi0) = (node.bits.rank0(lo), node.bits.rank0(hi));
+            if k < hi0 - lo0 {
+                (lo, hi) = (lo0, hi0);
+                node = left;
+            } else {
+                k -= hi0 - lo0;
+                (lo, hi) = (lo - lo0, hi - hi0);
+                node = right;
+            }
+        }
+        node.lo
+    }
+
+    fn validate_index(&self, i: usize, max: usize) {
+        if i > max {
+            panic!("index {} is not between 0 and {}", i, max);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    use super::*;
+
+    fn check(data: &[u8]) {
+        let wt = WaveletTree::new(data);
+        assert_eq!(wt.len(), data.len());
+        for (i, &c) in data.iter().enumerate() {
+            assert_eq!(wt.access(i), c);
+        }
+
+        for c in 0..=255u8 {
+            let positions = (0..data.len())
+                .filter(|&i| data[i] == c)
+                .collect::<Vec<usize>>();
+            for i in 0..=data.len() {
+                assert_eq!(wt.rank(c, i), positions.partition_point(|&p| p < i));
+            }
+            for (k, &p) in positions.iter().enumerate() {
+                assert_eq!(wt.select(c, k), Some(p));
+                assert_eq!(wt.access(wt.select(c, k).unwrap()), c);
+            }
+            assert_eq!(wt.select(c, positions.len()), None);
+        }
+    }
+
+    fn check_quantile(data: &[u8], lo: usize, hi: usize) {
+        let wt = WaveletTree::new(data);
+        let mut sorted = data[lo..hi].to_vec();
+        sorted.sort_unstable();
+        for (k, &c) in sorted.iter().enumerate() {
+            assert_eq!(wt.quantile(lo, hi, k), c);
+        }
+    }
+
+    #[test]
+    fn random() {
+        let mut rng = StdRng::seed_from_u64(32);
+        for (n, sigma) in [(1, 256), (50, 4), (200, 26), (300, 256)] {
+            let data = (0..n)
+                .map(|_| rng.gen_range(0..sigma) as u8)
+                .collect::<Vec<u8>>();
+            check(&data);
+            check_quantile(&data, 0, n);
+            for _ in 0..20 {
+                let lo = rng.gen_range(0..n);
+                let hi = rng.gen_range(lo + 1..=n);
+                check_quantile(&data, lo, hi);
+                // single element range
+                assert_eq!(WaveletTree::new(&data).quantile(lo, lo + 1, 0), data[lo]);
+            }
+        }
+    }
+
+    #[test]
+    fn single_symbol() {
+        let data = vec![b'a'; 100];
+        check(&data);
+        check_quantile(&data, 0, 100);
+        let wt = WaveletTree::new(&data);
+        assert_eq!(wt.rank(b'a', 40), 40);
+        assert_eq!(wt.rank(b'b', 40), 0);
+        assert_eq!(wt.select(b'a', 99), Some(99));
+        assert_eq!(wt.select(b'z', 0), None);
+    }
+
+    #[test]
+    fn empty() {
+        let wt = WaveletTree::new(&[]);
+        assert!(wt.is_empty());
+        assert_eq!(wt.rank(b'a', 0), 0);
+        assert_eq!(wt.select(0, 0), None);
+    }
+
+    #[test]
+    fn text() {
+        let data = b"abracadabra";
+        check(data);
+        let wt = WaveletTree::new(data);
+        assert_eq!(wt.rank(b'a', 11), 5);
+        assert_eq!(wt.select(b'r', 1), Some(9));
+        // the median of "cadab"
+        assert_eq!(wt.quantile(4, 9, 2), b'b');
+        assert_eq!(wt.quantile(0, 11, 10), b'r');
+    }
+
+    #[test]
+    #[should_panic]
+    fn quantile_out_of_range() {
+        WaveletTree::new(b"abc").quantile(0, 2, 2);
+    }
+}
@@ -0,0 +1,7 @@
+//! # Test support
+//!
+//! Helpers for tests and examples whose output should be the same on every run.
+//! Build a hash table with `DeterministicState` to fix the order of its keys:
+//! `LinearProbingHashST::with_hasher(4, DeterministicState)`.
+
+pub use crate::searching::deterministic_state::{DeterministicState, FnvHasher};
@@ -0,0 +1,176 @@
+use algs4_rust::pipelines::{
+    build_schedule, cheapest_route, compress_file, expand_file, spell_route, Algo,
+};
+
+const ROUTES: &str = "JFK MCO
+ORD DEN
+ORD HOU
+DFW PHX
+JFK ATL
+ORD DFW
+ORD PHX
+ATL HOU
+DEN PHX
+PHX LAX
+JFK ORD
+DEN LAS
+DFW HOU
+ORD ATL
+LAS LAX
+ATL MCO
+HOU MCO
+LAS PHX
+";
+
+const TINY_EWD: &str = "8
+15
+4 5 0.35
+5 4 0.35
+4 7 0.37
+5 7 0.28
+7 5 0.28
+5 1 0.32
+0 4 0.38
+0 2 0.26
+7 3 0.39
+1 3 0.29
+2 7 0.34
+6 2 0.40
+3 6 0.52
+6 0 0.58
+6 4 0.93
+";
+
+const JOBS: &str = "Algorithms/Theoretical CS/Databases/Scientific Computing
+Introduction to CS/Advanced Programming/Algorithms
+Advanced Programming/Scientific Computing
+Scientific Computing/Computational Biology
+Theoretical CS/Computational Biology/Artificial Intelligence
+Linear Algebra/Theoretical CS
+Calculus/Linear Algebra
+Artificial Intelligence/Neural Networks/Robotics/Machine Learning
+Machine Learning/Neural Networks
+";
+
+#[test]
+fn spell_route_routes() {
+    assert_eq!(
+        spell_route(ROUTES, "JFK", "LAS").unwrap(),
+        vec!["JFK", "ORD", "DEN", "LAS"]
+    );
+    assert_eq!(
+        spell_route(ROUTES, "LAX", "JFK").unwrap(),
+        vec!["LAX", "PHX", "ORD", "JFK"]
+    );
+    assert_eq!(spell_route(ROUTES, "ATL", "ATL").unwrap(), vec!["ATL"]);
+    assert_eq!(spell_route(ROUTES, "JFK", "SFO"), None);
+
+    let islands = format!("{}SFO SEA\n", ROUTES);
+    assert_eq!(spell_route(&islands, "JFK", "SEA"), None);
+}
+
+#[test]
+fn cheapest_route_tiny_ewd() {
+    let (dist, path) = cheapest_route(TINY_EWD, 0, 6).unwrap().unwrap();
+    assert!((dist - 1.51).abs() < 1e-9);
+    let hops: Vec<(usize, usize)> = path.iter().map(|e| (e.from(), e.to())).collect();
+    assert_eq!(hops, vec![(0, 2), (2, 7), (7, 3), (3, 6)]);
+
+    let (dist, path) = cheapest_route(TINY_EWD, 0, 0).unwrap().unwrap();
+    assert_eq!(dist, 0.0);
+    assert!(path.is_empty());
+
+    let isolated = "3\n1\n0 1 0.5\n";
+    assert!(cheapest_route(isolated, 0, 2).unwrap().is_none());
+    assert!(cheapest_route("8\n1\n0 1", 0, 1).is_err());
+}
+
+#[test]
+fn build_schedule_jobs() {
+    let schedule = build_schedule(JOBS).unwrap();
+    assert_eq!(schedule.len(), 13);
+    // every job comes before the jobs that depend on it
+    let position = |job: &str| schedule.iter().position(|s| s == job).unwrap();
+    for line in JOBS.lines() {
+        let mut jobs = line.split('/');
+        let first = position(jobs.next().unwrap());
+        for job in jobs {
+            assert!(first < position(job), "{}", line);
+        }
+    }
+    assert_eq!(
+        schedule,
+        vec![
+            "Calculus",
+            "Linear Algebra",
+            "Introduction to CS",
+            "Advanced Programming",
+            "Algorithms",
+            "Scientific Computing",
+            "Databases",
+            "Theoretical CS",
+            "Artificial Intelligence",
+            "Machine Learning",
+            "Robotics",
+            "Neural Networks",
+            "Computational Biology",
+        ]
+    );
+
+    assert_eq!(build_schedule("A/B\nB/C\nC/A\n"), None);
+    // no jobs is an empty schedule, not a cycle
+    assert_eq!(build_schedule(""), Some(vec![]));
+    assert_eq!(build_schedule("A\n"), Some(vec![String::from("A")]));
+}
+
+const ALGOS: [Algo; 3] = [Algo::Huffman, Algo::Lzw, Algo::RunLength];
+
+#[test]
+fn compress_file_round_trips() {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    let mut rng = StdRng::seed_from_u64(289);
+    let random: Vec<u8> = (0..5_000).map(|_| rng.gen()).collect();
+    let inputs: Vec<&[u8]> = vec![
+        b"",
+        b"A",
+        b"ABRACADABRA!",
+        b"TOBEORNOTTOBEORTOBEORNOT",
+        ROUTES.as_bytes(),
+        JOBS.as_bytes(),
+        &[0; 4_000],
+        &[0xFF; 4_000],
+        &random,
+    ];
+    for algo in ALGOS {
+        for &input in &inputs {
+            let compressed = compress_file(input, algo);
+            assert_eq!(expand_file(&compressed, algo), input, "{:?}", algo);
+        }
+    }
+}
+
+#[test]
+fn compress_file_sizes() {
+    // Huffman: 59 bits of trie, 32 of length and 28 of codewords, as in the book
+    assert_eq!(compress_file(b"ABRACADABRA!", Algo::Huffman).len(), 15);
+    // LZW: 16 12-bit codewords, as in the book, and the end of input: 204 bits
+    assert_eq!(
+        compress_file(b"TOBEORNOTTOBEORTOBEORNOT", Algo::Lzw).len(),
+        26
+    );
+    // run-length: 16 0s then 16 1s
+    assert_eq!(
+        compress_file(&[0x00, 0x00, 0xFF, 0xFF], Algo::RunLength),
+        vec![16, 16]
+    );
+
+    // a long run of one byte shrinks a lot under every algorithm
+    let zeros = vec![0; 4_000];
+    for algo in ALGOS {
+        assert!(compress_file(&zeros, algo).len() < 1_000, "{:?}", algo);
+    }
+    // but text with short runs of bits is expanded by run-length coding
+    assert!(compress_file(ROUTES.as_bytes(), Algo::RunLength).len() > ROUTES.len());
+}